@@ -0,0 +1,187 @@
+//! Compile-time validated JSON with comments literals for [`json_with_comments`].
+//!
+//! Unlike `json_with_comments::jsonc!`, these macros take real JSONC text, so `//` comments are kept,
+//! and syntax errors are reported as compile errors instead of runtime errors.
+//!
+//! - [`jsonc_str!`] parses a string literal.
+//! - [`include_jsonc!`] parses a file, the path is relative to the file that invokes the macro like [`include_str!`].
+//!
+//! Both macros expand to a [`json_with_comments::Value`] constructor,
+//! or to `json_with_comments::from_str::<T>(..)` that returns `json_with_comments::Result<T>` if type `T` is given.
+
+use std::path::PathBuf;
+
+use json_with_comments::{value::number::Number, Value};
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, LitStr, Token, Type,
+};
+
+/// Parse a JSONC string literal at compile time.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::jsonc;
+/// use json_with_comments_macros::jsonc_str;
+/// let value = jsonc_str!(r#"
+/// {
+///     // comments are allowed
+///     "name": "app",
+///     "ports": [80, 443,],
+/// }"#);
+/// assert_eq!(value, jsonc!({"name": "app", "ports": [80, 443]}));
+///
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     name: String,
+/// }
+/// let config = jsonc_str!(Config, r#"{"name": "app" /* typed */}"#).unwrap();
+/// assert_eq!(config.name, "app");
+/// ```
+///
+/// Invalid text is a compile error.
+/// ```compile_fail
+/// let value = json_with_comments_macros::jsonc_str!(r#"{"name": "app""#);
+/// ```
+#[proc_macro]
+pub fn jsonc_str(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let JsoncInput { ty, lit } = parse_macro_input!(input as JsoncInput);
+    expand(ty, &lit.value(), &lit, None).into()
+}
+
+/// Parse a JSONC file at compile time, the path is relative to the file that invokes the macro.
+///
+/// # Examples
+/// ```
+/// use json_with_comments_macros::include_jsonc;
+/// let defaults = include_jsonc!("../tests/data/defaults.jsonc");
+/// assert_eq!(defaults["server"]["port"], 8080.into());
+/// ```
+///
+/// Missing or invalid file is a compile error.
+/// ```compile_fail
+/// let value = json_with_comments_macros::include_jsonc!("nonexistent.jsonc");
+/// ```
+#[proc_macro]
+pub fn include_jsonc(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let JsoncInput { ty, lit } = parse_macro_input!(input as JsoncInput);
+    let path = resolve(&lit.value());
+    match std::fs::read_to_string(&path) {
+        Ok(text) => expand(ty, &text, &lit, Some(&path)).into(),
+        Err(e) => syn::Error::new(lit.span(), format!("cannot read {}: {e}", path.display())).to_compile_error().into(),
+    }
+}
+
+/// `"text"` or `Type, "text"`
+struct JsoncInput {
+    ty: Option<Type>,
+    lit: LitStr,
+}
+impl Parse for JsoncInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self { ty: None, lit: input.parse()? });
+        }
+        let ty = input.parse()?;
+        input.parse::<Token![,]>()?;
+        Ok(Self { ty: Some(ty), lit: input.parse()? })
+    }
+}
+
+/// Resolve `path` as an absolute path, because the expanded `include_str!` is also relative to the invoking file.
+fn resolve(path: &str) -> PathBuf {
+    let caller = proc_macro::Span::call_site().local_file();
+    let dir = match caller.as_ref().and_then(|file| file.parent()) {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default()),
+    };
+    std::env::current_dir().unwrap_or_default().join(dir).join(path)
+}
+
+fn expand(ty: Option<Type>, text: &str, lit: &LitStr, path: Option<&PathBuf>) -> TokenStream {
+    let value = match json_with_comments::from_str::<Value>(text) {
+        Ok(value) => value,
+        Err(e) => {
+            let message = match path {
+                Some(path) => format!("invalid JSON with comments in {}: {e}", path.display()),
+                None => format!("invalid JSON with comments in JSONC literal: {}", locate(&e.to_string(), lit)),
+            };
+            return syn::Error::new(lit.span(), message).to_compile_error();
+        }
+    };
+    // `include_str!` makes cargo rebuild when the file is changed
+    let text = match path {
+        Some(path) => {
+            let path = path.display().to_string();
+            quote! { ::core::include_str!(#path) }
+        }
+        None => quote! { #text },
+    };
+    match ty {
+        Some(ty) => quote! { ::json_with_comments::from_str::<#ty>(#text) },
+        None => match path {
+            Some(_) => {
+                let value = construct(&value);
+                quote! {{ const _: &str = #text; #value }}
+            }
+            None => construct(&value),
+        },
+    }
+}
+
+/// Convert the leading `line:column` (or `line:column-line:column`) of the error message, that is relative to the text
+/// of the literal, into the position in the invoking file, because the span of the error covers the whole literal.
+/// Line breaks written as escape sequences such as `\n` are not in the file, so they are not taken into account.
+fn locate(message: &str, lit: &LitStr) -> String {
+    let start = lit.span().unwrap();
+    let quote = lit.token().to_string().find('"').map_or(0, |i| i + 1);
+    let convert = |pos: &str| {
+        let (line, column) = pos.split_once(':')?;
+        let (line, column) = (line.parse::<usize>().ok()?, column.parse::<usize>().ok()?);
+        Some(match line {
+            1 => format!("{}:{}", start.line(), start.column() + quote + column - 1),
+            _ => format!("{}:{column}", start.line() + line - 1),
+        })
+    };
+    let converted = message.split_once(": ").and_then(|(range, rest)| {
+        let range = range.split('-').map(convert).collect::<Option<Vec<_>>>()?;
+        Some(format!("{}: {rest}", range.join("-")))
+    });
+    converted.unwrap_or_else(|| message.to_string())
+}
+
+fn construct(value: &Value) -> TokenStream {
+    match value {
+        Value::Object(map) => {
+            let entries = map.iter().map(|(k, v)| {
+                let v = construct(v);
+                quote! { (::std::string::String::from(#k), #v) }
+            });
+            quote! { ::json_with_comments::Value::Object(::core::iter::FromIterator::from_iter([#(#entries),*])) }
+        }
+        Value::Array(vec) => {
+            let elements = vec.iter().map(construct);
+            quote! { ::json_with_comments::Value::Array(::std::vec![#(#elements),*]) }
+        }
+        Value::Bool(b) => quote! { ::json_with_comments::Value::Bool(#b) },
+        Value::Null => quote! { ::json_with_comments::Value::Null },
+        Value::String(s) => quote! { ::json_with_comments::Value::String(::std::string::String::from(#s)) },
+        Value::Number(Number::Integer(i)) => {
+            let i = Literal::i64_suffixed(*i);
+            quote! { ::json_with_comments::Value::Number(::json_with_comments::value::number::Number::Integer(#i)) }
+        }
+        Value::Number(Number::Float(f)) if f.is_infinite() => {
+            let f = match f.is_sign_positive() {
+                true => quote! { ::core::f64::INFINITY },
+                false => quote! { ::core::f64::NEG_INFINITY },
+            };
+            quote! { ::json_with_comments::Value::Number(::json_with_comments::value::number::Number::Float(#f)) }
+        }
+        Value::Number(Number::Float(f)) => {
+            let f = Literal::f64_suffixed(*f);
+            quote! { ::json_with_comments::Value::Number(::json_with_comments::value::number::Number::Float(#f)) }
+        }
+    }
+}
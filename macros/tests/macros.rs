@@ -0,0 +1,75 @@
+use json_with_comments::{jsonc, Value};
+use json_with_comments_macros::{include_jsonc, jsonc_str};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+#[derive(Deserialize, Debug, PartialEq)]
+struct Defaults {
+    server: Server,
+    features: Vec<String>,
+    ratio: f64,
+    debug: bool,
+    token: Option<String>,
+}
+
+#[test]
+fn test_jsonc_str_value() {
+    let value: Value = jsonc_str!(
+        r#"
+        // leading comment
+        {
+            "string": "escaped \"quote\"\n",
+            "integer": -42, // negative
+            "float": 1.5e3,
+            "array": [true, false, null, [], {}],
+        }
+        "#
+    );
+    assert_eq!(
+        value,
+        jsonc!({
+            "string": "escaped \"quote\"\n",
+            "integer": -42,
+            "float": 1.5e3,
+            "array": [true, false, null, [], {}],
+        })
+    );
+    assert_eq!(jsonc_str!("null"), Value::Null);
+    assert_eq!(jsonc_str!("[1e999, -1e999]"), jsonc!([f64::INFINITY, f64::NEG_INFINITY]));
+}
+
+#[test]
+fn test_jsonc_str_typed() {
+    let server = jsonc_str!(Server, r#"{"host": "example.com", "port": 443 /* https */}"#).unwrap();
+    assert_eq!(server, Server { host: "example.com".to_string(), port: 443 });
+    assert!(jsonc_str!(Server, r#"{"host": "example.com", "port": -1}"#).is_err());
+}
+
+#[test]
+fn test_include_jsonc() {
+    let value = include_jsonc!("data/defaults.jsonc");
+    assert_eq!(
+        value,
+        jsonc!({
+            "server": {"host": "localhost", "port": 8080},
+            "features": ["a", "b"],
+            "ratio": -0.5,
+            "debug": false,
+            "token": null,
+        })
+    );
+
+    let defaults = include_jsonc!(Defaults, "data/defaults.jsonc").unwrap();
+    assert_eq!(defaults.server, Server { host: "localhost".to_string(), port: 8080 });
+    assert_eq!((defaults.features.len(), defaults.ratio, defaults.debug, defaults.token), (2, -0.5, false, None));
+}
+
+#[test]
+fn test_compile_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
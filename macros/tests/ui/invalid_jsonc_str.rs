@@ -0,0 +1,14 @@
+use json_with_comments_macros::jsonc_str;
+
+fn main() {
+    let _ = jsonc_str!(r#"{"name": "app"}"#);
+    let _ = jsonc_str!(r#"{"name": "app",, }"#);
+    let _ = jsonc_str!(
+        r#"
+        {
+            // comment
+            "name": "app",
+            "ports": [80 443],
+        }"#
+    );
+}
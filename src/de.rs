@@ -1,5 +1,10 @@
 pub mod access;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod encoding;
 pub mod position;
+pub mod push;
+pub mod span;
 pub mod token;
 
 use std::{fs::File, io, path::Path};
@@ -7,7 +12,10 @@ use std::{fs::File, io, path::Path};
 use serde::de;
 
 use crate::{
-    de::token::{raw::RawTokenizer, read::ReadTokenizer, Tokenizer},
+    de::{
+        encoding::Decoding,
+        token::{raw::RawTokenizer, read::ReadTokenizer, Tokenizer},
+    },
     Value,
 };
 
@@ -65,6 +73,29 @@ where
     from_tokenizer(StrTokenizer::new(s))
 }
 
+/// Parse a JSON with comments text as [`Value`], together with [`span::SourceMap`] that maps
+/// JSON Pointer of every value to the source location of its key and value.
+/// It is useful to report precise locations in validation that runs after parsing.
+///
+/// # Examples
+/// ```
+/// let text = r#"{
+///     "servers": [
+///         {"host": "localhost", "port": 0}, // invalid port
+///     ],
+/// }"#;
+/// let (value, spans) = json_with_comments::from_str_with_spans(text).unwrap();
+/// for (i, server) in value["servers"].as_vec().unwrap().iter().enumerate() {
+///     if server["port"] == 0.into() {
+///         let span = spans.value(&format!("/servers/{i}/port")).unwrap();
+///         assert_eq!(span.range.0.to_string(), "3:39");
+///     }
+/// }
+/// ```
+pub fn from_str_with_spans(s: &str) -> crate::Result<(Value, span::SourceMap)> {
+    span::parse(s)
+}
+
 /// Deserialize a JSON with comments text as type `D`.
 /// Deserialized instance may have raw string value that contain escape sequence.
 /// This function can deserialize any string value as borrowed `&str`.
@@ -151,6 +182,15 @@ where
     from_file(&File::open(p)?)
 }
 
+/// Deserialize a JSON with comments text of the given path as type `D`, that is decoded by [`Decoding`].
+/// See [`from_read_with`] also.
+pub fn from_path_with<D>(p: &Path, decoding: Decoding) -> crate::Result<D>
+where
+    D: de::DeserializeOwned,
+{
+    from_read_with(File::open(p)?, decoding)
+}
+
 /// Deserialize a JSON with comments text of the given file as type `D`.
 ///
 /// # Examples
@@ -248,6 +288,64 @@ where
     from_tokenizer(ReadTokenizer::new(read))
 }
 
+/// Deserialize a JSON with comments text from the given reader as type `D`, that is decoded by [`Decoding`].
+/// [`from_read`] is same as [`Decoding::Utf8`], that skips UTF-8 BOM but rejects UTF-16/32 input.
+/// [`Decoding::Detect`] detects UTF-16/32 input by BOM or RFC 4627 heuristic and transcodes it.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{de::encoding::Decoding, from_read, from_read_with, Value, jsonc};
+///
+/// let utf8 = b"\xEF\xBB\xBF{\"lang\": \"en\"}";
+/// assert_eq!(from_read::<_, Value>(&utf8[..]).unwrap(), jsonc!({"lang": "en"}));
+///
+/// let utf16le: Vec<u8> = "\u{FEFF}{\"lang\": \"日本語\"} // comment".encode_utf16().flat_map(u16::to_le_bytes).collect();
+/// assert!(from_read::<_, Value>(&utf16le[..]).is_err());
+/// assert_eq!(from_read_with::<_, Value>(&utf16le[..], Decoding::Detect).unwrap(), jsonc!({"lang": "日本語"}));
+/// ```
+pub fn from_read_with<R, D>(read: R, decoding: Decoding) -> crate::Result<D>
+where
+    R: io::Read,
+    D: de::DeserializeOwned,
+{
+    from_tokenizer(ReadTokenizer::with_decoding(read, decoding))
+}
+
+/// Deserialize a JSON with comments text from the given [`tokio::io::AsyncRead`] as type `D`.
+/// Input is read in chunks, and each chunk is tokenized by [`push::PushParser`] as soon as it is read,
+/// so the text is not buffered in memory and no thread is blocked while waiting for the reader.
+/// The parsed [`crate::Value`] is deserialized as `D` at the end of the input,
+/// so errors of the type `D` are reported without the position, unlike [`from_read`].
+///
+/// # Examples
+/// ```
+/// use serde::Deserialize;
+/// use tokio::io::AsyncWriteExt;
+/// #[derive(Deserialize)]
+/// struct Product {
+///     name: String,
+///     price: u32,
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (mut client, server) = tokio::io::duplex(16);
+/// tokio::spawn(async move {
+///     client.write_all(br#"{"name": "candy", "price": 100} // async"#).await.unwrap();
+/// });
+/// let product: Product = json_with_comments::from_async_read(server).await.unwrap();
+/// assert_eq!(product.name, "candy");
+/// assert_eq!(product.price, 100);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn from_async_read<R, D>(read: R) -> crate::Result<D>
+where
+    R: tokio::io::AsyncRead + Unpin,
+    D: de::DeserializeOwned,
+{
+    D::deserialize(asynchronous::read_value(read).await?)
+}
+
 /// TODO doc
 pub fn from_raw<'de, D>(s: &'de [u8]) -> crate::Result<D>
 where
@@ -55,7 +55,7 @@ where
     }
 }
 
-impl<'de, 'a, T> de::Deserializer<'de> for &'a mut JsoncDeserializer<'de, T>
+impl<'de, T> de::Deserializer<'de> for &mut JsoncDeserializer<'de, T>
 where
     T: 'de + Tokenizer<'de>,
 {
@@ -236,11 +236,21 @@ where
         self.deserialize_unit(visitor)
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        if name == crate::value::raw::TOKEN {
+            self.tokenizer.skip_whitespace()?;
+            self.tokenizer.start_capture()?;
+            de::Deserializer::deserialize_ignored_any(&mut *self, IgnoredAny)?;
+            match self.tokenizer.end_capture()? {
+                ParsedString::Borrowed(s) => visitor.visit_borrowed_str(s),
+                ParsedString::Owned(s) => visitor.visit_string(s),
+            }
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
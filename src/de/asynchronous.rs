@@ -0,0 +1,76 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{error::Ensure, Value};
+
+use super::push::{PushParser, ValueBuilder};
+
+/// Size of a chunk read from the async reader at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Read chunks from the async reader until EOF, and parse them by [`PushParser`] while they are read.
+/// Only a chunk of the input is buffered at once, and the parsed value is built by [`ValueBuilder`].
+pub async fn read_value<R>(mut read: R) -> crate::Result<Value>
+where
+    R: AsyncRead + Unpin,
+{
+    let (mut parser, mut builder) = (PushParser::new(), ValueBuilder::new());
+    let mut chunk = vec![0; CHUNK_SIZE];
+    let mut value = None;
+    loop {
+        match read.read(&mut chunk).await? {
+            0 => break,
+            n => value = parser.feed(&chunk[..n])?.events.into_iter().fold(value, |v, e| builder.push(e).or(v)),
+        }
+    }
+    let value = parser.finish()?.into_iter().fold(value, |v, e| builder.push(e).or(v));
+    Ok(value.ok_or(Ensure::CompletedRoot)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use tokio::io::AsyncWriteExt;
+
+    use crate::{jsonc, Value};
+
+    #[tokio::test]
+    async fn test_from_async_read_duplex() {
+        let (mut client, server) = tokio::io::duplex(64);
+        let writer = tokio::spawn(async move {
+            client.write_all(b"// config\n{\n  \"name\": \"app\",\n  \"ports\": [").await.unwrap();
+            for port in 0..1000 {
+                client.write_all(format!("{port}, ").as_bytes()).await.unwrap();
+            }
+            client.write_all(b"],\n  \"nested\": {\"key\": \"value\"}, /* trailing */\n}\n").await.unwrap();
+        });
+
+        #[derive(Deserialize)]
+        struct Config {
+            name: String,
+            ports: Vec<u16>,
+            nested: Value,
+        }
+        let config: Config = crate::from_async_read(server).await.unwrap();
+        writer.await.unwrap();
+        assert_eq!(config.name, "app");
+        assert_eq!(config.ports, (0..1000).collect::<Vec<_>>());
+        assert_eq!(config.nested, jsonc!({"key": "value"}));
+    }
+
+    #[tokio::test]
+    async fn test_from_async_read_errors() {
+        let (mut client, server) = tokio::io::duplex(16);
+        let writer = tokio::spawn(async move {
+            // writer may fail when the reader is dropped after the syntax error
+            let _ = client.write_all(b"[1, 2 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18]").await;
+        });
+        let err = crate::from_async_read::<_, Vec<i32>>(server).await.unwrap_err();
+        assert!(err.to_string().starts_with("1:7: "), "{err}");
+        writer.await.unwrap();
+
+        let err = crate::from_async_read::<_, Value>(&b"[\"unterminated"[..]).await.unwrap_err();
+        assert!(err.into_inner().downcast_ref::<crate::error::SyntaxError>().is_some());
+        assert!(crate::from_async_read::<_, Value>(&b"1 2"[..]).await.is_err());
+        assert_eq!(crate::from_async_read::<_, Value>(&b"\xEF\xBB\xBF{}"[..]).await.unwrap(), jsonc!({}));
+    }
+}
@@ -0,0 +1,272 @@
+use std::{fmt, io};
+
+use crate::error::EncodingError;
+
+/// UTF-8 byte order mark, that is skipped at the start of the input.
+pub const BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// Unicode encoding of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Encoding::Utf8 => write!(f, "UTF-8"),
+            Encoding::Utf16Le => write!(f, "UTF-16LE"),
+            Encoding::Utf16Be => write!(f, "UTF-16BE"),
+            Encoding::Utf32Le => write!(f, "UTF-32LE"),
+            Encoding::Utf32Be => write!(f, "UTF-32BE"),
+        }
+    }
+}
+impl Encoding {
+    /// Detect the encoding from the first (up to) 4 bytes of the input, by BOM or by the heuristic of RFC 4627,
+    /// that the first two characters are ASCII so the pattern of null bytes tells the encoding.
+    /// Null byte never appears in UTF-8 JSON with comments text, so other inputs are detected as UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::de::encoding::Encoding;
+    /// assert_eq!(Encoding::detect(b"\xFF\xFE/\x00"), Encoding::Utf16Le);
+    /// assert_eq!(Encoding::detect(b"\x00{\x00}"), Encoding::Utf16Be);
+    /// assert_eq!(Encoding::detect(b"[\x00\x00\x00"), Encoding::Utf32Le);
+    /// assert_eq!(Encoding::detect(b"\xEF\xBB\xBF{"), Encoding::Utf8);
+    /// assert_eq!(Encoding::detect(b"{}"), Encoding::Utf8);
+    /// ```
+    pub fn detect(head: &[u8]) -> Self {
+        match head {
+            [0x00, 0x00, 0xFE, 0xFF, ..] => Encoding::Utf32Be,
+            [0xFF, 0xFE, 0x00, 0x00, ..] => Encoding::Utf32Le,
+            [0xFE, 0xFF, ..] => Encoding::Utf16Be,
+            [0xFF, 0xFE, ..] => Encoding::Utf16Le,
+            [0x00, 0x00, 0x00, _, ..] => Encoding::Utf32Be,
+            [_, 0x00, 0x00, 0x00, ..] => Encoding::Utf32Le,
+            [0x00, ..] => Encoding::Utf16Be,
+            [_, 0x00, ..] => Encoding::Utf16Le,
+            _ => Encoding::Utf8,
+        }
+    }
+
+    fn unit(&self) -> usize {
+        match self {
+            Encoding::Utf8 => 1,
+            Encoding::Utf16Le | Encoding::Utf16Be => 2,
+            Encoding::Utf32Le | Encoding::Utf32Be => 4,
+        }
+    }
+}
+
+/// How to decode the input of [`crate::from_read_with`] and [`crate::from_path_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Decoding {
+    /// Input must be UTF-8, the leading UTF-8 BOM is skipped, and UTF-16/32 input is rejected with clear error.
+    #[default]
+    Utf8,
+    /// Detect UTF-8, UTF-16 or UTF-32 by [`Encoding::detect`], and transcode it into UTF-8.
+    Detect,
+}
+
+/// Reader that transcodes the input into UTF-8 by [`Decoding`].
+/// Transcoded BOM is kept as UTF-8 BOM, and it is skipped by the tokenizer.
+#[derive(Debug)]
+pub struct Transcoder<R> {
+    read: R,
+    decoding: Decoding,
+    encoding: Option<Encoding>,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    consumed: usize,
+}
+impl<R: io::Read> Transcoder<R> {
+    pub fn new(read: R, decoding: Decoding) -> Self {
+        Self { read, decoding, encoding: None, input: Vec::new(), output: Vec::new(), consumed: 0 }
+    }
+
+    /// Read the first bytes and detect the encoding, they are kept as input.
+    fn detect(&mut self) -> io::Result<Encoding> {
+        let mut head = [0; 4];
+        let mut len = 0;
+        while len < head.len() {
+            match self.read.read(&mut head[len..])? {
+                0 => break,
+                n => len += n,
+            }
+        }
+        self.input.extend_from_slice(&head[..len]);
+        match (self.decoding, Encoding::detect(&head[..len])) {
+            (_, Encoding::Utf8) => Ok(Encoding::Utf8),
+            (Decoding::Utf8, encoding) => Err(invalid_data(EncodingError::TranscodingDisabled { encoding })),
+            (Decoding::Detect, encoding) => Ok(encoding),
+        }
+    }
+
+    /// Decode complete code units of the input into the output, and returns `false` if nothing is decoded at EOF.
+    fn fill(&mut self, encoding: Encoding) -> io::Result<bool> {
+        let mut buf = [0; 1024];
+        let n = self.read.read(&mut buf)?;
+        self.input.extend_from_slice(&buf[..n]);
+
+        let unit = encoding.unit();
+        let mut units = self.input.chunks_exact(unit).peekable();
+        let mut decoded = 0;
+        while let Some(code) = units.next() {
+            let c = match encoding {
+                Encoding::Utf16Le | Encoding::Utf16Be => {
+                    let u16_of = |b: &[u8]| match encoding {
+                        Encoding::Utf16Le => u16::from_le_bytes([b[0], b[1]]),
+                        _ => u16::from_be_bytes([b[0], b[1]]),
+                    };
+                    let high = u16_of(code);
+                    match (high, units.peek()) {
+                        (0xD800..=0xDBFF, None) => break, // wait for the low surrogate
+                        (0xD800..=0xDBFF, Some(&low)) => {
+                            let low = u16_of(low);
+                            units.next();
+                            decoded += unit;
+                            char::decode_utf16([high, low]).next().and_then(Result::ok).ok_or(high as u32)
+                        }
+                        _ => char::decode_utf16([high]).next().and_then(Result::ok).ok_or(high as u32),
+                    }
+                }
+                _ => {
+                    let code = [code[0], code[1], code[2], code[3]];
+                    let scalar = match encoding {
+                        Encoding::Utf32Le => u32::from_le_bytes(code),
+                        _ => u32::from_be_bytes(code),
+                    };
+                    char::from_u32(scalar).ok_or(scalar)
+                }
+            };
+            let c = c.map_err(|unit| invalid_data(EncodingError::InvalidCodeUnit { encoding, unit }))?;
+            decoded += unit;
+            self.output.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        self.input.drain(..decoded);
+
+        match (n, self.input.is_empty()) {
+            (0, false) => Err(invalid_data(EncodingError::IncompleteCodeUnit { encoding })),
+            _ => Ok(n > 0 || !self.output.is_empty()),
+        }
+    }
+}
+impl<R: io::Read> io::Read for Transcoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let encoding = match self.encoding {
+            Some(encoding) => encoding,
+            None => {
+                let encoding = self.detect()?;
+                *self.encoding.insert(encoding)
+            }
+        };
+        if encoding == Encoding::Utf8 {
+            if self.input.is_empty() {
+                return self.read.read(buf);
+            }
+            let n = buf.len().min(self.input.len());
+            buf[..n].copy_from_slice(&self.input[..n]);
+            self.input.drain(..n);
+            return Ok(n);
+        }
+
+        while self.consumed == self.output.len() {
+            (self.output, self.consumed) = (Vec::new(), 0);
+            if !self.fill(encoding)? {
+                return Ok(0);
+            }
+        }
+        let n = buf.len().min(self.output.len() - self.consumed);
+        buf[..n].copy_from_slice(&self.output[self.consumed..self.consumed + n]);
+        self.consumed += n;
+        Ok(n)
+    }
+}
+
+fn invalid_data(err: EncodingError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    /// Reader that returns at most `chunk` bytes at once, to split code units.
+    struct Chunked<'a>(&'a [u8], usize);
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = self.1.min(buf.len()).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn transcode(input: &[u8], chunk: usize) -> io::Result<String> {
+        let mut output = Vec::new();
+        Transcoder::new(Chunked(input, chunk), Decoding::Detect).read_to_end(&mut output)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_transcode_utf16_utf32() {
+        let text = "[\"ascii\", \"日本語\", \"🦀\"] // comment";
+        let inputs: [(Vec<u8>, Encoding); 4] = [
+            (text.encode_utf16().flat_map(u16::to_le_bytes).collect(), Encoding::Utf16Le),
+            (text.encode_utf16().flat_map(u16::to_be_bytes).collect(), Encoding::Utf16Be),
+            (text.chars().flat_map(|c| (c as u32).to_le_bytes()).collect(), Encoding::Utf32Le),
+            (text.chars().flat_map(|c| (c as u32).to_be_bytes()).collect(), Encoding::Utf32Be),
+        ];
+        for (input, encoding) in inputs {
+            assert_eq!(Encoding::detect(&input), encoding);
+            for chunk in [1, 3, 5, 1024] {
+                assert_eq!(transcode(&input, chunk).unwrap(), text, "{encoding} {chunk}");
+            }
+        }
+
+        let with_bom: Vec<u8> = "\u{FEFF}{}".encode_utf16().flat_map(u16::to_be_bytes).collect();
+        assert_eq!(transcode(&with_bom, 1).unwrap(), "\u{FEFF}{}");
+        assert_eq!(transcode("{\"utf-8\": 1}".as_bytes(), 2).unwrap(), "{\"utf-8\": 1}");
+        assert_eq!(transcode(b"1", 1).unwrap(), "1");
+        assert_eq!(transcode(b"", 1).unwrap(), "");
+    }
+
+    #[test]
+    fn test_transcode_errors() {
+        let lone_surrogate = [b'[', 0x00, b'"', 0x00, 0x00, 0xD8, b'"', 0x00, b']', 0x00];
+        let err = transcode(&lone_surrogate, 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid UTF-16LE code unit 0xD800");
+
+        let incomplete = [b'[', 0x00, b']'];
+        assert_eq!(transcode(&incomplete, 1).unwrap_err().to_string(), "UTF-16LE input ends with incomplete code unit");
+
+        let out_of_range = [0x00, 0x00, 0x00, b'1', 0x00, 0x11, 0x00, 0x00];
+        assert_eq!(transcode(&out_of_range, 8).unwrap_err().to_string(), "invalid UTF-32BE code unit 0x110000");
+
+        let utf16: Vec<u8> = "[]".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let err = Transcoder::new(&utf16[..], Decoding::Utf8).read_to_end(&mut Vec::new()).unwrap_err();
+        assert!(err.to_string().starts_with("UTF-16LE input is detected"));
+    }
+
+    #[test]
+    fn test_skip_utf8_bom() {
+        let text = "\u{FEFF}[1, \"two\"]";
+        assert_eq!(crate::from_str::<(i32, &str)>(text).unwrap(), (1, "two"));
+        assert_eq!(crate::from_read::<_, (i32, String)>(text.as_bytes()).unwrap(), (1, "two".to_string()));
+        assert_eq!(crate::from_str_raw::<(i32, &str)>(text).unwrap(), (1, "two"));
+
+        let (_, spans) = crate::from_str_with_spans(text).unwrap();
+        let two = spans.value("/1").unwrap();
+        assert_eq!(&text[two.offset.clone()], "\"two\"");
+        assert_eq!((two.range.0.column_utf8, two.range.0.column_chars), (4, 4));
+
+        assert!(crate::from_str::<Vec<i32>>("[\u{FEFF}1]").is_err());
+    }
+}
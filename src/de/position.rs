@@ -1,18 +1,83 @@
-pub type Position = (usize, usize);
+use std::{collections::VecDeque, fmt};
+
+use super::encoding::BOM;
+
+/// Position of a byte in the source text, all fields are 0-based.
+/// Line breaks are `\n`, and `\r` is not counted as a column, so `\r\n` is handled also.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::de::position::Position;
+/// let err = json_with_comments::from_str::<Vec<String>>("[\r\n  \"日本🦀\", x]").unwrap_err();
+/// assert!(err.to_string().starts_with("2:10: "));
+///
+/// let (_, spans) = json_with_comments::from_str_with_spans("[\r\n  \"日本🦀\", null]").unwrap();
+/// let null = spans.value("/1").unwrap().range.0;
+/// assert_eq!(null, Position { offset: 19, line: 1, column_utf8: 16, column_utf16: 10, column_chars: 9 });
+/// assert_eq!(null.to_string(), "2:10");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Position {
+    /// Byte offset from the start of the text.
+    pub offset: usize,
+    /// Line number.
+    pub line: usize,
+    /// Column counted in UTF-8 bytes.
+    pub column_utf8: usize,
+    /// Column counted in UTF-16 code units, that is used by LSP.
+    pub column_utf16: usize,
+    /// Column counted in characters (Unicode scalar values).
+    pub column_chars: usize,
+}
+impl fmt::Display for Position {
+    /// Display as 1-based `line:column`, the column is counted in characters.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column_chars + 1)
+    }
+}
+impl Position {
+    /// Advance the position by byte `c`, and `pending` is (remaining continuation bytes, length) of a character.
+    /// Bytes of a multibyte character have the same UTF-16 and character columns, that advance after its last byte.
+    pub(crate) fn advance(&mut self, c: u8, pending: &mut (usize, usize)) {
+        self.offset += 1;
+        *pending = match (c, *pending) {
+            (b'\n', _) => {
+                self.line += 1;
+                (self.column_utf8, self.column_utf16, self.column_chars) = (0, 0, 0);
+                return;
+            }
+            (b'\r', _) => return,
+            (0x80..=0xBF, (remaining, len)) if remaining > 0 => (remaining - 1, len),
+            (0xC0..=0xDF, _) => (1, 2),
+            (0xE0..=0xEF, _) => (2, 3),
+            (0xF0..=0xF7, _) => (3, 4),
+            _ => (0, 1),
+        };
+        self.column_utf8 += 1;
+        if let (0, len) = *pending {
+            self.column_utf16 += if len == 4 { 2 } else { 1 };
+            self.column_chars += 1;
+        }
+    }
+}
+
 pub type PosRange = (Position, Position); // TODO RangeBound
 
-pub struct RowColIterator<I> {
+/// Iterator of bytes with their [`Position`], that skips the leading UTF-8 BOM.
+/// The skipped BOM is counted in [`Position::offset`], but not counted in columns.
+pub struct RowColIterator<I: Iterator> {
     iter: I,
-    row: usize,
-    col: usize,
+    pos: Position,
+    pending: (usize, usize),
+    head: Option<VecDeque<I::Item>>,
 }
-impl<I> RowColIterator<I> {
+impl<I: Iterator> RowColIterator<I> {
     pub fn new(iter: I) -> Self {
-        RowColIterator { iter, row: 0, col: 0 }
+        RowColIterator { iter, pos: Position::default(), pending: (0, 0), head: None }
     }
 
     pub fn pos(&self) -> Position {
-        (self.row, self.col)
+        self.pos
     }
 }
 impl<I, E> Iterator for RowColIterator<I>
@@ -22,18 +87,17 @@ where
     type Item = (Position, I::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|r| {
+        let head = self.head.get_or_insert_with(|| {
+            let head: VecDeque<_> = self.iter.by_ref().take(BOM.len()).collect();
+            if head.iter().map(|r| r.as_ref().ok()).eq(BOM.iter().map(Some)) {
+                self.pos.offset = BOM.len();
+                return VecDeque::new();
+            }
+            head
+        });
+        head.pop_front().or_else(|| self.iter.next()).map(|r| {
             let pos = self.pos();
-            let res = r.map(|c| {
-                match c {
-                    b'\n' => {
-                        self.row += 1;
-                        self.col = 0;
-                    }
-                    _ => self.col += 1,
-                };
-                c
-            });
+            let res = r.inspect(|&c| self.pos.advance(c, &mut self.pending));
             (pos, res)
         })
     }
@@ -45,6 +109,10 @@ mod tests {
 
     use super::*;
 
+    fn row_col((pos, c): (Position, std::io::Result<u8>)) -> ((usize, usize), u8) {
+        ((pos.line, pos.column_utf8), c.unwrap())
+    }
+
     #[test]
     fn behavior_row_col_iterator() {
         // [
@@ -54,43 +122,60 @@ mod tests {
         // ]
         let target = ["[", r#"  "foo","#, r#"  "bar","#, r#"  "baz""#, "]"].join("\n");
         let reader = BufReader::new(target.as_bytes());
-        let mut iter = RowColIterator::new(reader.bytes());
-
-        assert!(matches!(iter.next(), Some(((0, 0), Ok(b'[')))));
-        assert!(matches!(iter.next(), Some(((0, 1), Ok(b'\n')))));
-
-        assert!(matches!(iter.next(), Some(((1, 0), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((1, 1), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((1, 2), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((1, 3), Ok(b'f')))));
-        assert!(matches!(iter.next(), Some(((1, 4), Ok(b'o')))));
-        assert!(matches!(iter.next(), Some(((1, 5), Ok(b'o')))));
-        assert!(matches!(iter.next(), Some(((1, 6), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((1, 7), Ok(b',')))));
-        assert!(matches!(iter.next(), Some(((1, 8), Ok(b'\n')))));
-
-        assert!(matches!(iter.next(), Some(((2, 0), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((2, 1), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((2, 2), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((2, 3), Ok(b'b')))));
-        assert!(matches!(iter.next(), Some(((2, 4), Ok(b'a')))));
-        assert!(matches!(iter.next(), Some(((2, 5), Ok(b'r')))));
-        assert!(matches!(iter.next(), Some(((2, 6), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((2, 7), Ok(b',')))));
-        assert!(matches!(iter.next(), Some(((2, 8), Ok(b'\n')))));
-
-        assert!(matches!(iter.next(), Some(((3, 0), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((3, 1), Ok(b' ')))));
-        assert!(matches!(iter.next(), Some(((3, 2), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((3, 3), Ok(b'b')))));
-        assert!(matches!(iter.next(), Some(((3, 4), Ok(b'a')))));
-        assert!(matches!(iter.next(), Some(((3, 5), Ok(b'z')))));
-        assert!(matches!(iter.next(), Some(((3, 6), Ok(b'"')))));
-        assert!(matches!(iter.next(), Some(((3, 7), Ok(b'\n')))));
-
-        assert!(matches!(iter.next(), Some(((4, 0), Ok(b']')))));
-        assert!(matches!(iter.next(), None));
-        assert!(matches!(iter.next(), None));
-        assert!(matches!(iter.next(), None));
+        let mut iter = RowColIterator::new(reader.bytes()).map(row_col);
+
+        assert_eq!(iter.next(), Some(((0, 0), b'[')));
+        assert_eq!(iter.next(), Some(((0, 1), b'\n')));
+
+        assert_eq!(iter.next(), Some(((1, 0), b' ')));
+        assert_eq!(iter.next(), Some(((1, 1), b' ')));
+        assert_eq!(iter.next(), Some(((1, 2), b'"')));
+        assert_eq!(iter.next(), Some(((1, 3), b'f')));
+        assert_eq!(iter.next(), Some(((1, 4), b'o')));
+        assert_eq!(iter.next(), Some(((1, 5), b'o')));
+        assert_eq!(iter.next(), Some(((1, 6), b'"')));
+        assert_eq!(iter.next(), Some(((1, 7), b',')));
+        assert_eq!(iter.next(), Some(((1, 8), b'\n')));
+
+        assert_eq!(iter.next(), Some(((2, 0), b' ')));
+        assert_eq!(iter.next(), Some(((2, 1), b' ')));
+        assert_eq!(iter.next(), Some(((2, 2), b'"')));
+        assert_eq!(iter.next(), Some(((2, 3), b'b')));
+        assert_eq!(iter.next(), Some(((2, 4), b'a')));
+        assert_eq!(iter.next(), Some(((2, 5), b'r')));
+        assert_eq!(iter.next(), Some(((2, 6), b'"')));
+        assert_eq!(iter.next(), Some(((2, 7), b',')));
+        assert_eq!(iter.next(), Some(((2, 8), b'\n')));
+
+        assert_eq!(iter.next(), Some(((3, 0), b' ')));
+        assert_eq!(iter.next(), Some(((3, 1), b' ')));
+        assert_eq!(iter.next(), Some(((3, 2), b'"')));
+        assert_eq!(iter.next(), Some(((3, 3), b'b')));
+        assert_eq!(iter.next(), Some(((3, 4), b'a')));
+        assert_eq!(iter.next(), Some(((3, 5), b'z')));
+        assert_eq!(iter.next(), Some(((3, 6), b'"')));
+        assert_eq!(iter.next(), Some(((3, 7), b'\n')));
+
+        assert_eq!(iter.next(), Some(((4, 0), b']')));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_multibyte_and_crlf_columns() {
+        let target = "a\r\nあ🦀b\rc";
+        let positions: Vec<_> = RowColIterator::new(target.bytes().map(Ok::<_, ()>)).map(|(pos, _)| pos).collect();
+        let columns = |pos: &Position| (pos.offset, pos.line, pos.column_utf8, pos.column_utf16, pos.column_chars);
+
+        assert_eq!(columns(&positions[0]), (0, 0, 0, 0, 0)); // a
+        assert_eq!(columns(&positions[1]), (1, 0, 1, 1, 1)); // \r
+        assert_eq!(columns(&positions[2]), (2, 0, 1, 1, 1)); // \n
+        assert_eq!(columns(&positions[3]), (3, 1, 0, 0, 0)); // あ
+        assert_eq!(columns(&positions[5]), (5, 1, 2, 0, 0)); // last byte of あ
+        assert_eq!(columns(&positions[6]), (6, 1, 3, 1, 1)); // 🦀
+        assert_eq!(columns(&positions[10]), (10, 1, 7, 3, 2)); // b
+        assert_eq!(columns(&positions[12]), (12, 1, 8, 4, 3)); // c, `\r` is not counted
+        assert_eq!(positions[10].to_string(), "2:3");
     }
 }
@@ -0,0 +1,514 @@
+use crate::{
+    de::{encoding::BOM, position::Position, token::Tokenizer},
+    error::SyntaxError,
+    value::map::{DefaultMap, MapBackend},
+    Value,
+};
+
+/// Event of the JSON with comments text, emitted by [`PushParser`] in the order of the source text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    /// Key of the next member of the object.
+    Key(String),
+    /// Scalar value, that is null, boolean, number or string.
+    Value(Value),
+}
+
+/// State of the input after [`PushParser::feed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The root value is not completed yet, feed more input.
+    /// Root number or ident is not completed until a delimiter or [`PushParser::finish`], because it may continue.
+    NeedMoreInput,
+    /// The root value is completed, only whitespaces or comments can follow.
+    Complete,
+}
+
+/// Result of [`PushParser::feed`], events completed by the chunk and the state of the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub events: Vec<Event>,
+    pub status: Status,
+}
+
+/// Push based parser, that is fed chunks of JSON with comments text with arbitrary boundaries.
+/// Unlike [`Tokenizer`] that pulls bytes from its reader, this parser never blocks,
+/// so it can be used in event loops. The chunk may split a comment, a string, an escape sequence or a number.
+/// Events are same as [`crate::from_str`] accepts, and errors are also same as it returns.
+/// Once an error is returned, the parser should not be used anymore.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{
+///     de::push::{Event, PushParser, Status, ValueBuilder},
+///     jsonc,
+/// };
+///
+/// let mut parser = PushParser::new();
+/// let feed = parser.feed(br#"{"name": "ap"#).unwrap();
+/// assert_eq!(feed.events, [Event::StartObject, Event::Key("name".to_string())]);
+/// assert_eq!(feed.status, Status::NeedMoreInput);
+///
+/// let feed = parser.feed(br#"p", /* comm"#).unwrap();
+/// assert_eq!(feed.events, [Event::Value(jsonc!("app"))]);
+/// let feed = parser.feed(b"ent */ \"ports\": [80, 4").unwrap();
+/// assert_eq!(feed.events, [Event::Key("ports".to_string()), Event::StartArray, Event::Value(jsonc!(80))]);
+///
+/// let feed = parser.feed(b"43]}").unwrap();
+/// assert_eq!(feed.events, [Event::Value(jsonc!(443)), Event::EndArray, Event::EndObject]);
+/// assert_eq!(feed.status, Status::Complete);
+/// assert_eq!(parser.finish().unwrap(), []);
+///
+/// let mut builder = ValueBuilder::new();
+/// let mut parser = PushParser::new();
+/// let mut events = parser.feed(b"[1, tr").unwrap().events;
+/// events.extend(parser.feed(b"ue] // comment").unwrap().events);
+/// events.extend(parser.finish().unwrap());
+/// let values: Vec<_> = events.into_iter().filter_map(|event| builder.push(event)).collect();
+/// assert_eq!(values, [jsonc!([1, true])]);
+/// ```
+#[derive(Debug, Default)]
+pub struct PushParser {
+    pos: Position,
+    pending: (usize, usize),
+    head: Option<Vec<u8>>,
+    lexing: Lexing,
+    token: Token,
+    stack: Vec<Frame>,
+    done: bool,
+}
+
+/// Bytes of a token with the position of its first byte, positions of the rest are computed by [`TokenTokenizer`].
+#[derive(Debug, Default)]
+struct Token {
+    pos: Position,
+    bytes: Vec<u8>,
+}
+impl Token {
+    fn push(&mut self, pos: Position, c: u8) {
+        if self.bytes.is_empty() {
+            self.pos = pos;
+        }
+        self.bytes.push(c);
+    }
+}
+
+/// State of the lexer, that frames tokens and skips whitespaces and comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Lexing {
+    #[default]
+    Whitespace,
+    Slash,
+    LineComment,
+    BlockComment {
+        asterisk: bool,
+    },
+    String {
+        escaped: bool,
+    },
+    /// Number or ident, that continues until a delimiter.
+    Bare,
+}
+
+/// Expected next token of the array or the object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    ArrayValue,
+    ArraySeparator,
+    ObjectKey,
+    ObjectColon,
+    ObjectValue,
+    ObjectSeparator,
+}
+
+impl PushParser {
+    pub fn new() -> Self {
+        PushParser { head: Some(Vec::new()), ..Default::default() }
+    }
+
+    /// Feed the next chunk of the input, and returns the events completed by the chunk.
+    pub fn feed(&mut self, chunk: &[u8]) -> crate::Result<Feed> {
+        let mut events = Vec::new();
+        for &c in chunk {
+            match self.head.take() {
+                Some(mut head) => {
+                    head.push(c);
+                    if head == BOM {
+                        self.pos.offset = BOM.len();
+                    } else if BOM.starts_with(&head) {
+                        self.head = Some(head);
+                    } else {
+                        for c in head {
+                            self.lex(c, &mut events)?;
+                        }
+                    }
+                }
+                None => self.lex(c, &mut events)?,
+            }
+        }
+        let status = if self.done { Status::Complete } else { Status::NeedMoreInput };
+        Ok(Feed { events, status })
+    }
+
+    /// End the input, and returns the rest of events. The root value must be completed.
+    pub fn finish(mut self) -> crate::Result<Vec<Event>> {
+        let mut events = Vec::new();
+        for c in self.head.take().unwrap_or_default() {
+            self.lex(c, &mut events)?;
+        }
+        match self.lexing {
+            Lexing::Whitespace | Lexing::LineComment => (),
+            Lexing::Slash => Err(SyntaxError::EofWhileStartParsingComment)?,
+            Lexing::BlockComment { .. } => Err(SyntaxError::UnterminatedComment)?,
+            Lexing::String { .. } | Lexing::Bare => self.emit(&mut events)?,
+        }
+        match self.stack.last() {
+            None if self.done => Ok(events),
+            None | Some(Frame::ObjectValue) => Err(SyntaxError::EofWhileStartParsingValue)?,
+            Some(Frame::ArrayValue) => Err(SyntaxError::EofWhileStartParsingArray)?,
+            Some(Frame::ArraySeparator) => Err(SyntaxError::EofWhileEndParsingArray)?,
+            Some(Frame::ObjectKey) => Err(SyntaxError::EofWhileParsingObjectKey)?,
+            Some(Frame::ObjectColon | Frame::ObjectSeparator) => Err(SyntaxError::EofWhileParsingObjectValue)?,
+        }
+    }
+
+    fn lex(&mut self, c: u8, events: &mut Vec<Event>) -> crate::Result<()> {
+        let pos = self.pos;
+        self.pos.advance(c, &mut self.pending);
+        self.lex_at(pos, c, events)
+    }
+
+    fn lex_at(&mut self, pos: Position, c: u8, events: &mut Vec<Event>) -> crate::Result<()> {
+        self.lexing = match (self.lexing, c) {
+            (Lexing::Whitespace, c) if c.is_ascii_whitespace() => Lexing::Whitespace,
+            (Lexing::Whitespace, b'/') => Lexing::Slash,
+            (Lexing::Whitespace, b'"') => {
+                self.token.push(pos, c);
+                Lexing::String { escaped: false }
+            }
+            (Lexing::Whitespace, b'{' | b'}' | b'[' | b']' | b':' | b',') => {
+                self.token.push(pos, c);
+                self.emit(events)?;
+                Lexing::Whitespace
+            }
+            (Lexing::Whitespace, c) => {
+                self.token.push(pos, c);
+                Lexing::Bare
+            }
+            (Lexing::Slash, b'/') => Lexing::LineComment,
+            (Lexing::Slash, b'*') => Lexing::BlockComment { asterisk: false },
+            (Lexing::Slash, found) => Err(SyntaxError::UnexpectedTokenWhileStartParsingComment { pos, found })?,
+            (Lexing::LineComment, b'\n') => Lexing::Whitespace,
+            (Lexing::LineComment, _) => Lexing::LineComment,
+            (Lexing::BlockComment { asterisk: true }, b'/') => Lexing::Whitespace,
+            (Lexing::BlockComment { .. }, c) => Lexing::BlockComment { asterisk: c == b'*' },
+            (Lexing::String { escaped }, c) => {
+                self.token.push(pos, c);
+                match c {
+                    _ if escaped => Lexing::String { escaped: false },
+                    b'\\' => Lexing::String { escaped: true },
+                    // control character is also emitted, then parsing string fails as same as other tokenizers
+                    b'"' => self.emit(events).map(|()| Lexing::Whitespace)?,
+                    c if c.is_ascii_control() => self.emit(events).map(|()| Lexing::Whitespace)?,
+                    _ => Lexing::String { escaped: false },
+                }
+            }
+            (Lexing::Bare, b'{' | b'}' | b'[' | b']' | b':' | b',' | b'"' | b'/') => {
+                self.emit(events)?;
+                self.lexing = Lexing::Whitespace;
+                return self.lex_at(pos, c, events);
+            }
+            (Lexing::Bare, c) if c.is_ascii_whitespace() => self.emit(events).map(|()| Lexing::Whitespace)?,
+            (Lexing::Bare, c) => {
+                self.token.push(pos, c);
+                Lexing::Bare
+            }
+        };
+        Ok(())
+    }
+
+    fn emit(&mut self, events: &mut Vec<Event>) -> crate::Result<()> {
+        let token = std::mem::take(&mut self.token);
+        self.parse_token(token, events)
+    }
+
+    fn parse_token(&mut self, token: Token, events: &mut Vec<Event>) -> crate::Result<()> {
+        let (pos, Some(&found)) = (token.pos, token.bytes.first()) else {
+            return Ok(());
+        };
+        match (self.stack.last_mut(), found) {
+            (None, _) if self.done => Err(SyntaxError::ExpectedEof { pos, found })?,
+            (Some(Frame::ArrayValue | Frame::ArraySeparator), b']') => self.end(Event::EndArray, events),
+            (Some(Frame::ObjectKey | Frame::ObjectSeparator), b'}') => self.end(Event::EndObject, events),
+            (None | Some(Frame::ArrayValue | Frame::ObjectValue), _) => self.parse_value(token, events)?,
+            (Some(frame @ Frame::ArraySeparator), b',') => *frame = Frame::ArrayValue,
+            (Some(Frame::ArraySeparator), _) => Err(SyntaxError::UnexpectedTokenWhileParsingArrayValue { pos, found })?,
+            (Some(frame @ Frame::ObjectKey), b'"') => {
+                *frame = Frame::ObjectColon;
+                let mut tokenizer = TokenTokenizer::new(token);
+                events.push(Event::Key(tokenizer.parse_string()?.to_string()));
+                self.parse_token(tokenizer.rest(), events)?;
+            }
+            (Some(Frame::ObjectKey), _) => Err(SyntaxError::UnexpectedTokenWhileParsingObjectKey { pos, found })?,
+            (Some(frame @ Frame::ObjectColon), b':') => *frame = Frame::ObjectValue,
+            (Some(Frame::ObjectColon), _) => {
+                Err(SyntaxError::UnexpectedTokenWhileStartParsingObjectValue { pos, found })?
+            }
+            (Some(frame @ Frame::ObjectSeparator), b',') => *frame = Frame::ObjectKey,
+            (Some(Frame::ObjectSeparator), _) => {
+                Err(SyntaxError::UnexpectedTokenWhileEndParsingObjectValue { pos, found })?
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_value(&mut self, token: Token, events: &mut Vec<Event>) -> crate::Result<()> {
+        let mut tokenizer = TokenTokenizer::new(token);
+        let (pos, found) = tokenizer.look()?.ok_or(SyntaxError::EofWhileStartParsingValue)?;
+        let value = match found {
+            b'[' => return Ok(self.start(Frame::ArrayValue, Event::StartArray, events)),
+            b'{' => return Ok(self.start(Frame::ObjectKey, Event::StartObject, events)),
+            b'n' => tokenizer.parse_ident(b"null", Value::Null)?,
+            b't' => tokenizer.parse_ident(b"true", Value::Bool(true))?,
+            b'f' => tokenizer.parse_ident(b"false", Value::Bool(false))?,
+            b'-' | b'0'..=b'9' => Value::Number(tokenizer.parse_number()?),
+            b'"' => Value::String(tokenizer.parse_string()?.to_string()),
+            found => Err(SyntaxError::UnexpectedTokenWhileParsingValue { pos, found })?,
+        };
+        events.push(Event::Value(value));
+        self.complete();
+        // rest of the token, such as `x` of `nullx`, is unexpected as the next token
+        self.parse_token(tokenizer.rest(), events)
+    }
+
+    fn start(&mut self, frame: Frame, event: Event, events: &mut Vec<Event>) {
+        self.stack.push(frame);
+        events.push(event);
+    }
+
+    fn end(&mut self, event: Event, events: &mut Vec<Event>) {
+        self.stack.pop();
+        events.push(event);
+        self.complete();
+    }
+
+    /// Complete a value in the current array or object, or the root value.
+    fn complete(&mut self) {
+        match self.stack.last_mut() {
+            Some(frame @ Frame::ArrayValue) => *frame = Frame::ArraySeparator,
+            Some(frame @ Frame::ObjectValue) => *frame = Frame::ObjectSeparator,
+            Some(_) => (),
+            None => self.done = true,
+        }
+    }
+}
+
+/// Tokenizer of a framed token, that parses it with the positions of the source text.
+/// Positions are advanced from the first byte while eating, and a token starts at a character boundary,
+/// so no continuation byte is pending at first.
+struct TokenTokenizer {
+    token: Token,
+    cursor: usize,
+    pending: (usize, usize),
+}
+impl TokenTokenizer {
+    fn new(token: Token) -> Self {
+        TokenTokenizer { token, cursor: 0, pending: (0, 0) }
+    }
+
+    /// Rest of the token that is not eaten yet.
+    fn rest(mut self) -> Token {
+        self.token.bytes.drain(..self.cursor);
+        self.token
+    }
+}
+impl<'de> Tokenizer<'de> for TokenTokenizer {
+    fn eat(&mut self) -> crate::Result<Option<(Position, u8)>> {
+        let found = self.look()?;
+        if let Some((_, c)) = found {
+            self.token.pos.advance(c, &mut self.pending);
+            self.cursor += 1;
+        }
+        Ok(found)
+    }
+
+    fn look(&mut self) -> crate::Result<Option<(Position, u8)>> {
+        Ok(self.token.bytes.get(self.cursor).map(|&c| (self.token.pos, c)))
+    }
+}
+
+/// Build [`Value`]s from [`Event`]s of [`PushParser`].
+#[derive(Debug, Default)]
+pub struct ValueBuilder {
+    stack: Vec<(Value, Option<String>)>,
+    key: Option<String>,
+}
+impl ValueBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push the next event, and returns the root value when it is completed.
+    pub fn push(&mut self, event: Event) -> Option<Value> {
+        match event {
+            Event::StartObject => {
+                self.stack.push((Value::Object(<DefaultMap as MapBackend>::Map::default()), self.key.take()))
+            }
+            Event::StartArray => self.stack.push((Value::Array(Vec::new()), self.key.take())),
+            Event::Key(key) => self.key = Some(key),
+            Event::Value(value) => {
+                let key = self.key.take();
+                return self.insert(value, key);
+            }
+            Event::EndObject | Event::EndArray => {
+                let (value, key) = self.stack.pop()?;
+                return self.insert(value, key);
+            }
+        }
+        None
+    }
+
+    fn insert(&mut self, value: Value, key: Option<String>) -> Option<Value> {
+        match self.stack.last_mut() {
+            None => return Some(value),
+            Some((Value::Array(vec), _)) => vec.push(value),
+            Some((Value::Object(map), _)) => _ = map.insert(key.unwrap_or_default(), value),
+            Some(_) => (),
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jsonc;
+
+    use super::*;
+
+    /// Parse the text fed in chunks of `size` bytes, and build the value.
+    fn parse_chunked(text: &[u8], size: usize) -> crate::Result<Value> {
+        let (mut parser, mut builder, mut values) = (PushParser::new(), ValueBuilder::new(), Vec::new());
+        for chunk in text.chunks(size) {
+            values.extend(parser.feed(chunk)?.events.into_iter().filter_map(|event| builder.push(event)));
+        }
+        values.extend(parser.finish()?.into_iter().filter_map(|event| builder.push(event)));
+        assert_eq!(values.len(), 1);
+        Ok(values.remove(0))
+    }
+
+    /// Parse the text split at a boundary, that may split a comment, a string, an escape sequence or a number.
+    fn parse_split(text: &[u8], at: usize) -> crate::Result<Value> {
+        let (mut parser, mut builder) = (PushParser::new(), ValueBuilder::new());
+        let mut events = parser.feed(&text[..at])?.events;
+        events.extend(parser.feed(&text[at..])?.events);
+        events.extend(parser.finish()?);
+        Ok(events.into_iter().filter_map(|event| builder.push(event)).last().unwrap())
+    }
+
+    #[test]
+    fn test_push_parser_any_boundaries() {
+        let text = concat!(
+            "\u{FEFF}// leading\n{\r\n",
+            "  \"a\\\"b\": [1, -2.5e-3, 0, true, false, null, \"\\u3042\\uD83E\\uDD80\", ],\n",
+            "  /* block * comment */ \"日本\": {\"nested\": {}, \"empty\": []}, // trailing\n",
+            "}\n// end",
+        );
+        let expected = crate::from_str::<Value>(text).unwrap();
+        assert_eq!(
+            expected,
+            jsonc!({"a\"b": [1, -2.5e-3, 0, true, false, null, "あ🦀"], "日本": {"nested": {}, "empty": []}})
+        );
+        for size in 1..=text.len() {
+            assert_eq!(parse_chunked(text.as_bytes(), size).unwrap(), expected, "size {size}");
+        }
+        for at in 0..=text.len() {
+            assert_eq!(parse_split(text.as_bytes(), at).unwrap(), expected, "at {at}");
+        }
+        for scalar in ["123", "-0.5e10", "\"string\"", "true", "null", " 1 // comment"] {
+            for at in 0..=scalar.len() {
+                assert_eq!(parse_split(scalar.as_bytes(), at).unwrap(), crate::from_str::<Value>(scalar).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_parser_status() {
+        let mut parser = PushParser::new();
+        assert_eq!(parser.feed(b"[1, [2]").unwrap().status, Status::NeedMoreInput);
+        let feed = parser.feed(b"] /* trailing").unwrap();
+        assert_eq!((feed.events, feed.status), (vec![Event::EndArray], Status::Complete));
+        assert_eq!(parser.feed(b" */").unwrap(), Feed { events: Vec::new(), status: Status::Complete });
+        assert_eq!(parser.finish().unwrap(), []);
+
+        let mut parser = PushParser::new();
+        assert_eq!(parser.feed(b"12").unwrap(), Feed { events: Vec::new(), status: Status::NeedMoreInput });
+        assert_eq!(parser.feed(b"3").unwrap().status, Status::NeedMoreInput);
+        assert_eq!(parser.finish().unwrap(), [Event::Value(jsonc!(123))]);
+
+        let mut parser = PushParser::new();
+        assert_eq!(parser.feed(b"123 ").unwrap().status, Status::Complete);
+        assert_eq!(parser.feed(b"4").unwrap().status, Status::Complete);
+        assert_eq!(
+            parser.finish().unwrap_err().to_string(),
+            crate::from_str::<Value>("123 4").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_push_parser_errors_same_as_from_str() {
+        let texts = [
+            "",
+            " ",
+            "[",
+            "[1",
+            "[1,",
+            "{",
+            "{\"a\"",
+            "{\"a\":",
+            "{\"a\": 1",
+            "{\"a\": 1,",
+            "[1 2]",
+            "[,]",
+            "{1: 2}",
+            "{\"a\" 1}",
+            "{\"a\": 1 \"b\": 2}",
+            "{\"a\": }",
+            "nul",
+            "nullx",
+            "[truefalse]",
+            "[1x]",
+            "[1.5.3]",
+            "01",
+            "+1",
+            "1.",
+            "1e",
+            "\"unterminated",
+            "\"new\nline\"",
+            "\"\\a\"",
+            "\"\\uXYZW\"",
+            "/",
+            "/x",
+            "/* unterminated",
+            "1 2",
+            "[1] x",
+            "[\"a\"b]",
+            "]",
+            "}",
+            ":",
+            "\u{FEFF}\u{FEFF}1",
+            "[\n  \"日本🦀\\a\"]",
+            "{\"日本🦀\": \"🦀\n\"}",
+            "[\"🦀\"🦀]",
+        ];
+        for text in texts {
+            let expected = crate::from_str::<Value>(text).unwrap_err().to_string();
+            for at in 0..=text.len() {
+                assert_eq!(parse_split(text.as_bytes(), at).unwrap_err().to_string(), expected, "{text:?} at {at}");
+            }
+        }
+    }
+}
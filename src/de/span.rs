@@ -0,0 +1,264 @@
+use std::{collections::BTreeMap, ops::Range};
+
+use crate::{
+    de::{
+        access::string::ParsedString,
+        position::{PosRange, Position},
+        token::{str::StrTokenizer, Tokenizer},
+    },
+    error::{Ensure, SyntaxError},
+    value::{
+        map::{DefaultMap, MapBackend},
+        pointer::to_pointer,
+    },
+    Value,
+};
+
+/// Location of a key or a value in the source text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Positions of the first and the last byte, both are inclusive.
+    pub range: PosRange,
+    /// Half-open byte offsets, `&text[span.offset.clone()]` is the source text.
+    pub offset: Range<usize>,
+}
+
+/// Spans of a value, and of its key if the value is a member of an object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spans {
+    pub key: Option<Span>,
+    pub value: Span,
+}
+
+/// Index from JSON Pointer of every value to its [`Spans`], built by [`crate::from_str_with_spans`].
+///
+/// # Examples
+/// ```
+/// let text = r#"{
+///     "name": "app", // application name
+///     "ports": [80, 443],
+/// }"#;
+/// let (value, spans) = json_with_comments::from_str_with_spans(text).unwrap();
+/// assert_eq!(value["ports"][1], 443.into());
+///
+/// let port = spans.value("/ports/1").unwrap();
+/// assert_eq!((port.range.0.line, port.range.0.column_chars), (2, 18));
+/// assert_eq!(port.range.1.to_string(), "3:21");
+/// assert_eq!(&text[port.offset.clone()], "443");
+/// assert_eq!(&text[spans.key("/name").unwrap().offset.clone()], r#""name""#);
+/// assert_eq!(spans.value("").unwrap().offset, 0..text.len());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    spans: BTreeMap<String, Spans>,
+}
+impl SourceMap {
+    /// Get [`Spans`] of the value of the JSON Pointer, the root value is `""`.
+    pub fn get(&self, pointer: &str) -> Option<&Spans> {
+        self.spans.get(pointer)
+    }
+
+    /// Get [`Span`] of the key of the JSON Pointer, or `None` if the value is not a member of an object.
+    pub fn key(&self, pointer: &str) -> Option<&Span> {
+        self.get(pointer)?.key.as_ref()
+    }
+
+    /// Get [`Span`] of the value of the JSON Pointer.
+    pub fn value(&self, pointer: &str) -> Option<&Span> {
+        Some(&self.get(pointer)?.value)
+    }
+
+    /// Iterate JSON Pointers and their [`Spans`] in lexicographic order of the pointers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Spans)> {
+        self.spans.iter().map(|(pointer, spans)| (&pointer[..], spans))
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+}
+
+/// Parse JSON with comments text as [`Value`] and [`SourceMap`] at once.
+pub(crate) fn parse(s: &str) -> crate::Result<(Value, SourceMap)> {
+    let mut parser = SpanParser { tokenizer: TrackingTokenizer::new(StrTokenizer::new(s)), path: Vec::new() };
+    let mut map = SourceMap::default();
+    let value = parser.parse_value(None, &mut map)?;
+    match parser.tokenizer.eat_whitespace()? {
+        Some((pos, found)) => Err(SyntaxError::ExpectedEof { pos, found })?,
+        None => Ok((value, map)),
+    }
+}
+
+/// Tokenizer that remembers the position of the last eaten byte.
+struct TrackingTokenizer<T> {
+    delegate: T,
+    last: Position,
+}
+impl<T> TrackingTokenizer<T> {
+    fn new(delegate: T) -> Self {
+        TrackingTokenizer { delegate, last: Position::default() }
+    }
+}
+impl<'de, T: Tokenizer<'de>> Tokenizer<'de> for TrackingTokenizer<T> {
+    fn eat(&mut self) -> crate::Result<Option<(Position, u8)>> {
+        let eaten = self.delegate.eat()?;
+        if let Some((pos, _)) = eaten {
+            self.last = pos;
+        }
+        Ok(eaten)
+    }
+
+    fn look(&mut self) -> crate::Result<Option<(Position, u8)>> {
+        self.delegate.look()
+    }
+}
+
+struct SpanParser<T> {
+    tokenizer: TrackingTokenizer<T>,
+    path: Vec<String>,
+}
+impl<'de, T: Tokenizer<'de>> SpanParser<T> {
+    fn span(&self, start: Position) -> Span {
+        let end = self.tokenizer.last;
+        Span { range: (start, end), offset: start.offset..end.offset + 1 }
+    }
+
+    fn parse_value(&mut self, key: Option<Span>, map: &mut SourceMap) -> crate::Result<Value> {
+        let (start, token) = self.tokenizer.skip_whitespace()?.ok_or(SyntaxError::EofWhileStartParsingValue)?;
+        let value = match token {
+            b'n' => self.tokenizer.parse_ident(b"null", Value::Null)?,
+            b't' => self.tokenizer.parse_ident(b"true", Value::Bool(true))?,
+            b'f' => self.tokenizer.parse_ident(b"false", Value::Bool(false))?,
+            b'-' | b'0'..=b'9' => Value::Number(self.tokenizer.parse_number()?),
+            b'"' => Value::String(into_string(self.tokenizer.parse_string()?)),
+            b'[' => self.parse_array(map)?,
+            b'{' => self.parse_object(map)?,
+            found => Err(SyntaxError::UnexpectedTokenWhileParsingValue { pos: start, found })?,
+        };
+        let value_span = self.span(start);
+        map.spans.insert(to_pointer(&self.path), Spans { key, value: value_span });
+        Ok(value)
+    }
+
+    fn parse_array(&mut self, map: &mut SourceMap) -> crate::Result<Value> {
+        self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?;
+        let mut vec = Vec::new();
+        loop {
+            match self.tokenizer.skip_whitespace()?.ok_or(SyntaxError::EofWhileStartParsingArray)? {
+                (_, b']') => break,
+                _ => {
+                    self.path.push(vec.len().to_string());
+                    let element = self.parse_value(None, map);
+                    self.path.pop();
+                    vec.push(element?);
+                }
+            }
+            match self.tokenizer.skip_whitespace()?.ok_or(SyntaxError::EofWhileEndParsingArray)? {
+                (_, b',') => _ = self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?,
+                (_, b']') => (),
+                (pos, found) => Err(SyntaxError::UnexpectedTokenWhileParsingArrayValue { pos, found })?,
+            }
+        }
+        self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?;
+        Ok(Value::Array(vec))
+    }
+
+    fn parse_object(&mut self, map: &mut SourceMap) -> crate::Result<Value> {
+        self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?;
+        let mut object = <DefaultMap as MapBackend>::Map::default();
+        loop {
+            let start = match self.tokenizer.skip_whitespace()?.ok_or(SyntaxError::EofWhileParsingObjectKey)? {
+                (_, b'}') => break,
+                (pos, b'"') => pos,
+                (pos, found) => Err(SyntaxError::UnexpectedTokenWhileParsingObjectKey { pos, found })?,
+            };
+            let key = into_string(self.tokenizer.parse_string()?);
+            let key_span = self.span(start);
+            match self.tokenizer.eat_whitespace()?.ok_or(SyntaxError::EofWhileParsingObjectValue)? {
+                (_, b':') => (),
+                (pos, found) => Err(SyntaxError::UnexpectedTokenWhileStartParsingObjectValue { pos, found })?,
+            }
+            self.path.push(key.clone());
+            let value = self.parse_value(Some(key_span), map);
+            self.path.pop();
+            object.insert(key, value?);
+            match self.tokenizer.skip_whitespace()?.ok_or(SyntaxError::EofWhileParsingObjectValue)? {
+                (_, b',') => _ = self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?,
+                (_, b'}') => (),
+                (pos, found) => Err(SyntaxError::UnexpectedTokenWhileEndParsingObjectValue { pos, found })?,
+            }
+        }
+        self.tokenizer.eat()?.ok_or(Ensure::EatAfterLook)?;
+        Ok(Value::Object(object))
+    }
+}
+
+fn into_string(parsed: ParsedString) -> String {
+    match parsed {
+        ParsedString::Borrowed(s) => s.to_string(),
+        ParsedString::Owned(s) => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc, value::pointer::to_pointer};
+
+    use super::*;
+
+    #[test]
+    fn test_spans_of_every_value() {
+        let text = r#"
+        // leading comment
+        {
+            "a/b": [1, /* one */ -2.5e1, "three",],
+            "c": {"d": null, "e~": true} , // trailing
+        }
+        "#;
+        let (value, spans) = crate::from_str_with_spans(text).unwrap();
+        assert_eq!(value, jsonc!({"a/b": [1, -2.5e1, "three"], "c": {"d": null, "e~": true}}));
+        assert_eq!(value, crate::from_str::<Value>(text).unwrap());
+
+        let source = |pointer: &str| &text[spans.value(pointer).unwrap().offset.clone()];
+        assert_eq!(source(&to_pointer(["a/b"])), r#"[1, /* one */ -2.5e1, "three",]"#);
+        assert_eq!(source("/a~1b/1"), "-2.5e1");
+        assert_eq!(source("/a~1b/2"), r#""three""#);
+        assert_eq!(source("/c"), r#"{"d": null, "e~": true}"#);
+        assert_eq!(source("/c/e~0"), "true");
+        assert!(source("").starts_with('{') && source("").ends_with('}'));
+        assert_eq!(&text[spans.key("/c/d").unwrap().offset.clone()], r#""d""#);
+        assert_eq!(spans.key("/a~1b/0"), None);
+        assert_eq!(spans.key(""), None);
+        assert_eq!(spans.len(), 8);
+        assert_eq!(spans.iter().map(|(pointer, _)| pointer).collect::<Vec<_>>()[..3], ["", "/a~1b", "/a~1b/0"]);
+
+        let line_col = |(start, end): PosRange| ((start.line, start.column_chars), (end.line, end.column_chars));
+        assert_eq!(line_col(spans.value("/a~1b/1").unwrap().range), ((3, 33), (3, 38)));
+        assert_eq!(line_col(spans.get("/c").unwrap().key.as_ref().unwrap().range), ((4, 12), (4, 14)));
+        assert_eq!(line_col(spans.value("/c").unwrap().range), ((4, 17), (4, 39)));
+    }
+
+    #[test]
+    fn test_spans_escaped_and_multibyte() {
+        let text = "{\"\\u3042\": \"日本\\n\"}";
+        let (value, spans) = crate::from_str_with_spans(text).unwrap();
+        assert_eq!(value, jsonc!({"あ": "日本\n"}));
+        assert_eq!(&text[spans.key("/あ").unwrap().offset.clone()], r#""\u3042""#);
+        assert_eq!(&text[spans.value("/あ").unwrap().offset.clone()], "\"日本\\n\"");
+        let (start, end) = spans.value("/あ").unwrap().range;
+        assert_eq!((start.column_utf8, start.column_utf16, start.column_chars), (11, 11, 11));
+        assert_eq!((end.column_utf8, end.column_utf16, end.column_chars), (20, 16, 16));
+    }
+
+    #[test]
+    fn test_spans_errors() {
+        for text in ["", "[1, 2", "[1 2]", r#"{"a" 1}"#, r#"{"a": 1 "b": 2}"#, "{1: 2}", "nul", "[,]", "1 2"] {
+            assert!(crate::from_str_with_spans(text).is_err(), "{text}");
+            assert!(crate::from_str::<Value>(text).is_err(), "{text}");
+        }
+    }
+}
@@ -3,7 +3,7 @@ pub mod read;
 pub mod slice;
 pub mod str;
 
-use crate::error::{Ensure, SyntaxError};
+use crate::error::{Ensure, SemanticError, SyntaxError};
 
 use super::{
     access::{
@@ -17,6 +17,15 @@ pub trait Tokenizer<'de> {
     fn eat(&mut self) -> crate::Result<Option<(Position, u8)>>;
     fn look(&mut self) -> crate::Result<Option<(Position, u8)>>;
 
+    /// Start capturing the source text from the next token, used by [`crate::value::RawValue`].
+    fn start_capture(&mut self) -> crate::Result<()> {
+        Err(SemanticError::RawValueCapture)?
+    }
+    /// Stop capturing and return the source text since [`Tokenizer::start_capture`].
+    fn end_capture(&mut self) -> crate::Result<ParsedString<'de>> {
+        Err(SemanticError::RawValueCapture)?
+    }
+
     fn eat_whitespace(&mut self) -> crate::Result<Option<(Position, u8)>> {
         loop {
             match self.eat()? {
@@ -105,7 +114,7 @@ pub trait Tokenizer<'de> {
 
     fn parse_ident<T>(&mut self, ident: &[u8], value: T) -> crate::Result<T> {
         let mut iter = ident.iter();
-        let (p, parsed) = self.fold_token(|_, c| iter.next().map_or(false, |&i| i == c))?;
+        let (p, parsed) = self.fold_token(|_, c| iter.next().is_some_and(|&i| i == c))?;
         match (p, iter.next().is_none() && parsed.len() == ident.len()) {
             (_, true) => Ok(value),
             (Some(pos), false) => Err(SyntaxError::UnexpectedIdent { pos, expected: ident.into(), found: parsed })?,
@@ -164,6 +173,23 @@ pub trait Tokenizer<'de> {
     }
 
     fn parse_unicode(&mut self, buff: &mut Vec<u8>) -> crate::Result<()> {
+        let (pos, hex) = self.parse_hex4()?;
+        let code = match hex {
+            0xD800..=0xDBFF => match (self.eat()?, self.eat()?) {
+                (Some((_, b'\\')), Some((_, b'u'))) => match self.parse_hex4()? {
+                    (_, low @ 0xDC00..=0xDFFF) => 0x10000 + ((hex - 0xD800) << 10) + (low - 0xDC00),
+                    _ => Err(SyntaxError::CannotConvertChar { pos, char: hex })?,
+                },
+                _ => Err(SyntaxError::CannotConvertChar { pos, char: hex })?,
+            },
+            _ => hex,
+        };
+        let ch = char::from_u32(code).ok_or(SyntaxError::CannotConvertChar { pos, char: code })?;
+        Ok(buff.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()))
+    }
+
+    fn parse_hex4(&mut self) -> crate::Result<(Position, u32)> {
+        let (start, _) = self.look()?.ok_or(SyntaxError::EofWhileParsingEscapeSequence)?;
         let mut hex: u32 = 0;
         for i in 0..4 {
             match self.eat()?.ok_or(SyntaxError::EofWhileParsingEscapeSequence)? {
@@ -173,8 +199,7 @@ pub trait Tokenizer<'de> {
                 (pos, found) => return Err(SyntaxError::InvalidUnicodeEscape { pos, found })?,
             }
         }
-        let ch = unsafe { char::from_u32_unchecked(hex) }; // TODO maybe safe
-        Ok(buff.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()))
+        Ok((start, hex))
     }
 
     fn parse_number<T: FromNumberBuilder>(&mut self) -> crate::Result<T>
@@ -247,64 +272,78 @@ mod tests {
 
     use super::*;
 
+    type LineCol = (usize, usize);
+    fn line_col(token: Option<(Position, u8)>) -> Option<(LineCol, u8)> {
+        token.map(|(pos, c)| ((pos.line, pos.column_utf8), c))
+    }
+    fn range_line_col((start, end): PosRange) -> (LineCol, LineCol) {
+        ((start.line, start.column_utf8), (end.line, end.column_utf8))
+    }
+    fn fold_line_col((range, token): (Option<PosRange>, Vec<u8>)) -> (Option<(LineCol, LineCol)>, Vec<u8>) {
+        (range.map(range_line_col), token)
+    }
+
     pub fn behavior_fold_token<'a, T: 'a + Tokenizer<'a>, F: Fn(&'a str) -> T>(from: F) {
         let target = r#"[123, 456]"#;
         let mut tokenizer = from(target);
 
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 0), b'[')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 0), b'[')));
         assert_eq!(
-            tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap(),
+            fold_line_col(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap()),
             (Some(((0, 1), (0, 3))), vec![b'1', b'2', b'3']),
         );
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 4), b',')));
-        assert_eq!(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap(), (Some(((0, 5), (0, 5))), vec![]));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 5), b' ')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 4), b',')));
+        assert_eq!(
+            fold_line_col(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap()),
+            (Some(((0, 5), (0, 5))), vec![])
+        );
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 5), b' ')));
         assert_eq!(
-            tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap(),
+            fold_line_col(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap()),
             (Some(((0, 6), (0, 8))), vec![b'4', b'5', b'6']),
         );
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 9), b']')));
-        assert_eq!(tokenizer.eat().unwrap(), None);
-        assert_eq!(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap(), (None, vec![]));
-        assert_eq!(tokenizer.eat().unwrap(), None);
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 9), b']')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), None);
+        assert_eq!(fold_line_col(tokenizer.fold_token(|_t, c| matches!(c, b'1'..=b'9')).unwrap()), (None, vec![]));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), None);
     }
 
     pub fn behavior_parse_ident<'a, T: 'a + Tokenizer<'a>, F: Fn(&'a str) -> T>(from: F) {
         let target = r#"[true, fal, nulled, nul,]"#;
         let mut tokenizer = from(target);
 
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 0), b'[')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 0), b'[')));
         assert_eq!(tokenizer.parse_ident(b"true", true).unwrap(), true);
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 5), b',')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 6), b' ')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 5), b',')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 6), b' ')));
 
         match tokenizer.parse_ident(b"false", false).unwrap_err().into_inner().downcast_ref().unwrap() {
             SyntaxError::UnexpectedIdent { pos, expected, found } => {
-                assert_eq!(pos, &((0, 7), (0, 9)));
+                assert_eq!(range_line_col(*pos), ((0, 7), (0, 9)));
                 assert_eq!(expected, &b"false".to_vec());
                 assert_eq!(found, &b"fal".to_vec());
             }
             _ => unreachable!(),
         }
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 10), b',')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 11), b' ')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 10), b',')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 11), b' ')));
 
         assert_eq!(tokenizer.parse_ident(b"null", ()).unwrap(), ());
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 16), b'e')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 17), b'd')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 18), b',')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 19), b' ')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 16), b'e')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 17), b'd')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 18), b',')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 19), b' ')));
 
         match tokenizer.parse_ident(b"null", ()).unwrap_err().into_inner().downcast_ref().unwrap() {
             SyntaxError::UnexpectedIdent { pos, expected, found } => {
-                assert_eq!(pos, &((0, 20), (0, 22)));
+                assert_eq!(range_line_col(*pos), ((0, 20), (0, 22)));
                 assert_eq!(expected, &b"null".to_vec());
                 assert_eq!(found, &b"nul".to_vec());
             }
             _ => unreachable!(),
         }
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 23), b',')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 24), b']')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 23), b',')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 24), b']')));
 
         assert!(matches!(
             tokenizer.parse_ident(b"None", ()).unwrap_err().into_inner().downcast_ref().unwrap(),
@@ -325,17 +364,17 @@ mod tests {
         "#;
         let mut tokenizer = from(target);
 
-        assert_eq!(tokenizer.look().unwrap(), Some(((0, 0), b'\n')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((0, 0), b'\n')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((0, 0), b'\n')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((1, 0), b' ')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((1, 0), b' ')));
-        assert_eq!(tokenizer.eat().unwrap(), Some(((1, 0), b' ')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((0, 0), b'\n')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((0, 0), b'\n')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((0, 0), b'\n')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((1, 0), b' ')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((1, 0), b' ')));
+        assert_eq!(line_col(tokenizer.eat().unwrap()), Some(((1, 0), b' ')));
 
-        assert_eq!(tokenizer.eat_whitespace().unwrap(), Some(((1, 12), b'[')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((1, 13), b'\n')));
-        assert_eq!(tokenizer.skip_whitespace().unwrap(), Some(((2, 16), b'"')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((2, 16), b'"')));
+        assert_eq!(line_col(tokenizer.eat_whitespace().unwrap()), Some(((1, 12), b'[')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((1, 13), b'\n')));
+        assert_eq!(line_col(tokenizer.skip_whitespace().unwrap()), Some(((2, 16), b'"')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((2, 16), b'"')));
 
         assert_eq!(tokenizer.parse_string().unwrap(), "jsonc");
         assert!(matches!(tokenizer.eat(), Ok(Some((_, b',')))));
@@ -356,9 +395,9 @@ mod tests {
         assert!(matches!(tokenizer.parse_ident(b"null", ()), Ok(())));
         assert!(matches!(tokenizer.eat(), Ok(Some((_, b',')))));
 
-        assert_eq!(tokenizer.eat_whitespace().unwrap(), Some(((7, 12), b']')));
-        assert_eq!(tokenizer.look().unwrap(), Some(((7, 13), b'\n')));
-        assert_eq!(tokenizer.eat_whitespace().unwrap(), None);
+        assert_eq!(line_col(tokenizer.eat_whitespace().unwrap()), Some(((7, 12), b']')));
+        assert_eq!(line_col(tokenizer.look().unwrap()), Some(((7, 13), b'\n')));
+        assert_eq!(line_col(tokenizer.eat_whitespace().unwrap()), None);
     }
 
     pub fn behavior_parse_unescaped_string<'a, T: 'a + Tokenizer<'a>, F: Fn(&'a str) -> T>(from: F) {
@@ -379,6 +418,8 @@ mod tests {
         assert_eq!(parse(from(r#""line\u000Afeed""#)), "line\nfeed");
         assert_eq!(parse(from(r#""epsilon \u03b5""#)), "epsilon ε");
         assert_eq!(parse(from(r#""💯""#)), "💯");
+        assert_eq!(parse(from(r#""\uD83D\uDCAF""#)), "💯");
+        assert_eq!(parse(from(r#""\udbff\udfff""#)), "\u{10FFFF}");
     }
 
     pub fn behavior_parse_raw_string<'a, T: 'a + Tokenizer<'a>, F: Fn(&'a str) -> T>(from: F) {
@@ -436,7 +477,19 @@ mod tests {
         assert!(matches!(
             parse_err(from(r#""invalid unicode \uXXXX""#)).downcast_ref().unwrap(),
             SyntaxError::InvalidUnicodeEscape { found: b'X', .. }
-        ))
+        ));
+        assert!(matches!(
+            parse_err(from(r#""lone high surrogate \uD83D""#)).downcast_ref().unwrap(),
+            SyntaxError::CannotConvertChar { char: 0xD83D, .. }
+        ));
+        assert!(matches!(
+            parse_err(from(r#""lone low surrogate \uDCAF""#)).downcast_ref().unwrap(),
+            SyntaxError::CannotConvertChar { char: 0xDCAF, .. }
+        ));
+        assert!(matches!(
+            parse_err(from(r#""unpaired surrogate \uD83D\u0041""#)).downcast_ref().unwrap(),
+            SyntaxError::CannotConvertChar { char: 0xD83D, .. }
+        ));
     }
 
     pub fn behavior_parse_number<'a, T: 'a + Tokenizer<'a>, F: Fn(&'a str) -> T>(from: F) {
@@ -497,7 +550,7 @@ mod tests {
         assert!(matches!(parse_err::<i32>(from("-999999999999")).downcast_ref().unwrap(), ParseIntError { .. }));
         assert!(matches!(
             parse_err::<f32>(from("0.")).downcast_ref().unwrap(),
-            SyntaxError::EofWhileStartParsingFraction { .. },
+            SyntaxError::EofWhileStartParsingFraction,
         ));
         assert!(matches!(
             parse_err::<f32>(from("0.e")).downcast_ref().unwrap(),
@@ -505,7 +558,7 @@ mod tests {
         ));
         assert!(matches!(
             parse_err::<f64>(from("0e")).downcast_ref().unwrap(),
-            SyntaxError::EofWhileStartParsingExponent { .. },
+            SyntaxError::EofWhileStartParsingExponent,
         ));
         assert!(matches!(
             parse_err::<f64>(from("1e.")).downcast_ref().unwrap(),
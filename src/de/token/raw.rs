@@ -20,6 +20,14 @@ impl<'de> Tokenizer<'de> for RawTokenizer<'de> {
         self.delegate.look()
     }
 
+    fn start_capture(&mut self) -> crate::Result<()> {
+        self.delegate.start_capture()
+    }
+
+    fn end_capture(&mut self) -> crate::Result<ParsedString<'de>> {
+        self.delegate.end_capture()
+    }
+
     fn parse_string_content(&mut self) -> crate::Result<ParsedString<'de>> {
         let offset = self.delegate.current;
         let _ = self.parse_string_content_super()?;
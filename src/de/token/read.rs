@@ -1,7 +1,11 @@
 use std::{io, iter::Peekable};
 
 use crate::{
-    de::position::{Position, RowColIterator},
+    de::{
+        access::string::ParsedString,
+        encoding::{Decoding, Transcoder},
+        position::{Position, RowColIterator},
+    },
     error::Ensure,
 };
 
@@ -11,14 +15,21 @@ pub struct ReadTokenizer<R>
 where
     R: io::Read,
 {
-    iter: Peekable<RowColIterator<io::Bytes<R>>>,
+    iter: Peekable<RowColIterator<io::Bytes<Transcoder<R>>>>,
+    capture: Option<Vec<u8>>,
 }
 impl<R> ReadTokenizer<R>
 where
     R: io::Read,
 {
     pub fn new(read: R) -> Self {
-        ReadTokenizer { iter: RowColIterator::new(read.bytes()).peekable() }
+        Self::with_decoding(read, Decoding::default())
+    }
+
+    #[allow(clippy::unbuffered_bytes)] // TODO buffering
+    pub fn with_decoding(read: R, decoding: Decoding) -> Self {
+        let transcoder = Transcoder::new(read, decoding);
+        ReadTokenizer { iter: RowColIterator::new(io::Read::bytes(transcoder)).peekable(), capture: None }
     }
 }
 
@@ -28,7 +39,12 @@ where
 {
     fn eat(&mut self) -> crate::Result<Option<(Position, u8)>> {
         match self.iter.next() {
-            Some((pos, Ok(c))) => Ok(Some((pos, c))),
+            Some((pos, Ok(c))) => {
+                if let Some(capture) = &mut self.capture {
+                    capture.push(c);
+                }
+                Ok(Some((pos, c)))
+            }
             Some((_, Err(e))) => Err(e)?,
             None => Ok(None),
         }
@@ -44,6 +60,16 @@ where
             None => Ok(None),
         }
     }
+
+    fn start_capture(&mut self) -> crate::Result<()> {
+        self.capture = Some(Vec::new());
+        Ok(())
+    }
+
+    fn end_capture(&mut self) -> crate::Result<ParsedString<'de>> {
+        let captured = self.capture.take().ok_or(Ensure::StartCapture)?;
+        Ok(ParsedString::Owned(String::from_utf8(captured)?))
+    }
 }
 
 #[cfg(test)]
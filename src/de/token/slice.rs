@@ -1,7 +1,11 @@
 use std::iter::Peekable;
 
 use crate::{
-    de::position::{Position, RowColIterator},
+    de::{
+        access::string::ParsedString,
+        encoding::BOM,
+        position::{Position, RowColIterator},
+    },
     error::Ensure,
 };
 
@@ -11,13 +15,15 @@ type SliceIter<'de> = Box<dyn Iterator<Item = Result<u8, ()>> + 'de>;
 pub struct SliceTokenizer<'de> {
     pub slice: &'de [u8],
     pub current: usize,
+    capture: Option<usize>,
     iter: Peekable<RowColIterator<SliceIter<'de>>>,
 }
 impl<'de> SliceTokenizer<'de> {
     pub fn new(slice: &'de [u8]) -> Self {
         let i: Box<dyn Iterator<Item = Result<u8, ()>> + 'de> = Box::new(slice.iter().cloned().map(Ok));
-        let (current, iter) = (0, RowColIterator::new(i).peekable());
-        SliceTokenizer { slice, current, iter }
+        let current = if slice.starts_with(&BOM) { BOM.len() } else { 0 }; // skipped by `RowColIterator`
+        let iter = RowColIterator::new(i).peekable();
+        SliceTokenizer { slice, current, capture: None, iter }
     }
 }
 
@@ -38,6 +44,17 @@ impl<'de> Tokenizer<'de> for SliceTokenizer<'de> {
             None => Ok(None),
         }
     }
+
+    fn start_capture(&mut self) -> crate::Result<()> {
+        self.capture = Some(self.current);
+        Ok(())
+    }
+
+    fn end_capture(&mut self) -> crate::Result<ParsedString<'de>> {
+        let start = self.capture.take().ok_or(Ensure::StartCapture)?;
+        let end = self.current.min(self.slice.len());
+        Ok(ParsedString::Borrowed(std::str::from_utf8(&self.slice[start..end])?))
+    }
 }
 
 #[cfg(test)]
@@ -24,8 +24,17 @@ impl<'de> Tokenizer<'de> for StrTokenizer<'de> {
         self.delegate.look()
     }
 
+    fn start_capture(&mut self) -> crate::Result<()> {
+        self.delegate.start_capture()
+    }
+
+    fn end_capture(&mut self) -> crate::Result<ParsedString<'de>> {
+        self.delegate.end_capture()
+    }
+
     fn parse_string_content(&mut self) -> crate::Result<ParsedString<'de>> {
         let offset = self.delegate.current;
+        self.unescaped = false;
         let value = self.parse_string_content_super()?;
         match (self.unescaped, value) {
             // if string contain escape sequence, it should be unescaped
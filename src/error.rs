@@ -3,7 +3,10 @@ use std::fmt;
 use serde::{de, ser};
 use thiserror::Error;
 
-use crate::de::position::{PosRange, Position};
+use crate::de::{
+    encoding::Encoding,
+    position::{PosRange, Position},
+};
 
 pub type Result<T> = std::result::Result<T, JsonWithCommentsError>;
 #[derive(Error, Debug)]
@@ -80,70 +83,70 @@ impl From<std::char::ParseCharError> for JsonWithCommentsError {
 
 #[derive(Error, Debug)]
 pub enum SyntaxError {
-    #[error("{pos:?}: Expected value, but found {found:?}")]
+    #[error("{pos}: Expected value, but found {found:?}")]
     UnexpectedTokenWhileParsingValue { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected string start `\"`, but found {found:?}")]
+    #[error("{pos}: Expected string start `\"`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingString { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected bytes start, but found {found:?}")]
+    #[error("{pos}: Expected bytes start, but found {found:?}")]
     UnexpectedTokenWhileStartParsingBytes { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected string end `\"`, but found {found:?}")]
+    #[error("{pos}: Expected string end `\"`, but found {found:?}")]
     UnexpectedTokenWhileParsingString { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected string end `\"`, but found {found:?}")]
+    #[error("{pos}: Expected string end `\"`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingString { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected escape sequence start `\\`, but found {found:?}")]
+    #[error("{pos}: Expected escape sequence start `\\`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingEscapeSequence { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected number start `-` or 0-9 , but found {found:?}")]
+    #[error("{pos}: Expected number start `-` or 0-9 , but found {found:?}")]
     UnexpectedTokenWhileStartParsingNumber { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected bool, but found {found:?}")]
+    #[error("{pos}: Expected bool, but found {found:?}")]
     UnexpectedTokenWhileParsingBool { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected null, but found {found:?}")]
+    #[error("{pos}: Expected null, but found {found:?}")]
     UnexpectedTokenWhileParsingNull { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected object start `{{`, but found {found:?}")]
+    #[error("{pos}: Expected object start `{{`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingObject { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected object end `}}`, but found {found:?}")]
+    #[error("{pos}: Expected object end `}}`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingObject { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected object key, but found {found:?}")]
+    #[error("{pos}: Expected object key, but found {found:?}")]
     UnexpectedTokenWhileParsingObjectKey { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected object value start `:`, but found {found:?}")]
+    #[error("{pos}: Expected object value start `:`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingObjectValue { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected object value end `,` or `}}`, but found {found:?}")]
+    #[error("{pos}: Expected object value end `,` or `}}`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingObjectValue { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected array start `[`, but found {found:?}")]
+    #[error("{pos}: Expected array start `[`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingArray { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected array end `]`, but found {found:?}")]
+    #[error("{pos}: Expected array end `]`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingArray { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected array value, but found {found:?}")]
+    #[error("{pos}: Expected array value, but found {found:?}")]
     UnexpectedTokenWhileParsingArrayValue { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected enum start `{{`, but found {found:?}")]
+    #[error("{pos}: Expected enum start `{{`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingEnum { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected enum end `}}`, but found {found:?}")]
+    #[error("{pos}: Expected enum end `}}`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingEnum { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected enum value start `:`, but found {found:?}")]
+    #[error("{pos}: Expected enum value start `:`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingEnumValue { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected comment start `//` or `/*`, but found {found:?}")]
+    #[error("{pos}: Expected comment start `//` or `/*`, but found {found:?}")]
     UnexpectedTokenWhileStartParsingComment { pos: Position, found: u8 },
 
-    #[error("{pos:?}: Expected comment end `*/`, but found {found:?}")]
+    #[error("{pos}: Expected comment end `*/`, but found {found:?}")]
     UnexpectedTokenWhileEndParsingComment { pos: Position, found: u8 },
 
     #[error("Expected value, but got EOF")]
@@ -209,34 +212,34 @@ pub enum SyntaxError {
     #[error("Expected start comment `//` or `/*`, but got EOF")]
     EofWhileStartParsingComment,
 
-    #[error("{pos:?}: Expected ident {expected:?}, but found {found:?}")]
+    #[error("{}-{}: Expected ident {expected:?}, but found {found:?}", pos.0, pos.1)]
     UnexpectedIdent { pos: PosRange, expected: Vec<u8>, found: Vec<u8> },
 
-    #[error("{pos:?}: Expected EOF, but found trailing {found:?}")]
+    #[error("{pos}: Expected EOF, but found trailing {found:?}")]
     ExpectedEof { pos: Position, found: u8 },
 
-    #[error("{pos:?}: control character U+{c:04X} must be escaped in string")]
+    #[error("{pos}: control character U+{c:04X} must be escaped in string")]
     ControlCharacterWhileParsingString { pos: Position, c: u8 },
 
-    #[error("{pos:?}: invalid escape sequence \\{found:?}")]
+    #[error("{pos}: invalid escape sequence \\{found:?}")]
     InvalidEscapeSequence { pos: Position, found: u8 },
 
-    #[error("{pos:?}: invalid \\uXXXX escape, cannot parse {found:?} as hex digit")]
+    #[error("{pos}: invalid \\uXXXX escape, cannot parse {found:?} as hex digit")]
     InvalidUnicodeEscape { pos: Position, found: u8 },
 
-    #[error("{pos:?}: cannot convert {char:08X} to char")]
+    #[error("{pos}: cannot convert {char:08X} to char")]
     CannotConvertChar { pos: Position, char: u32 },
 
-    #[error("{pos:?}: JSON with comments number does not start from `+`")]
+    #[error("{pos}: JSON with comments number does not start from `+`")]
     InvalidLeadingPlus { pos: Position },
 
-    #[error("{pos:?}: JSON with comments number is forbidden leading `0`")]
+    #[error("{pos}: JSON with comments number is forbidden leading `0`")]
     InvalidLeadingZeros { pos: Position },
 
-    #[error("{pos:?}: expect exponent part, but found {found:?}")]
+    #[error("{pos}: expect exponent part, but found {found:?}")]
     MissingExponent { pos: Position, found: u8 },
 
-    #[error("{pos:?}: expect fraction part, but found {found:?}")]
+    #[error("{pos}: expect fraction part, but found {found:?}")]
     MissingFraction { pos: Position, found: u8 },
 
     #[error("comment starts with `/*` must be ends with `*/`, but got EoF")]
@@ -250,7 +253,7 @@ impl From<SyntaxError> for JsonWithCommentsError {
 
 #[derive(Error, Debug)]
 pub enum SemanticError {
-    #[error("{pos:?}: Expected struct start with `{{` or `[`, but found {found:?}")]
+    #[error("{pos}: Expected struct start with `{{` or `[`, but found {found:?}")]
     ExpectStruct { pos: Position, found: u8 },
 
     #[error("map key of JSON with comments must be string")]
@@ -259,8 +262,17 @@ pub enum SemanticError {
     #[error("JSON with comments must not be empty")]
     EmptyJsonWithComment,
 
-    #[error("{pos:?}: cannot convert {rep:?} to number")]
+    #[error("{pos}: cannot convert {rep:?} to number")]
     InvalidNumber { pos: Position, rep: String },
+
+    #[error("non-finite number {rep:?} cannot be represented in JSON with comments")]
+    NonFiniteNumber { rep: String },
+
+    #[error("this tokenizer cannot capture the source text of raw value")]
+    RawValueCapture,
+
+    #[error("raw value must be borrowed from the source text, use `Box<RawValue>` instead")]
+    BorrowedRawValue,
 }
 impl From<SemanticError> for JsonWithCommentsError {
     fn from(err: SemanticError) -> Self {
@@ -268,6 +280,23 @@ impl From<SemanticError> for JsonWithCommentsError {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum EncodingError {
+    #[error("{encoding} input is detected, but only UTF-8 is accepted without `Decoding::Detect`")]
+    TranscodingDisabled { encoding: Encoding },
+
+    #[error("invalid {encoding} code unit {unit:#X}")]
+    InvalidCodeUnit { encoding: Encoding, unit: u32 },
+
+    #[error("{encoding} input ends with incomplete code unit")]
+    IncompleteCodeUnit { encoding: Encoding },
+}
+impl From<EncodingError> for JsonWithCommentsError {
+    fn from(err: EncodingError) -> Self {
+        JsonWithCommentsError::new(err)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ConvertError {
     #[error("Cannot convert float to integer")]
@@ -281,6 +310,12 @@ pub enum ConvertError {
 
     #[error("converted range must contain converting range")]
     InvalidFloatConvert,
+
+    #[error("number is out of range of {target}")]
+    OutOfRange { target: &'static str },
+
+    #[error("number cannot be represented as {target} without precision loss")]
+    PrecisionLoss { target: &'static str },
 }
 impl From<ConvertError> for JsonWithCommentsError {
     fn from(err: ConvertError) -> Self {
@@ -327,6 +362,9 @@ pub enum IndexError {
 
     #[error("not exist key {key:?}")]
     NotExistKey { key: String },
+
+    #[error("index {index} is out of range of array of length {len}")]
+    OutOfRange { index: usize, len: usize },
 }
 impl From<IndexError> for JsonWithCommentsError {
     fn from(err: IndexError) -> Self {
@@ -334,6 +372,86 @@ impl From<IndexError> for JsonWithCommentsError {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum PointerError {
+    #[error("JSON pointer {pointer:?} must be empty or start with `/`")]
+    InvalidPointer { pointer: String },
+
+    #[error("reference token {token:?} has invalid escape, `~` must be followed by `0` or `1`")]
+    InvalidEscape { token: String },
+
+    #[error("reference token {token:?} is not valid array index")]
+    InvalidArrayIndex { token: String },
+
+    #[error("{pointer:?}: index {index} is out of range of array of length {len}")]
+    OutOfRange { pointer: String, index: usize, len: usize },
+
+    #[error("{pointer:?}: {value} value cannot be traversed by {token:?}")]
+    CannotTraverse { pointer: String, token: String, value: String },
+}
+impl From<PointerError> for JsonWithCommentsError {
+    fn from(err: PointerError) -> Self {
+        JsonWithCommentsError::new(err)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PathError {
+    #[error("JSONPath at {pos}: expected {expected}, but found {found:?}")]
+    UnexpectedToken { pos: usize, expected: &'static str, found: String },
+
+    #[error("JSONPath at {pos}: integer is out of range of I-JSON")]
+    IntegerOutOfRange { pos: usize },
+
+    #[error("JSONPath at {pos}: invalid unicode escape")]
+    InvalidUnicode { pos: usize },
+
+    #[error("JSONPath at {pos}: unknown function `{name}`")]
+    UnknownFunction { pos: usize, name: String },
+
+    #[error("JSONPath at {pos}: function `{name}` requires `regex` feature")]
+    RegexFeatureRequired { pos: usize, name: String },
+
+    #[error("JSONPath at {pos}: expression is not well-typed")]
+    NotWellTyped { pos: usize },
+
+    #[error("selected nodes {ancestor} and {descendant} overlap, cannot borrow both mutably")]
+    OverlappingSelection { ancestor: String, descendant: String },
+}
+impl From<PathError> for JsonWithCommentsError {
+    fn from(err: PathError) -> Self {
+        JsonWithCommentsError::new(err)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PatchError {
+    #[error("{pointer:?}: target value does not exist")]
+    NotFound { pointer: String },
+
+    #[error("{pointer:?}: test failed, value is not equal to expected")]
+    TestFailed { pointer: String },
+
+    #[error("{from:?} cannot be moved into its own child {pointer:?}")]
+    MoveIntoChild { from: String, pointer: String },
+}
+impl From<PatchError> for JsonWithCommentsError {
+    fn from(err: PatchError) -> Self {
+        JsonWithCommentsError::new(err)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum MergeError {
+    #[error("{pointer:?}: merge conflict is not resolved")]
+    Unresolved { pointer: String },
+}
+impl From<MergeError> for JsonWithCommentsError {
+    fn from(err: MergeError) -> Self {
+        JsonWithCommentsError::new(err)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum Ensure {
     #[error("next should return peeked value")]
@@ -362,6 +480,18 @@ pub enum Ensure {
 
     #[error("ensure map like variant")]
     MapLikeVariant,
+
+    #[error("selected node should be reachable by its normalized path")]
+    SelectedNode,
+
+    #[error("capture should be started before it ends")]
+    StartCapture,
+
+    #[error("finished push parser should have completed the root value")]
+    CompletedRoot,
+
+    #[error("serialization future should be polled only once")]
+    PolledTwice,
 }
 impl From<Ensure> for JsonWithCommentsError {
     fn from(err: Ensure) -> Self {
@@ -58,7 +58,8 @@
 //! let data: Value = from_str(json).unwrap();
 //! assert_eq!(data["name"], Value::String("John Doe".into()));
 //! assert_eq!(data["address"]["street"], Value::String("Main".into()));
-//! assert_eq!(data.query("address.number"), Some(&42.into()));
+//! assert_eq!(data.pointer("/address/number"), Some(&42.into()));
+//! assert_eq!(data.select("$..number").unwrap(), vec![&42.into()]);
 //! assert_eq!(data, jsonc!({ "name": "John Doe", "address": { "street": "Main", "number": 42 }}));
 //! ```
 //!
@@ -129,9 +130,17 @@ pub mod error;
 pub mod ser;
 pub mod value;
 
-pub use de::{from_file, from_path, from_read, from_str, from_str_raw, from_value};
+pub use de::{
+    from_file, from_path, from_path_with, from_read, from_read_with, from_str, from_str_raw, from_str_with_spans,
+    from_value,
+};
 pub use error::{JsonWithCommentsError as Error, Result};
-pub use ser::{to_file, to_file_pretty, to_path, to_path_pretty, to_string, to_string_pretty, to_value, to_write};
+pub use ser::{
+    to_file, to_file_pretty, to_fmt, to_path, to_path_pretty, to_string, to_string_canonical, to_string_pretty,
+    to_string_with, to_value, to_write,
+};
+#[cfg(feature = "async")]
+pub use {de::from_async_read, ser::to_async_write};
 
 /// [`Value`] is type alias for [`value::JsoncValue<i64, f64>`].
 pub type Value = value::JsoncValue<i64, f64>;
@@ -1,12 +1,15 @@
 pub mod access;
+#[cfg(feature = "async")]
+pub mod asynchronous;
 pub mod formatter;
+pub mod write;
 
 use serde::ser;
-use std::{fs::File, io, path::Path};
+use std::{fmt, fs::File, io, path::Path};
 
 use crate::Value;
 
-use self::access::jsonc::JsoncSerializer;
+use self::{access::jsonc::JsoncSerializer, write::FmtWriter};
 
 /// Serialize struct `S` as minified JSON with comments text.
 /// If you want to serialize as pretty formatted JSONC text, use [`to_string_pretty`] instead.
@@ -32,9 +35,7 @@ pub fn to_string<S>(value: S) -> crate::Result<String>
 where
     S: ser::Serialize,
 {
-    let mut write = Vec::new();
-    to_write(value, &mut write, formatter::minify::MinifyFormatter)?;
-    Ok(unsafe { String::from_utf8_unchecked(write) }) // TODO maybe safe
+    to_string_with(value, formatter::minify::MinifyFormatter::new())
 }
 
 /// Serialize struct `S` as pretty formatted JSON with comments text.
@@ -70,9 +71,49 @@ pub fn to_string_pretty<S>(value: S) -> crate::Result<String>
 where
     S: ser::Serialize,
 {
-    let mut write = Vec::new();
-    to_write(value, &mut write, formatter::pretty::PrettyFormatter::new())?;
-    Ok(unsafe { String::from_utf8_unchecked(write) }) // TODO maybe safe
+    to_string_with(value, formatter::pretty::PrettyFormatter::new())
+}
+
+/// Serialize struct `S` as canonical JSON text defined by [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785).
+/// Object keys are sorted, and the output does not depend on formatting of the source text.
+///
+/// # Examples
+/// ```
+/// let a = json_with_comments::from_str::<json_with_comments::Value>(r#"{"b": [1.0, 2], "a": null}"#).unwrap();
+/// let b = json_with_comments::from_str::<json_with_comments::Value>(r#"
+/// {
+///     // comment
+///     "a": null,
+///     "b": [1, 2,],
+/// }"#).unwrap();
+/// assert_eq!(json_with_comments::to_string_canonical(&a).unwrap(), r#"{"a":null,"b":[1,2]}"#);
+/// assert_eq!(json_with_comments::to_string_canonical(&b).unwrap(), r#"{"a":null,"b":[1,2]}"#);
+/// ```
+pub fn to_string_canonical<S>(value: S) -> crate::Result<String>
+where
+    S: ser::Serialize,
+{
+    to_string_with(value, formatter::canonical::CanonicalFormatter::new())
+}
+
+/// Serialize struct `S` as JSON with comments text formatted by the given formatter.
+/// If the formatter writes invalid UTF-8, an error is returned.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::pretty::{Indent, PrettyFormatter, TrailingComma};
+/// let formatter = PrettyFormatter::builder().indent(Indent::Tab).trailing_comma(TrailingComma::Never).build();
+/// let text = json_with_comments::to_string_with(json_with_comments::jsonc!({"key": [1]}), formatter).unwrap();
+/// assert_eq!(text, "{\n\t\"key\": [\n\t\t1\n\t]\n}");
+/// ```
+pub fn to_string_with<S, F>(value: S, formatter: F) -> crate::Result<String>
+where
+    S: ser::Serialize,
+    F: formatter::JsoncFormatter,
+{
+    let mut string = String::new();
+    to_fmt(value, &mut string, formatter)?;
+    Ok(string)
 }
 
 /// Serialize struct `S` as a minified JSON with comments text of the given path.
@@ -174,7 +215,7 @@ pub fn to_file<S>(value: S, file: &mut File) -> crate::Result<()>
 where
     S: ser::Serialize,
 {
-    to_write(value, file, formatter::minify::MinifyFormatter)
+    to_write(value, file, formatter::minify::MinifyFormatter::new())
 }
 
 /// Serialize struct `S` as a pretty formatted JSON with comments text of the given file.
@@ -217,7 +258,7 @@ pub fn to_file_pretty(value: impl ser::Serialize, file: &mut File) -> crate::Res
 /// }
 /// let mut write = Vec::new();
 /// let product = Product { name: "candy".to_string(), price: 100 };
-/// json_with_comments::to_write(product, &mut write, json_with_comments::ser::formatter::minify::MinifyFormatter).unwrap();
+/// json_with_comments::to_write(product, &mut write, json_with_comments::ser::formatter::minify::MinifyFormatter::new()).unwrap();
 /// assert_eq!(String::from_utf8(write).unwrap(), r#"{"name":"candy","price":100}"#);
 /// ```
 pub fn to_write<W, F, S>(value: S, write: W, formatter: F) -> crate::Result<()>
@@ -227,7 +268,76 @@ where
     S: ser::Serialize,
 {
     let mut ser = JsoncSerializer::new(write, formatter);
-    value.serialize(&mut ser)
+    value.serialize(&mut ser)?;
+    ser.finish()
+}
+
+/// Serialize struct `S` as a JSON with comments text of the given [`tokio::io::AsyncWrite`].
+/// Serialization of serde is synchronous, so it runs on the current task and writes the text at chunk boundaries
+/// while the writer is ready. Text that the writer cannot accept yet is buffered and written asynchronously after
+/// the serialization, so the whole text is buffered in memory only if the writer is never ready.
+///
+/// # Examples
+/// ```
+/// use serde::Serialize;
+/// use tokio::io::AsyncReadExt;
+/// #[derive(Serialize)]
+/// struct Product {
+///     name: String,
+///     price: u32,
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (mut client, mut server) = tokio::io::duplex(1024);
+/// let product = Product { name: "candy".to_string(), price: 100 };
+/// let formatter = json_with_comments::ser::formatter::minify::MinifyFormatter::new();
+/// json_with_comments::to_async_write(&product, &mut client, formatter).await.unwrap();
+/// drop(client);
+///
+/// let mut written = String::new();
+/// server.read_to_string(&mut written).await.unwrap();
+/// assert_eq!(written, r#"{"name":"candy","price":100}"#);
+/// # });
+/// ```
+#[cfg(feature = "async")]
+pub async fn to_async_write<W, F, S>(value: S, mut write: W, formatter: F) -> crate::Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+    F: formatter::JsoncFormatter,
+    S: ser::Serialize,
+{
+    use tokio::io::AsyncWriteExt;
+    let mut serializing = Some((value, formatter));
+    let rest = std::future::poll_fn(|cx| {
+        let (value, formatter) = serializing.take().ok_or(crate::error::Ensure::PolledTwice)?;
+        let mut writer = asynchronous::PollWriter::new(std::pin::Pin::new(&mut write), cx);
+        to_write(value, &mut writer, formatter)?;
+        std::task::Poll::Ready(Ok::<_, crate::Error>(writer.into_buffer()))
+    })
+    .await?;
+    write.write_all(&rest).await?;
+    Ok(write.flush().await?)
+}
+
+/// Serialize struct `S` as a JSON with comments text of the given [`fmt::Write`], such as [`fmt::Formatter`].
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::minify::MinifyFormatter;
+/// let mut text = String::from("value: ");
+/// json_with_comments::to_fmt(vec![1, 2], &mut text, MinifyFormatter::new()).unwrap();
+/// assert_eq!(text, "value: [1,2]");
+/// ```
+pub fn to_fmt<W, F, S>(value: S, write: W, formatter: F) -> crate::Result<()>
+where
+    W: fmt::Write,
+    F: formatter::JsoncFormatter,
+    S: ser::Serialize,
+{
+    let mut write = FmtWriter::new(write);
+    to_write(value, &mut write, formatter)?;
+    write.finish()?;
+    Ok(())
 }
 
 /// Serialize `T` to [`crate::value::JsoncValue`]
@@ -28,6 +28,8 @@ mod tests {
         assert_eq!(to_string("linefeed\u{000A}").unwrap(), r#""linefeed\n""#);
         assert_eq!(to_string("null\u{0000}").unwrap(), r#""null\u0000""#);
         assert_eq!(to_string("del\u{007f}").unwrap(), r#""del\u007F""#);
+        assert_eq!(to_string("\"quote\"").unwrap(), r#""\"quote\"""#);
+        assert_eq!(to_string(r"back\slash/").unwrap(), r#""back\\slash/""#);
     }
 
     #[test]
@@ -137,6 +139,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_unknown_length() {
+        struct Unsized<T>(Vec<T>);
+        impl<T: Serialize> Serialize for Unsized<T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().filter(|_| true))
+            }
+        }
+        assert_eq!(to_string(Unsized::<()>(vec![])).unwrap(), "[]");
+        assert_eq!(to_string(Unsized(vec![1])).unwrap(), "[1]");
+        assert_eq!(to_string(Unsized(vec![1, 2, 3])).unwrap(), "[1,2,3]");
+        assert_eq!(to_string_pretty(Unsized(vec![1, 2])).unwrap(), ["[", "  1,", "  2,", "]"].join("\n"));
+
+        #[derive(Serialize)]
+        struct Config {
+            name: &'static str,
+            #[serde(flatten)]
+            server: Server,
+        }
+        #[derive(Serialize)]
+        struct Server {
+            host: &'static str,
+            port: u16,
+        }
+        let config = Config { name: "app", server: Server { host: "localhost", port: 8080 } };
+        assert_eq!(to_string(&config).unwrap(), r#"{"name":"app","host":"localhost","port":8080}"#);
+        assert_eq!(
+            to_string_pretty(&config).unwrap(),
+            [r#"{"#, r#"  "name": "app","#, r#"  "host": "localhost","#, r#"  "port": 8080,"#, r#"}"#].join("\n")
+        );
+    }
+
     #[test]
     fn test_serialize_pretty() {
         assert_eq!(
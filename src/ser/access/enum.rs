@@ -39,7 +39,7 @@ where
         serializer.formatter.write_object_value_start(&mut serializer.write, 0, Some(1))?;
         value.serialize(&mut *serializer)?;
         serializer.formatter.write_object_value_end(&mut serializer.write, 0, Some(1))?;
-        serializer.formatter.write_object_end(&mut serializer.write)
+        serializer.formatter.write_object_end(&mut serializer.write, 1)
     }
 
     pub fn start_tuple_variant(
@@ -98,8 +98,8 @@ where
         match self.delegate {
             Delegate::Seq(seq) => {
                 // seq.end()?; // end() cause move
-                seq.serializer.formatter.write_array_end(&mut seq.serializer.write)?;
-                seq.serializer.formatter.write_object_end(&mut seq.serializer.write)
+                seq.serializer.formatter.write_array_end(&mut seq.serializer.write, seq.index)?;
+                seq.serializer.formatter.write_object_end(&mut seq.serializer.write, 1)
             }
             Delegate::Map(_) => Err(Ensure::SeqLikeVariant)?,
         }
@@ -129,8 +129,8 @@ where
             Delegate::Seq(_) => Err(Ensure::MapLikeVariant)?,
             Delegate::Map(map) => {
                 // map.end()?; // end() cause move
-                map.serializer.formatter.write_object_end(&mut map.serializer.write)?;
-                map.serializer.formatter.write_object_end(&mut map.serializer.write)
+                map.serializer.formatter.write_object_end(&mut map.serializer.write, map.index)?;
+                map.serializer.formatter.write_object_end(&mut map.serializer.write, 1)
             }
         }
     }
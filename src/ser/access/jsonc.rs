@@ -10,6 +10,8 @@ where
 {
     pub(crate) write: W,
     pub(crate) formatter: F,
+    /// Next string is a raw value, that should be written as it is.
+    raw_value: bool,
 }
 
 impl<W, F> JsoncSerializer<W, F>
@@ -18,7 +20,11 @@ where
     F: JsoncFormatter,
 {
     pub fn new(write: W, formatter: F) -> Self {
-        JsoncSerializer { write, formatter }
+        JsoncSerializer { write, formatter, raw_value: false }
+    }
+
+    pub fn finish(&mut self) -> crate::Result<()> {
+        self.formatter.write_document_end(&mut self.write)
     }
 }
 
@@ -88,7 +94,10 @@ where
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        self.formatter.write_str(&mut self.write, v)
+        match std::mem::take(&mut self.raw_value) {
+            true => self.formatter.write_raw_fragment(&mut self.write, v.as_bytes()),
+            false => self.formatter.write_str(&mut self.write, v),
+        }
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
@@ -123,10 +132,11 @@ where
         self.serialize_str(variant)
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T: ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ser::Serialize,
     {
+        self.raw_value = name == crate::value::raw::TOKEN;
         value.serialize(self)
     }
 
@@ -9,8 +9,8 @@ where
     F: JsoncFormatter,
 {
     pub(crate) serializer: &'a mut JsoncSerializer<W, F>,
-    index: usize,
-    len: Option<usize>,
+    pub(crate) index: usize,
+    pub(crate) len: Option<usize>,
 }
 
 impl<'a, W, F> MapSerializer<'a, W, F>
@@ -53,7 +53,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        self.serializer.formatter.write_object_end(&mut self.serializer.write)
+        self.serializer.formatter.write_object_end(&mut self.serializer.write, self.index)
     }
 }
 
@@ -100,9 +100,9 @@ where
         ) -> Result<<&'a mut Self as ser::Serializer>::Ok, <&'a mut Self as ser::Serializer>::Error>,
         <&'a mut Self as ser::Serializer>::Error: From<crate::Error>,
     {
-        self.serializer.write.write_all(b"\"")?;
+        self.serializer.formatter.write_raw_fragment(&mut self.serializer.write, b"\"")?;
         f(self)?;
-        self.serializer.write.write_all(b"\"")?;
+        self.serializer.formatter.write_raw_fragment(&mut self.serializer.write, b"\"")?;
         Ok(())
     }
 }
@@ -9,8 +9,8 @@ where
     F: JsoncFormatter,
 {
     pub(crate) serializer: &'a mut JsoncSerializer<W, F>,
-    index: usize,
-    len: Option<usize>,
+    pub(crate) index: usize,
+    pub(crate) len: Option<usize>,
 }
 
 impl<'a, W, F> SeqSerializer<'a, W, F>
@@ -43,7 +43,7 @@ where
     }
 
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        self.serializer.formatter.write_array_end(&mut self.serializer.write)
+        self.serializer.formatter.write_array_end(&mut self.serializer.write, self.index)
     }
 }
 
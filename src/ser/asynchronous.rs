@@ -0,0 +1,126 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio::io::AsyncWrite;
+
+/// Size of a chunk written to the async writer at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Synchronous writer that buffers the serialized text, and writes it to the async writer at chunk boundaries
+/// as long as the async writer is ready. The rest of the text remains in [`PollWriter::into_buffer`].
+#[derive(Debug)]
+pub struct PollWriter<'a, 'b, W> {
+    write: Pin<&'a mut W>,
+    cx: &'a mut Context<'b>,
+    buffer: Vec<u8>,
+    threshold: usize,
+}
+impl<'a, 'b, W: AsyncWrite> PollWriter<'a, 'b, W> {
+    /// Create a new [`PollWriter`] that writes to `write` while it is polled with `cx`.
+    pub fn new(write: Pin<&'a mut W>, cx: &'a mut Context<'b>) -> Self {
+        PollWriter { write, cx, buffer: Vec::with_capacity(CHUNK_SIZE), threshold: CHUNK_SIZE }
+    }
+
+    /// Get the buffered text that is not written to the async writer yet.
+    pub fn into_buffer(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Write the buffered text until the async writer is not ready.
+    /// If the async writer is not ready, next draining is postponed until one more chunk is buffered.
+    fn drain(&mut self) -> io::Result<()> {
+        let mut written = 0;
+        while written < self.buffer.len() {
+            match self.write.as_mut().poll_write(self.cx, &self.buffer[written..]) {
+                Poll::Ready(Ok(0)) => return Err(io::ErrorKind::WriteZero.into()),
+                Poll::Ready(Ok(n)) => written += n,
+                Poll::Ready(Err(e)) => return Err(e),
+                Poll::Pending => break,
+            }
+        }
+        self.buffer.drain(..written);
+        self.threshold = self.buffer.len() + CHUNK_SIZE;
+        Ok(())
+    }
+}
+impl<W: AsyncWrite> io::Write for PollWriter<'_, '_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= self.threshold {
+            self.drain()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // flushing is done by the async writer after the serialization
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        pin::Pin,
+        task::{Context, Waker},
+    };
+
+    use tokio::io::AsyncReadExt;
+
+    use crate::ser::formatter::minify::MinifyFormatter;
+
+    use super::{PollWriter, CHUNK_SIZE};
+
+    #[test]
+    fn test_poll_writer_drains_chunks() {
+        let (mut write, mut cx) = (Vec::new(), Context::from_waker(Waker::noop()));
+        let mut writer = PollWriter::new(Pin::new(&mut write), &mut cx);
+        for _ in 0..4 * CHUNK_SIZE {
+            writer.write_all(b"x").unwrap();
+            assert!(writer.buffer.len() <= CHUNK_SIZE);
+        }
+        let rest = writer.into_buffer();
+        assert_eq!(write.len() + rest.len(), 4 * CHUNK_SIZE);
+        assert!(rest.len() < CHUNK_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_to_async_write_duplex() {
+        let value = ("app".to_string(), (0..10000).collect::<Vec<u32>>());
+        let expected = crate::to_string(&value).unwrap();
+        assert!(expected.len() > 4 * super::CHUNK_SIZE);
+
+        // the duplex buffer is much smaller than the output, so the output must be read while it is written
+        let (client, mut server) = tokio::io::duplex(64);
+        let reader = tokio::spawn(async move {
+            let mut written = String::new();
+            server.read_to_string(&mut written).await.map(|_| written)
+        });
+        crate::to_async_write(&value, client, MinifyFormatter::new()).await.unwrap();
+        assert_eq!(reader.await.unwrap().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_to_async_write_errors() {
+        let (client, server) = tokio::io::duplex(64);
+        drop(server);
+        let ports: Vec<u32> = (0..10000).collect();
+        let err = crate::to_async_write(ports, client, MinifyFormatter::new()).await.unwrap_err();
+        assert_eq!(err.into_inner().downcast_ref::<std::io::Error>().unwrap().kind(), std::io::ErrorKind::BrokenPipe);
+
+        struct Failing;
+        impl serde::Serialize for Failing {
+            fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("failing"))
+            }
+        }
+        let mut write = Vec::new();
+        let err = crate::to_async_write(Failing, &mut write, MinifyFormatter::new()).await.unwrap_err();
+        assert_eq!(err.to_string(), "failing");
+        assert!(write.is_empty());
+    }
+}
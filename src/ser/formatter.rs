@@ -1,5 +1,10 @@
+pub mod canonical;
+pub mod escape;
 pub mod minify;
 pub mod pretty;
+pub mod width;
+
+use self::escape::EscapeStrategy;
 
 use super::access::number::ToNumberRepresentation;
 
@@ -24,27 +29,25 @@ pub trait JsoncFormatter {
     }
 
     fn write_str<W: std::io::Write>(&mut self, write: &mut W, value: &str) -> crate::Result<()> {
-        write.write_all(b"\"")?;
-        for &b in value.as_bytes() {
-            match b {
-                b'"' => write.write_all(br#"""#)?,
-                b'\\' => write.write_all(br"\\")?,
-                b'/' => write.write_all(br"/")?,
-                b'\x08' => write.write_all(br"\b")?,
-                b'\x0C' => write.write_all(br"\f")?,
-                b'\n' => write.write_all(br"\n")?,
-                b'\r' => write.write_all(br"\r")?,
-                b'\t' => write.write_all(br"\t")?,
-                b if b.is_ascii_control() => {
-                    let (big, little) = (b >> 4, b & 0x0F);
-                    let bb = if big < 10 { b'0' + big } else { b'A' + big - 10 };
-                    let lb = if little < 10 { b'0' + little } else { b'A' + little - 10 };
-                    write.write_all(&[b'\\', b'u', b'0', b'0', bb, lb])?
-                }
-                b => write.write_all(&[b])?,
-            };
+        self.escape_strategy().write_escaped_str(write, value)
+    }
+
+    fn escape_strategy(&self) -> EscapeStrategy {
+        EscapeStrategy::minimal()
+    }
+
+    /// Write `fragment` as it is, such as double quotes of non-string map key.
+    fn write_raw_fragment<W: std::io::Write>(&mut self, write: &mut W, fragment: &[u8]) -> crate::Result<()> {
+        Ok(write.write_all(fragment)?)
+    }
+
+    /// Separator between elements of array or entries of object, it is written before every non-first element.
+    /// So it works even if the length is unknown, such as `#[serde(flatten)]` or iterator.
+    fn write_separator<W: std::io::Write>(&mut self, write: &mut W, index: usize) -> crate::Result<()> {
+        if index > 0 {
+            write.write_all(b",")?;
         }
-        Ok(write.write_all(b"\"")?)
+        Ok(())
     }
 
     fn write_array_start<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
@@ -64,11 +67,11 @@ pub trait JsoncFormatter {
     }
     fn write_array_value_start_super<W: std::io::Write>(
         &mut self,
-        _write: &mut W,
-        _index: usize,
+        write: &mut W,
+        index: usize,
         _len: Option<usize>,
     ) -> crate::Result<()> {
-        Ok(())
+        self.write_separator(write, index)
     }
 
     fn write_array_value_end<W: std::io::Write>(
@@ -81,20 +84,17 @@ pub trait JsoncFormatter {
     }
     fn write_array_value_end_super<W: std::io::Write>(
         &mut self,
-        write: &mut W,
-        index: usize,
-        len: Option<usize>,
+        _write: &mut W,
+        _index: usize,
+        _len: Option<usize>,
     ) -> crate::Result<()> {
-        match len.map(|l| index + 1 < l) {
-            Some(true) => Ok(write.write_all(b",")?),
-            _ => Ok(()),
-        }
+        Ok(())
     }
 
-    fn write_array_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
-        self.write_array_end_super(write)
+    fn write_array_end<W: std::io::Write>(&mut self, write: &mut W, len: usize) -> crate::Result<()> {
+        self.write_array_end_super(write, len)
     }
-    fn write_array_end_super<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+    fn write_array_end_super<W: std::io::Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
         Ok(write.write_all(b"]")?)
     }
 
@@ -115,11 +115,11 @@ pub trait JsoncFormatter {
     }
     fn write_object_key_start_super<W: std::io::Write>(
         &mut self,
-        _write: &mut W,
-        _index: usize,
+        write: &mut W,
+        index: usize,
         _len: Option<usize>,
     ) -> crate::Result<()> {
-        Ok(())
+        self.write_separator(write, index)
     }
 
     fn write_object_key_end<W: std::io::Write>(
@@ -166,20 +166,24 @@ pub trait JsoncFormatter {
     }
     fn write_object_value_end_super<W: std::io::Write>(
         &mut self,
-        write: &mut W,
-        index: usize,
-        len: Option<usize>,
+        _write: &mut W,
+        _index: usize,
+        _len: Option<usize>,
     ) -> crate::Result<()> {
-        match len.map(|l| index + 1 < l) {
-            Some(true) => Ok(write.write_all(b",")?),
-            _ => Ok(()),
-        }
+        Ok(())
     }
 
-    fn write_object_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
-        self.write_object_end_super(write)
+    fn write_object_end<W: std::io::Write>(&mut self, write: &mut W, len: usize) -> crate::Result<()> {
+        self.write_object_end_super(write, len)
     }
-    fn write_object_end_super<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+    fn write_object_end_super<W: std::io::Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
         Ok(write.write_all(b"}")?)
     }
+
+    fn write_document_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        self.write_document_end_super(write)
+    }
+    fn write_document_end_super<W: std::io::Write>(&mut self, _write: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
 }
@@ -0,0 +1,287 @@
+use std::io::Write;
+
+use crate::{error::SemanticError, ser::access::number::ToNumberRepresentation};
+
+use super::{escape::EscapeSequence, JsoncFormatter};
+
+/// Formatter of canonical JSON defined by [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785) (JCS).
+/// Object entries are sorted by UTF-16 code units of their keys, numbers are written in ECMAScript format,
+/// strings are minimally escaped, and no whitespace and trailing comma are written.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::canonical::CanonicalFormatter;
+///
+/// let value = json_with_comments::jsonc!({"b": [1.0, 1e21, 1e-7], "a": {"€": "\u{7f}", "\r": "/"}});
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(value, &mut write, CanonicalFormatter::new()).unwrap();
+/// assert_eq!(String::from_utf8(write).unwrap(), "{\"a\":{\"\\r\":\"/\",\"€\":\"\u{7f}\"},\"b\":[1,1e+21,1e-7]}");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalFormatter {
+    stack: Vec<ObjectFrame>,
+}
+impl CanonicalFormatter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Objects are buffered until they are closed, so their nested values are written into the buffer.
+    fn sink<'a, W: Write>(&'a mut self, write: &'a mut W) -> &'a mut dyn Write {
+        match self.stack.last_mut() {
+            Some(frame) => &mut frame.buffer,
+            None => write,
+        }
+    }
+}
+impl JsoncFormatter for CanonicalFormatter {
+    fn write_bool<W: Write>(&mut self, write: &mut W, value: bool) -> crate::Result<()> {
+        Ok(self.sink(write).write_all(if value { b"true" } else { b"false" })?)
+    }
+
+    fn write_null<W: Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        Ok(self.sink(write).write_all(b"null")?)
+    }
+
+    fn write_number<W: Write, N: ToNumberRepresentation>(&mut self, write: &mut W, value: N) -> crate::Result<()>
+    where
+        crate::Error: From<N::Err>,
+    {
+        let canonical = canonical_number(&value.to_number_representation()?)?;
+        Ok(self.sink(write).write_all(canonical.as_bytes())?)
+    }
+
+    fn write_str<W: Write>(&mut self, write: &mut W, value: &str) -> crate::Result<()> {
+        let sink = self.sink(write);
+        sink.write_all(b"\"")?;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            let sequence = match c {
+                '"' => EscapeSequence::Short(b'"'),
+                '\\' => EscapeSequence::Short(b'\\'),
+                '\x08' => EscapeSequence::Short(b'b'),
+                '\x0C' => EscapeSequence::Short(b'f'),
+                '\n' => EscapeSequence::Short(b'n'),
+                '\r' => EscapeSequence::Short(b'r'),
+                '\t' => EscapeSequence::Short(b't'),
+                '\0'..='\x1F' => EscapeSequence::Unicode(c),
+                _ => continue,
+            };
+            sink.write_all(&value.as_bytes()[start..i])?;
+            match sequence {
+                EscapeSequence::Unicode(c) => write!(sink, "\\u{:04x}", c as u32)?,
+                short => short.write(sink)?,
+            }
+            start = i + c.len_utf8();
+        }
+        sink.write_all(&value.as_bytes()[start..])?;
+        Ok(sink.write_all(b"\"")?)
+    }
+
+    fn write_raw_fragment<W: Write>(&mut self, write: &mut W, fragment: &[u8]) -> crate::Result<()> {
+        Ok(self.sink(write).write_all(fragment)?)
+    }
+
+    fn write_array_start<W: Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        Ok(self.sink(write).write_all(b"[")?)
+    }
+
+    fn write_array_value_start<W: Write>(
+        &mut self,
+        write: &mut W,
+        index: usize,
+        _: Option<usize>,
+    ) -> crate::Result<()> {
+        if index > 0 {
+            self.sink(write).write_all(b",")?;
+        }
+        Ok(())
+    }
+
+    fn write_array_end<W: Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
+        Ok(self.sink(write).write_all(b"]")?)
+    }
+
+    fn write_object_start<W: Write>(&mut self, _write: &mut W) -> crate::Result<()> {
+        Ok(self.stack.push(ObjectFrame::default()))
+    }
+
+    fn write_object_key_start<W: Write>(&mut self, _: &mut W, _: usize, _: Option<usize>) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn write_object_key_end<W: Write>(&mut self, _: &mut W, _: usize, _: Option<usize>) -> crate::Result<()> {
+        if let Some(frame) = self.stack.last_mut() {
+            let key = std::mem::take(&mut frame.buffer);
+            let sort_key = crate::from_str::<String>(std::str::from_utf8(&key)?)?.encode_utf16().collect();
+            frame.key = Some((sort_key, key));
+        }
+        Ok(())
+    }
+
+    fn write_object_value_end<W: Write>(&mut self, _: &mut W, _: usize, _: Option<usize>) -> crate::Result<()> {
+        if let Some(frame) = self.stack.last_mut() {
+            let value = std::mem::take(&mut frame.buffer);
+            if let Some((sort_key, key)) = frame.key.take() {
+                frame.entries.push((sort_key, key, value));
+            }
+        }
+        Ok(())
+    }
+
+    fn write_object_end<W: Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
+        let mut frame = self.stack.pop().expect("object end should be paired with object start");
+        frame.entries.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        let sink = self.sink(write);
+        sink.write_all(b"{")?;
+        for (i, (_, key, value)) in frame.entries.iter().enumerate() {
+            if i > 0 {
+                sink.write_all(b",")?;
+            }
+            sink.write_all(key)?;
+            sink.write_all(b":")?;
+            sink.write_all(value)?;
+        }
+        Ok(sink.write_all(b"}")?)
+    }
+}
+
+/// Buffered object, its entries are sorted when it is closed.
+#[derive(Debug, Clone, Default)]
+struct ObjectFrame {
+    entries: Vec<(Vec<u16>, Vec<u8>, Vec<u8>)>,
+    key: Option<(Vec<u16>, Vec<u8>)>,
+    buffer: Vec<u8>,
+}
+
+/// Convert number representation to ECMAScript `Number.prototype.toString` format, as RFC 8785 requires.
+/// Integers that cannot be represented exactly in IEEE 754 double are also converted as double.
+fn canonical_number(representation: &[u8]) -> crate::Result<String> {
+    let rep = std::str::from_utf8(representation)?;
+    if let Ok(integer) = rep.parse::<i128>() {
+        if integer.unsigned_abs() <= 1 << 53 {
+            return Ok(integer.to_string());
+        }
+    }
+    let float: f64 = rep.parse()?;
+    if !float.is_finite() {
+        Err(SemanticError::NonFiniteNumber { rep: rep.to_string() })?
+    } else if float == 0.0 {
+        return Ok("0".to_string());
+    }
+
+    let (digits, n) = shortest_digits(float);
+    let (k, sign) = (digits.len() as i32, if float < 0.0 { "-" } else { "" });
+    let formatted = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let (e, exp) = (n - 1, if n - 1 < 0 { "-" } else { "+" });
+        match k {
+            1 => format!("{digits}e{exp}{}", e.abs()),
+            _ => format!("{}.{}e{exp}{}", &digits[..1], &digits[1..], e.abs()),
+        }
+    };
+    Ok(format!("{sign}{formatted}"))
+}
+
+/// Returns the shortest round-trip digits `s` and the exponent `n` such that `|float| = 0.s * 10^n`.
+fn shortest_digits(float: f64) -> (String, i32) {
+    let mut buffer = ryu::Buffer::new();
+    let represented = buffer.format_finite(float.abs());
+    let (mantissa, exponent) = match represented.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().unwrap_or_default()),
+        None => (represented, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{integer}{fraction}");
+    let trimmed = digits.trim_start_matches('0');
+    let leading = digits.len() - trimmed.len();
+    let trimmed = trimmed.trim_end_matches('0');
+    (trimmed.to_string(), integer.len() as i32 - leading as i32 + exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(value: impl serde::Serialize) -> String {
+        let mut write = Vec::new();
+        crate::to_write(value, &mut write, CanonicalFormatter::new()).unwrap();
+        String::from_utf8(write).unwrap()
+    }
+
+    #[test]
+    fn test_canonical_number() {
+        // https://www.rfc-editor.org/rfc/rfc8785#appendix-B
+        let expected = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (5e-324, "5e-324"),
+            (-5e-324, "-5e-324"),
+            (1.7976931348623157e308, "1.7976931348623157e+308"),
+            (-1.7976931348623157e308, "-1.7976931348623157e+308"),
+            (9007199254740992.0, "9007199254740992"),
+            (-9007199254740992.0, "-9007199254740992"),
+            (295147905179352830000.0, "295147905179352830000"),
+            (9.999999999999997e22, "9.999999999999997e+22"),
+            (1e23, "1e+23"),
+            (1.0000000000000001e23, "1.0000000000000001e+23"),
+            (999999999999999700000.0, "999999999999999700000"),
+            (999999999999999900000.0, "999999999999999900000"),
+            (1e21, "1e+21"),
+            (9.999999999999997e-7, "9.999999999999997e-7"),
+            (0.000001, "0.000001"),
+            (333333333.3333332, "333333333.3333332"),
+            (333333333.33333325, "333333333.33333325"),
+            (333333333.3333333, "333333333.3333333"),
+            (333333333.3333334, "333333333.3333334"),
+            (333333333.33333343, "333333333.33333343"),
+            (1424953923781206.2, "1424953923781206.2"),
+            (4.5, "4.5"),
+            (2e-3, "0.002"),
+            (0.000000000000000000000000001, "1e-27"),
+        ];
+        for (float, rep) in expected {
+            assert_eq!(canonical(float), rep, "{float:e}");
+        }
+
+        assert_eq!(canonical(100), "100");
+        assert_eq!(canonical(-9007199254740992i64), "-9007199254740992");
+        assert_eq!(canonical(u64::MAX), "18446744073709552000");
+        assert!(crate::to_string_canonical(f64::NAN).is_err());
+        assert!(crate::to_string_canonical(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_canonical_string() {
+        assert_eq!(canonical("\u{20ac}$\u{f}\nA'B\"\\\\\"/"), r#""€$\u000f\nA'B\"\\\\\"/""#);
+        assert_eq!(canonical("\u{7f}\u{1f}\u{8}"), "\"\u{7f}\\u001f\\b\"");
+    }
+
+    #[test]
+    fn test_canonical_sort() {
+        // https://www.rfc-editor.org/rfc/rfc8785#section-3.2.3
+        let target = crate::jsonc!({
+            "\u{20ac}": "Euro Sign",
+            "\r": "Carriage Return",
+            "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "\u{1f600}": "Emoji: Grinning Face",
+            "\u{80}": "Control",
+            "\u{f6}": "Latin Small Letter O With Diaeresis",
+        });
+        let keys = ["\r", "1", "\u{80}", "\u{f6}", "\u{20ac}", "\u{1f600}", "\u{fb33}"];
+        let expected =
+            keys.iter().map(|k| format!("{}:{}", canonical(k), canonical(&target[*k]))).collect::<Vec<_>>().join(",");
+        assert_eq!(canonical(&target), format!("{{{expected}}}"));
+
+        let nested = crate::jsonc!([{"b": {"d": 1, "c": [{"f": 2, "e": 3}]}, "a": []}, {}]);
+        assert_eq!(canonical(nested), r#"[{"a":[],"b":{"c":[{"e":3,"f":2}],"d":1}},{}]"#);
+        let numeric_key = std::collections::HashMap::from([(10, true), (2, false)]);
+        assert_eq!(canonical(numeric_key), r#"{"10":true,"2":false}"#);
+    }
+}
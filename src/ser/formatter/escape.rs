@@ -0,0 +1,169 @@
+/// Strategy of escaping string value in JSON with comments text.
+/// Characters that must be escaped (`"`, `\` and control characters) are always escaped,
+/// and the other characters are escaped according to the enabled options.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::{escape::EscapeStrategy, minify::MinifyFormatter};
+///
+/// let write = |escape| {
+///     let mut write = Vec::new();
+///     json_with_comments::to_write("<a href=\"/\">日本🗾</a>", &mut write, MinifyFormatter::with_escape(escape)).unwrap();
+///     String::from_utf8(write).unwrap()
+/// };
+///
+/// assert_eq!(write(EscapeStrategy::minimal()), r#""<a href=\"/\">日本🗾</a>""#);
+/// assert_eq!(write(EscapeStrategy::ascii_only()), r#""<a href=\"/\">\u65E5\u672C\uD83D\uDDFE</a>""#);
+/// assert_eq!(write(EscapeStrategy::html_safe()), r#""\u003Ca href=\"/\"\u003E日本🗾\u003C/a\u003E""#);
+/// assert_eq!(write(EscapeStrategy::minimal().slash(true)), r#""<a href=\"\/\">日本🗾<\/a>""#);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EscapeStrategy {
+    ascii_only: bool,
+    html_safe: bool,
+    slash: bool,
+}
+impl EscapeStrategy {
+    /// Escape only the characters that must be escaped in JSON with comments string.
+    pub fn minimal() -> Self {
+        Self::default()
+    }
+
+    /// Escape all non-ASCII characters as `\uXXXX`, characters out of BMP are escaped as surrogate pair.
+    pub fn ascii_only() -> Self {
+        Self::default().ascii(true)
+    }
+
+    /// Escape `<`, `>` and `&` as `\uXXXX`, so that the output can be embedded in HTML.
+    pub fn html_safe() -> Self {
+        Self::default().html(true)
+    }
+
+    /// Set whether to escape all non-ASCII characters (see [`Self::ascii_only`]).
+    pub fn ascii(self, ascii_only: bool) -> Self {
+        Self { ascii_only, ..self }
+    }
+
+    /// Set whether to escape `<`, `>` and `&` (see [`Self::html_safe`]).
+    pub fn html(self, html_safe: bool) -> Self {
+        Self { html_safe, ..self }
+    }
+
+    /// Set whether to escape `/` as `\/`.
+    pub fn slash(self, slash: bool) -> Self {
+        Self { slash, ..self }
+    }
+
+    /// Returns the escape sequence of `c`, or `None` if `c` can be written as it is.
+    pub fn escape(&self, c: char) -> Option<EscapeSequence> {
+        match c {
+            '"' => Some(EscapeSequence::Short(b'"')),
+            '\\' => Some(EscapeSequence::Short(b'\\')),
+            '\x08' => Some(EscapeSequence::Short(b'b')),
+            '\x0C' => Some(EscapeSequence::Short(b'f')),
+            '\n' => Some(EscapeSequence::Short(b'n')),
+            '\r' => Some(EscapeSequence::Short(b'r')),
+            '\t' => Some(EscapeSequence::Short(b't')),
+            '/' if self.slash => Some(EscapeSequence::Short(b'/')),
+            '<' | '>' | '&' if self.html_safe => Some(EscapeSequence::Unicode(c)),
+            c if c.is_ascii_control() => Some(EscapeSequence::Unicode(c)),
+            c if self.ascii_only && !c.is_ascii() => Some(EscapeSequence::Unicode(c)),
+            _ => None,
+        }
+    }
+
+    /// Write `value` as JSON with comments string, that is enclosed in `"` and escaped by this strategy.
+    pub fn write_escaped_str<W: std::io::Write>(&self, write: &mut W, value: &str) -> crate::Result<()> {
+        write.write_all(b"\"")?;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            if let Some(sequence) = self.escape(c) {
+                write.write_all(&value.as_bytes()[start..i])?;
+                sequence.write(write)?;
+                start = i + c.len_utf8();
+            }
+        }
+        write.write_all(&value.as_bytes()[start..])?;
+        Ok(write.write_all(b"\"")?)
+    }
+}
+
+/// Escape sequence of a character in JSON with comments string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EscapeSequence {
+    /// Two-character escape sequence such as `\n`, the value is the byte following `\`.
+    Short(u8),
+    /// `\uXXXX` escape sequence, characters out of BMP are represented as surrogate pair `\uXXXX\uXXXX`.
+    Unicode(char),
+}
+impl EscapeSequence {
+    pub fn write<W: std::io::Write + ?Sized>(&self, write: &mut W) -> crate::Result<()> {
+        match *self {
+            EscapeSequence::Short(b) => Ok(write.write_all(&[b'\\', b])?),
+            EscapeSequence::Unicode(c) => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    write.write_all(&Self::unicode_escape(*unit))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn unicode_escape(unit: u16) -> [u8; 6] {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        let hex = |shift: u16| HEX[((unit >> shift) & 0x0F) as usize];
+        [b'\\', b'u', hex(12), hex(8), hex(4), hex(0)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escaped(strategy: EscapeStrategy, value: &str) -> String {
+        let mut write = Vec::new();
+        strategy.write_escaped_str(&mut write, value).unwrap();
+        String::from_utf8(write).unwrap()
+    }
+
+    #[test]
+    fn test_minimal_escape() {
+        let minimal = EscapeStrategy::minimal();
+        assert_eq!(escaped(minimal, ""), r#""""#);
+        assert_eq!(escaped(minimal, r#""quote""#), r#""\"quote\"""#);
+        assert_eq!(escaped(minimal, r"back\slash"), r#""back\\slash""#);
+        assert_eq!(escaped(minimal, "slash/"), r#""slash/""#);
+        assert_eq!(escaped(minimal, "\x08\x0C\n\r\t"), r#""\b\f\n\r\t""#);
+        assert_eq!(escaped(minimal, "null\0 esc\x1b del\x7f"), r#""null\u0000 esc\u001B del\u007F""#);
+        assert_eq!(escaped(minimal, "<&>"), r#""<&>""#);
+        assert_eq!(escaped(minimal, "epsilon ε 💯"), r#""epsilon ε 💯""#);
+    }
+
+    #[test]
+    fn test_ascii_only_escape() {
+        let ascii = EscapeStrategy::ascii_only();
+        assert_eq!(escaped(ascii, "ascii"), r#""ascii""#);
+        assert_eq!(escaped(ascii, "epsilon ε"), r#""epsilon \u03B5""#);
+        assert_eq!(escaped(ascii, "日本語"), r#""\u65E5\u672C\u8A9E""#);
+        assert_eq!(escaped(ascii, "💯"), r#""\uD83D\uDCAF""#);
+        assert_eq!(escaped(ascii, "\u{10FFFF}"), r#""\uDBFF\uDFFF""#);
+        assert_eq!(escaped(ascii, "\"\n\""), r#""\"\n\"""#);
+    }
+
+    #[test]
+    fn test_html_safe_escape() {
+        let html = EscapeStrategy::html_safe();
+        assert_eq!(escaped(html, "<script>"), r#""\u003Cscript\u003E""#);
+        assert_eq!(escaped(html, "a & b"), r#""a \u0026 b""#);
+        assert_eq!(escaped(html, "</script>"), r#""\u003C/script\u003E""#);
+        assert_eq!(escaped(html.slash(true), "</script>"), r#""\u003C\/script\u003E""#);
+        assert_eq!(escaped(html.ascii(true), "<ε>"), r#""\u003C\u03B5\u003E""#);
+    }
+
+    #[test]
+    fn test_slash_escape() {
+        let slash = EscapeStrategy::minimal().slash(true);
+        assert_eq!(escaped(slash, "a/b/c"), r#""a\/b\/c""#);
+        assert_eq!(escaped(slash, r"\/"), r#""\\\/""#);
+    }
+}
@@ -1,4 +1,40 @@
-use super::JsoncFormatter;
+use super::{escape::EscapeStrategy, JsoncFormatter};
 
+/// Formatter that writes minified text, strings are escaped by [`EscapeStrategy::minimal`].
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::{escape::EscapeStrategy, minify::MinifyFormatter};
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(["<\"日本\">"], &mut write, MinifyFormatter).unwrap();
+/// assert_eq!(String::from_utf8(write).unwrap(), r#"["<\"日本\">"]"#);
+///
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(["<\"日本\">"], &mut write, MinifyFormatter::with_escape(EscapeStrategy::ascii_only())).unwrap();
+/// assert_eq!(String::from_utf8(write).unwrap(), r#"["<\"\u65E5\u672C\">"]"#);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
 pub struct MinifyFormatter;
+impl MinifyFormatter {
+    pub fn new() -> Self {
+        MinifyFormatter
+    }
+
+    /// Minify formatter that escapes strings by `escape`.
+    pub fn with_escape(escape: EscapeStrategy) -> EscapeMinifyFormatter {
+        EscapeMinifyFormatter { escape }
+    }
+}
 impl JsoncFormatter for MinifyFormatter {}
+
+/// Formatter that writes minified text, strings are escaped by the given [`EscapeStrategy`].
+/// It is created by [`MinifyFormatter::with_escape`].
+#[derive(Debug, Clone, Default)]
+pub struct EscapeMinifyFormatter {
+    escape: EscapeStrategy,
+}
+impl JsoncFormatter for EscapeMinifyFormatter {
+    fn escape_strategy(&self) -> EscapeStrategy {
+        self.escape
+    }
+}
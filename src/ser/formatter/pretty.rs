@@ -1,26 +1,84 @@
-use super::JsoncFormatter;
+use super::{escape::EscapeStrategy, JsoncFormatter};
 
+/// Pretty formatter of JSON with comments text.
+/// Default settings are two-space indent, trailing commas, `": "` separator and `\n` line ending.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::pretty::{Indent, LineEnding, PrettyFormatter, TrailingComma};
+///
+/// let formatter = PrettyFormatter::builder()
+///     .indent(Indent::Tab)
+///     .trailing_comma(TrailingComma::Never)
+///     .compact_empty(true)
+///     .line_ending(LineEnding::CrLf)
+///     .final_newline(true)
+///     .build();
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(vec![vec![1, 2], vec![]], &mut write, formatter).unwrap();
+/// assert_eq!(String::from_utf8(write).unwrap(), "[\r\n\t[\r\n\t\t1,\r\n\t\t2\r\n\t],\r\n\t[]\r\n]\r\n");
+/// ```
+#[derive(Debug, Clone, Default)]
 pub struct PrettyFormatter {
     indent: usize,
+    config: PrettyConfig,
 }
 impl PrettyFormatter {
     pub fn new() -> Self {
-        Self { indent: 0 }
+        Self::default()
+    }
+
+    pub fn with_escape(escape: EscapeStrategy) -> Self {
+        Self::builder().escape(escape).build()
+    }
+
+    pub fn with_config(config: PrettyConfig) -> Self {
+        Self { indent: 0, config }
+    }
+
+    pub fn builder() -> PrettyFormatterBuilder {
+        PrettyFormatterBuilder::default()
+    }
+
+    pub fn config(&self) -> &PrettyConfig {
+        &self.config
     }
 
     pub fn indent(&self) -> Vec<u8> {
-        b"  ".repeat(self.indent)
+        self.config.indent.as_bytes().repeat(self.indent)
+    }
+
+    pub fn newline(&self) -> &'static [u8] {
+        self.config.line_ending.as_bytes()
+    }
+
+    pub fn should_write_trailing_comma(&self, len: usize) -> bool {
+        match self.config.trailing_comma {
+            TrailingComma::Always | TrailingComma::Multiline => len > 0,
+            TrailingComma::Never => false,
+        }
     }
 
-    pub fn should_write_trailing_comma(&self, index: usize, len: Option<usize>) -> bool {
-        matches!(len.map(|l| index + 1 == l), Some(true))
+    fn write_container_end<W: std::io::Write>(&mut self, write: &mut W, len: usize) -> crate::Result<()> {
+        self.indent -= 1;
+        if len == 0 && self.config.compact_empty {
+            return Ok(());
+        }
+        if self.should_write_trailing_comma(len) {
+            write.write_all(b",")?;
+        }
+        write.write_all(self.newline())?;
+        Ok(write.write_all(&self.indent())?)
     }
 }
 impl JsoncFormatter for PrettyFormatter {
+    fn escape_strategy(&self) -> EscapeStrategy {
+        self.config.escape
+    }
+
     fn write_array_start<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
         let sup = self.write_array_start_super(write)?;
         self.indent += 1;
-        write.write_all(b"\n")?;
         Ok(sup)
     }
 
@@ -30,37 +88,21 @@ impl JsoncFormatter for PrettyFormatter {
         index: usize,
         len: Option<usize>,
     ) -> crate::Result<()> {
-        write.write_all(&self.indent())?;
         let sup = self.write_array_value_start_super(write, index, len)?;
+        write.write_all(self.newline())?;
+        write.write_all(&self.indent())?;
         Ok(sup)
     }
 
-    fn write_array_value_end<W: std::io::Write>(
-        &mut self,
-        write: &mut W,
-        index: usize,
-        len: Option<usize>,
-    ) -> crate::Result<()> {
-        let sup = self.write_array_value_end_super(write, index, len)?;
-        if self.should_write_trailing_comma(index, len) {
-            write.write_all(b",\n")?;
-        } else {
-            write.write_all(b"\n")?;
-        }
-        Ok(sup)
-    }
-
-    fn write_array_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
-        self.indent -= 1;
-        write.write_all(&self.indent())?;
-        let sup = self.write_array_end_super(write)?;
+    fn write_array_end<W: std::io::Write>(&mut self, write: &mut W, len: usize) -> crate::Result<()> {
+        self.write_container_end(write, len)?;
+        let sup = self.write_array_end_super(write, len)?;
         Ok(sup)
     }
 
     fn write_object_start<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
         let sup = self.write_object_start_super(write)?;
         self.indent += 1;
-        write.write_all(b"\n")?;
         Ok(sup)
     }
 
@@ -70,41 +112,248 @@ impl JsoncFormatter for PrettyFormatter {
         index: usize,
         len: Option<usize>,
     ) -> crate::Result<()> {
-        write.write_all(&self.indent())?;
         let sup = self.write_object_key_start_super(write, index, len)?;
+        write.write_all(self.newline())?;
+        write.write_all(&self.indent())?;
         Ok(sup)
     }
 
-    fn write_object_value_start<W: std::io::Write>(
+    fn write_object_key_end<W: std::io::Write>(
         &mut self,
         write: &mut W,
         index: usize,
         len: Option<usize>,
     ) -> crate::Result<()> {
-        write.write_all(b" ")?;
-        let sup = self.write_object_value_start_super(write, index, len)?;
+        if self.config.space_before_colon {
+            write.write_all(b" ")?;
+        }
+        let sup = self.write_object_key_end_super(write, index, len)?;
         Ok(sup)
     }
 
-    fn write_object_value_end<W: std::io::Write>(
+    fn write_object_value_start<W: std::io::Write>(
         &mut self,
         write: &mut W,
         index: usize,
         len: Option<usize>,
     ) -> crate::Result<()> {
-        let sup = self.write_object_value_end_super(write, index, len)?;
-        if self.should_write_trailing_comma(index, len) {
-            write.write_all(b",\n")?;
-        } else {
-            write.write_all(b"\n")?;
+        if self.config.space_after_colon {
+            write.write_all(b" ")?;
         }
+        let sup = self.write_object_value_start_super(write, index, len)?;
         Ok(sup)
     }
 
-    fn write_object_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
-        self.indent -= 1;
-        write.write_all(&self.indent())?;
-        let sup = self.write_object_end_super(write)?;
+    fn write_object_end<W: std::io::Write>(&mut self, write: &mut W, len: usize) -> crate::Result<()> {
+        self.write_container_end(write, len)?;
+        let sup = self.write_object_end_super(write, len)?;
+        Ok(sup)
+    }
+
+    fn write_document_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        let sup = self.write_document_end_super(write)?;
+        if self.config.final_newline {
+            write.write_all(self.newline())?;
+        }
         Ok(sup)
     }
 }
+
+/// Settings of [`PrettyFormatter`], it is usually built by [`PrettyFormatter::builder`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrettyConfig {
+    pub indent: Indent,
+    pub trailing_comma: TrailingComma,
+    pub compact_empty: bool,
+    pub space_before_colon: bool,
+    pub space_after_colon: bool,
+    pub line_ending: LineEnding,
+    pub final_newline: bool,
+    pub escape: EscapeStrategy,
+}
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            trailing_comma: TrailingComma::Always,
+            compact_empty: false,
+            space_before_colon: false,
+            space_after_colon: true,
+            line_ending: LineEnding::Lf,
+            final_newline: false,
+            escape: EscapeStrategy::minimal(),
+        }
+    }
+}
+
+/// Builder of [`PrettyFormatter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PrettyFormatterBuilder {
+    config: PrettyConfig,
+}
+impl PrettyFormatterBuilder {
+    /// Indent of each nest level, default is two spaces.
+    pub fn indent(self, indent: Indent) -> Self {
+        Self { config: PrettyConfig { indent, ..self.config } }
+    }
+
+    /// Trailing comma after the last element of non-empty array and object, default is [`TrailingComma::Always`].
+    pub fn trailing_comma(self, trailing_comma: TrailingComma) -> Self {
+        Self { config: PrettyConfig { trailing_comma, ..self.config } }
+    }
+
+    /// Write empty array and object as `[]` and `{}`, default is `false`.
+    pub fn compact_empty(self, compact_empty: bool) -> Self {
+        Self { config: PrettyConfig { compact_empty, ..self.config } }
+    }
+
+    /// Write space before `:` of object entry, default is `false`.
+    pub fn space_before_colon(self, space_before_colon: bool) -> Self {
+        Self { config: PrettyConfig { space_before_colon, ..self.config } }
+    }
+
+    /// Write space after `:` of object entry, default is `true`.
+    pub fn space_after_colon(self, space_after_colon: bool) -> Self {
+        Self { config: PrettyConfig { space_after_colon, ..self.config } }
+    }
+
+    /// Line ending, default is [`LineEnding::Lf`].
+    pub fn line_ending(self, line_ending: LineEnding) -> Self {
+        Self { config: PrettyConfig { line_ending, ..self.config } }
+    }
+
+    /// Write line ending at the end of the document, default is `false`.
+    pub fn final_newline(self, final_newline: bool) -> Self {
+        Self { config: PrettyConfig { final_newline, ..self.config } }
+    }
+
+    /// Escape strategy of string, default is [`EscapeStrategy::minimal`].
+    pub fn escape(self, escape: EscapeStrategy) -> Self {
+        Self { config: PrettyConfig { escape, ..self.config } }
+    }
+
+    pub fn config(&self) -> &PrettyConfig {
+        &self.config
+    }
+
+    pub fn build(self) -> PrettyFormatter {
+        PrettyFormatter::with_config(self.config)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+impl Indent {
+    pub fn as_bytes(&self) -> Vec<u8> {
+        match *self {
+            Indent::Spaces(width) => b" ".repeat(width),
+            Indent::Tab => b"\t".to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrailingComma {
+    /// Write trailing comma after the last element of every non-empty array and object.
+    Always,
+    /// Never write trailing comma, the output is also valid as JSON.
+    Never,
+    /// Write trailing comma only if the array or object is written across multiple lines.
+    Multiline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+impl LineEnding {
+    pub fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pretty(value: impl serde::Serialize, builder: PrettyFormatterBuilder) -> String {
+        let mut write = Vec::new();
+        crate::to_write(value, &mut write, builder.build()).unwrap();
+        String::from_utf8(write).unwrap()
+    }
+
+    #[derive(serde::Serialize)]
+    struct Data {
+        list: Vec<u32>,
+        empty: Vec<u32>,
+        map: std::collections::BTreeMap<&'static str, ()>,
+    }
+    fn data() -> Data {
+        Data { list: vec![1, 2], empty: vec![], map: [("k", ())].into() }
+    }
+
+    #[test]
+    fn test_default_config() {
+        let expected =
+            "{\n  \"list\": [\n    1,\n    2,\n  ],\n  \"empty\": [\n  ],\n  \"map\": {\n    \"k\": null,\n  },\n}";
+        assert_eq!(pretty(data(), PrettyFormatter::builder()), expected);
+        assert_eq!(crate::to_string_pretty(data()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_indent() {
+        let tab = PrettyFormatter::builder().indent(Indent::Tab);
+        assert_eq!(pretty(vec![vec![1]], tab), "[\n\t[\n\t\t1,\n\t],\n]");
+        let four = PrettyFormatter::builder().indent(Indent::Spaces(4));
+        assert_eq!(pretty(vec![vec![1]], four), "[\n    [\n        1,\n    ],\n]");
+        let zero = PrettyFormatter::builder().indent(Indent::Spaces(0));
+        assert_eq!(pretty(vec![vec![1]], zero), "[\n[\n1,\n],\n]");
+    }
+
+    #[test]
+    fn test_trailing_comma() {
+        let never = PrettyFormatter::builder().trailing_comma(TrailingComma::Never);
+        assert_eq!(
+            pretty(data(), never),
+            "{\n  \"list\": [\n    1,\n    2\n  ],\n  \"empty\": [\n  ],\n  \"map\": {\n    \"k\": null\n  }\n}"
+        );
+        let multiline = PrettyFormatter::builder().trailing_comma(TrailingComma::Multiline);
+        assert_eq!(pretty(vec![1, 2], multiline), "[\n  1,\n  2,\n]");
+    }
+
+    #[test]
+    fn test_compact_empty() {
+        let compact = PrettyFormatter::builder().compact_empty(true);
+        assert_eq!(pretty(Vec::<()>::new(), compact.clone()), "[]");
+        assert_eq!(pretty(std::collections::HashMap::<(), ()>::new(), compact.clone()), "{}");
+        assert_eq!(
+            pretty(data(), compact),
+            "{\n  \"list\": [\n    1,\n    2,\n  ],\n  \"empty\": [],\n  \"map\": {\n    \"k\": null,\n  },\n}"
+        );
+    }
+
+    #[test]
+    fn test_colon_spaces() {
+        let map = std::collections::BTreeMap::from([("k", 1)]);
+        let both = PrettyFormatter::builder().space_before_colon(true);
+        assert_eq!(pretty(&map, both), "{\n  \"k\" : 1,\n}");
+        let none = PrettyFormatter::builder().space_after_colon(false);
+        assert_eq!(pretty(&map, none), "{\n  \"k\":1,\n}");
+    }
+
+    #[test]
+    fn test_line_ending() {
+        let crlf = PrettyFormatter::builder().line_ending(LineEnding::CrLf);
+        assert_eq!(pretty(vec![1, 2], crlf.clone()), "[\r\n  1,\r\n  2,\r\n]");
+        assert_eq!(pretty(vec![1, 2], crlf.final_newline(true)), "[\r\n  1,\r\n  2,\r\n]\r\n");
+        assert_eq!(pretty(vec![1, 2], PrettyFormatter::builder().final_newline(true)), "[\n  1,\n  2,\n]\n");
+        assert_eq!(pretty("scalar", PrettyFormatter::builder().final_newline(true)), "\"scalar\"\n");
+    }
+}
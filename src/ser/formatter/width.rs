@@ -0,0 +1,401 @@
+use super::{
+    escape::EscapeStrategy,
+    minify::{EscapeMinifyFormatter, MinifyFormatter},
+    pretty::{PrettyConfig, TrailingComma},
+    JsoncFormatter,
+};
+
+/// Pretty formatter that keeps arrays and objects on one line if they fit in `max_width`, like Prettier.
+/// Arrays and objects are buffered until they are closed, and then laid out.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::ser::formatter::width::WidthFormatter;
+///
+/// #[derive(serde::Serialize)]
+/// struct Server {
+///     host: &'static str,
+///     ports: Vec<u16>,
+///     allowed: Vec<&'static str>,
+/// }
+/// let server = Server { host: "localhost", ports: vec![80, 443], allowed: vec!["192.168.0.1", "192.168.0.2"] };
+///
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(&server, &mut write, WidthFormatter::new(40)).unwrap();
+/// let expected = r#"{
+///   "host": "localhost",
+///   "ports": [80, 443],
+///   "allowed": [
+///     "192.168.0.1",
+///     "192.168.0.2",
+///   ],
+/// }"#;
+/// assert_eq!(String::from_utf8(write).unwrap(), expected);
+///
+/// let mut write = Vec::new();
+/// json_with_comments::to_write(&server, &mut write, WidthFormatter::new(100)).unwrap();
+/// let expected = r#"{ "host": "localhost", "ports": [80, 443], "allowed": ["192.168.0.1", "192.168.0.2"] }"#;
+/// assert_eq!(String::from_utf8(write).unwrap(), expected);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WidthFormatter {
+    max_width: usize,
+    config: PrettyConfig,
+    scalar: EscapeMinifyFormatter,
+    stack: Vec<Frame>,
+}
+impl WidthFormatter {
+    /// Width formatter with [`TrailingComma::Multiline`] and compact empty array and object.
+    pub fn new(max_width: usize) -> Self {
+        let config =
+            PrettyConfig { trailing_comma: TrailingComma::Multiline, compact_empty: true, ..PrettyConfig::default() };
+        Self::with_config(max_width, config)
+    }
+
+    /// Width formatter with indent, trailing comma, colon spaces, line ending, final newline and escape of `config`.
+    /// Empty array and object are always written as `[]` and `{}`.
+    pub fn with_config(max_width: usize, config: PrettyConfig) -> Self {
+        let scalar = MinifyFormatter::with_escape(config.escape);
+        Self { max_width, config, scalar, stack: Vec::new() }
+    }
+
+    pub fn max_width(&self) -> usize {
+        self.max_width
+    }
+
+    pub fn config(&self) -> &PrettyConfig {
+        &self.config
+    }
+
+    fn close<W: std::io::Write>(&mut self, write: &mut W, node: Node) -> crate::Result<()> {
+        match self.stack.last_mut() {
+            Some(parent) => {
+                let key = parent.key.take();
+                Ok(parent.entries.push((key, node)))
+            }
+            None => {
+                let mut out = Vec::new();
+                self.layout(&node, 0, 0, 0, &mut out);
+                Ok(write.write_all(&out)?)
+            }
+        }
+    }
+
+    fn end_value(&mut self) {
+        if let Some(frame) = self.stack.last_mut() {
+            if !frame.scratch.is_empty() {
+                let key = frame.key.take();
+                let scalar = Node::Scalar(std::mem::take(&mut frame.scratch));
+                frame.entries.push((key, scalar));
+            }
+        }
+    }
+
+    fn layout(&self, node: &Node, depth: usize, column: usize, suffix: usize, out: &mut Vec<u8>) {
+        let (open, close, entries) = match node {
+            Node::Scalar(scalar) => return out.extend_from_slice(scalar),
+            Node::Array(entries) => (b'[', b']', entries),
+            Node::Object(entries) => (b'{', b'}', entries),
+        };
+        if entries.is_empty() {
+            return out.extend_from_slice(&[open, close]);
+        }
+        let mut flat = Vec::new();
+        if self.flat(node, &mut flat) && column + width(&flat) + suffix <= self.max_width {
+            return out.extend_from_slice(&flat);
+        }
+
+        let (newline, indent) = (self.config.line_ending.as_bytes(), self.config.indent.as_bytes());
+        out.push(open);
+        for (i, (key, value)) in entries.iter().enumerate() {
+            out.extend_from_slice(newline);
+            out.extend_from_slice(&indent.repeat(depth + 1));
+            let mut column = width(&indent) * (depth + 1);
+            if let Some(key) = key {
+                let start = out.len();
+                self.write_key(key, out);
+                column += width(&out[start..]);
+            }
+            let last = i + 1 == entries.len();
+            let comma = !last || self.config.trailing_comma != TrailingComma::Never;
+            self.layout(value, depth + 1, column, comma as usize, out);
+            if comma {
+                out.push(b',');
+            }
+        }
+        out.extend_from_slice(newline);
+        out.extend_from_slice(&indent.repeat(depth));
+        out.push(close);
+    }
+
+    fn flat(&self, node: &Node, out: &mut Vec<u8>) -> bool {
+        let (open, close, entries): (&[u8], &[u8], _) = match node {
+            Node::Scalar(scalar) => {
+                out.extend_from_slice(scalar);
+                return out.len() <= self.max_width * 4; // a character is at most 4 bytes in UTF-8
+            }
+            Node::Object(entries) if entries.is_empty() => (b"{", b"}", entries),
+            Node::Array(entries) => (b"[", b"]", entries),
+            Node::Object(entries) => (b"{ ", b" }", entries),
+        };
+        out.extend_from_slice(open);
+        for (i, (key, value)) in entries.iter().enumerate() {
+            if i > 0 {
+                out.extend_from_slice(b", ");
+            }
+            if let Some(key) = key {
+                self.write_key(key, out);
+            }
+            if !self.flat(value, out) {
+                return false;
+            }
+        }
+        if !entries.is_empty() && self.config.trailing_comma == TrailingComma::Always {
+            out.push(b',');
+        }
+        out.extend_from_slice(close);
+        out.len() <= self.max_width * 4
+    }
+
+    fn write_key(&self, key: &[u8], out: &mut Vec<u8>) {
+        out.extend_from_slice(key);
+        if self.config.space_before_colon {
+            out.push(b' ');
+        }
+        out.push(b':');
+        if self.config.space_after_colon {
+            out.push(b' ');
+        }
+    }
+}
+impl JsoncFormatter for WidthFormatter {
+    fn write_bool<W: std::io::Write>(&mut self, write: &mut W, value: bool) -> crate::Result<()> {
+        match self.stack.last_mut() {
+            Some(frame) => self.scalar.write_bool(&mut frame.scratch, value),
+            None => self.scalar.write_bool(write, value),
+        }
+    }
+
+    fn write_null<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        match self.stack.last_mut() {
+            Some(frame) => self.scalar.write_null(&mut frame.scratch),
+            None => self.scalar.write_null(write),
+        }
+    }
+
+    fn write_number<W: std::io::Write, N: super::ToNumberRepresentation>(
+        &mut self,
+        write: &mut W,
+        value: N,
+    ) -> crate::Result<()>
+    where
+        crate::Error: From<N::Err>,
+    {
+        match self.stack.last_mut() {
+            Some(frame) => self.scalar.write_number(&mut frame.scratch, value),
+            None => self.scalar.write_number(write, value),
+        }
+    }
+
+    fn write_str<W: std::io::Write>(&mut self, write: &mut W, value: &str) -> crate::Result<()> {
+        match self.stack.last_mut() {
+            Some(frame) => self.scalar.write_str(&mut frame.scratch, value),
+            None => self.scalar.write_str(write, value),
+        }
+    }
+
+    fn escape_strategy(&self) -> EscapeStrategy {
+        self.config.escape
+    }
+
+    fn write_raw_fragment<W: std::io::Write>(&mut self, write: &mut W, fragment: &[u8]) -> crate::Result<()> {
+        match self.stack.last_mut() {
+            Some(frame) => self.scalar.write_raw_fragment(&mut frame.scratch, fragment),
+            None => self.scalar.write_raw_fragment(write, fragment),
+        }
+    }
+
+    fn write_separator<W: std::io::Write>(&mut self, _write: &mut W, _index: usize) -> crate::Result<()> {
+        Ok(()) // separators are written by layout
+    }
+
+    fn write_array_start<W: std::io::Write>(&mut self, _write: &mut W) -> crate::Result<()> {
+        Ok(self.stack.push(Frame::default()))
+    }
+
+    fn write_array_value_end<W: std::io::Write>(
+        &mut self,
+        _write: &mut W,
+        _index: usize,
+        _len: Option<usize>,
+    ) -> crate::Result<()> {
+        Ok(self.end_value())
+    }
+
+    fn write_array_end<W: std::io::Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
+        let frame = self.stack.pop().expect("array end should be paired with array start");
+        self.close(write, Node::Array(frame.entries))
+    }
+
+    fn write_object_start<W: std::io::Write>(&mut self, _write: &mut W) -> crate::Result<()> {
+        Ok(self.stack.push(Frame::default()))
+    }
+
+    fn write_object_key_end<W: std::io::Write>(
+        &mut self,
+        _write: &mut W,
+        _index: usize,
+        _len: Option<usize>,
+    ) -> crate::Result<()> {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.key = Some(std::mem::take(&mut frame.scratch));
+        }
+        Ok(())
+    }
+
+    fn write_object_value_end<W: std::io::Write>(
+        &mut self,
+        _write: &mut W,
+        _index: usize,
+        _len: Option<usize>,
+    ) -> crate::Result<()> {
+        Ok(self.end_value())
+    }
+
+    fn write_object_end<W: std::io::Write>(&mut self, write: &mut W, _len: usize) -> crate::Result<()> {
+        let frame = self.stack.pop().expect("object end should be paired with object start");
+        self.close(write, Node::Object(frame.entries))
+    }
+
+    fn write_document_end<W: std::io::Write>(&mut self, write: &mut W) -> crate::Result<()> {
+        let sup = self.write_document_end_super(write)?;
+        if self.config.final_newline {
+            write.write_all(self.config.line_ending.as_bytes())?;
+        }
+        Ok(sup)
+    }
+}
+
+/// Buffered array or object that is not closed yet.
+#[derive(Debug, Clone, Default)]
+struct Frame {
+    entries: Vec<(Option<Vec<u8>>, Node)>,
+    key: Option<Vec<u8>>,
+    scratch: Vec<u8>,
+}
+
+/// Buffered value, object entries have its formatted key.
+#[derive(Debug, Clone)]
+enum Node {
+    Scalar(Vec<u8>),
+    Array(Vec<(Option<Vec<u8>>, Node)>),
+    Object(Vec<(Option<Vec<u8>>, Node)>),
+}
+
+/// Display width of UTF-8 bytes, counted by characters.
+fn width(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b & 0xC0 != 0x80).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::ser::formatter::pretty::{Indent, PrettyFormatter};
+
+    use super::*;
+
+    fn width_format(value: impl serde::Serialize, formatter: WidthFormatter) -> String {
+        let mut write = Vec::new();
+        crate::to_write(value, &mut write, formatter).unwrap();
+        String::from_utf8(write).unwrap()
+    }
+
+    #[test]
+    fn test_fit_in_one_line() {
+        assert_eq!(width_format(vec![1, 2, 3], WidthFormatter::new(80)), "[1, 2, 3]");
+        assert_eq!(width_format(vec![1, 2, 3], WidthFormatter::new(9)), "[1, 2, 3]");
+        assert_eq!(width_format(vec![1, 2, 3], WidthFormatter::new(8)), "[\n  1,\n  2,\n  3,\n]");
+        assert_eq!(
+            width_format(BTreeMap::from([("a", 1), ("b", 2)]), WidthFormatter::new(80)),
+            r#"{ "a": 1, "b": 2 }"#
+        );
+        assert_eq!(width_format(Vec::<()>::new(), WidthFormatter::new(0)), "[]");
+        assert_eq!(width_format(BTreeMap::<(), ()>::new(), WidthFormatter::new(0)), "{}");
+        assert_eq!(width_format("scalar", WidthFormatter::new(0)), r#""scalar""#);
+    }
+
+    #[test]
+    fn test_break_only_outer() {
+        let target = BTreeMap::from([("ports", vec![80, 443]), ("range", vec![8000, 8001, 8002, 8003])]);
+        assert_eq!(
+            width_format(&target, WidthFormatter::new(36)),
+            ["{", r#"  "ports": [80, 443],"#, r#"  "range": [8000, 8001, 8002, 8003],"#, "}"].join("\n")
+        );
+        assert_eq!(
+            width_format(&target, WidthFormatter::new(35)),
+            [
+                "{",
+                r#"  "ports": [80, 443],"#,
+                r#"  "range": ["#,
+                "    8000,",
+                "    8001,",
+                "    8002,",
+                "    8003,",
+                "  ],",
+                "}"
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_width_counts_suffix_and_chars() {
+        // `  [1, 2],` is 9 characters including trailing comma
+        let target = vec![vec![1, 2], vec![3]];
+        assert_eq!(width_format(&target, WidthFormatter::new(9)), "[\n  [1, 2],\n  [3],\n]");
+        assert_eq!(width_format(&target, WidthFormatter::new(8)), "[\n  [\n    1,\n    2,\n  ],\n  [3],\n]");
+        assert_eq!(width_format(vec!["日本"], WidthFormatter::new(6)), r#"["日本"]"#);
+        assert_eq!(width_format(vec!["日本"], WidthFormatter::new(5)), "[\n  \"日本\",\n]");
+    }
+
+    #[test]
+    fn test_non_string_key() {
+        let target = BTreeMap::from([(1, vec![true]), (2, vec![false])]);
+        assert_eq!(width_format(&target, WidthFormatter::new(80)), r#"{ "1": [true], "2": [false] }"#);
+        assert_eq!(width_format(&target, WidthFormatter::new(20)), "{\n  \"1\": [true],\n  \"2\": [false],\n}");
+    }
+
+    #[test]
+    fn test_width_config() {
+        let config = PrettyFormatter::builder()
+            .indent(Indent::Tab)
+            .trailing_comma(TrailingComma::Never)
+            .space_before_colon(true)
+            .final_newline(true)
+            .config()
+            .clone();
+        let target = BTreeMap::from([("list", vec![1, 2])]);
+        assert_eq!(width_format(&target, WidthFormatter::with_config(80, config.clone())), "{ \"list\" : [1, 2] }\n");
+        assert_eq!(
+            width_format(&target, WidthFormatter::with_config(10, config.clone())),
+            "{\n\t\"list\" : [\n\t\t1,\n\t\t2\n\t]\n}\n"
+        );
+
+        let always = PrettyConfig { trailing_comma: TrailingComma::Always, ..PrettyConfig::default() };
+        assert_eq!(width_format(vec![1, 2], WidthFormatter::with_config(80, always)), "[1, 2,]");
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let target = crate::jsonc!({
+            "name": "json with comments",
+            "list": [1, 2.5, null, true, "string", {"nested": [[], {}]}],
+            "escaped": "\"quote\"\n",
+        });
+        for max_width in [0, 10, 40, 80, 200] {
+            let text = width_format(&target, WidthFormatter::new(max_width));
+            assert_eq!(crate::from_str::<crate::Value>(&text).unwrap(), target, "{text}");
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::{fmt, io};
+
+/// Adapter to use [`fmt::Write`] as [`io::Write`], such as [`fmt::Formatter`] or [`String`].
+/// Written bytes must be UTF-8, but a character may be split into multiple writes.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use json_with_comments::ser::write::FmtWriter;
+/// let mut text = String::new();
+/// let mut write = FmtWriter::new(&mut text);
+/// write.write_all(&"あ".as_bytes()[..1]).unwrap();
+/// write.write_all(&"あ".as_bytes()[1..]).unwrap();
+/// write.finish().unwrap();
+/// assert_eq!(text, "あ");
+/// ```
+#[derive(Debug)]
+pub struct FmtWriter<W> {
+    write: W,
+    pending: Vec<u8>,
+}
+impl<W: fmt::Write> FmtWriter<W> {
+    pub fn new(write: W) -> Self {
+        Self { write, pending: Vec::with_capacity(4) }
+    }
+
+    /// Ensure that no incomplete character is left, and get the inner writer.
+    pub fn finish(self) -> crate::Result<W> {
+        std::str::from_utf8(&self.pending)?;
+        Ok(self.write)
+    }
+
+    fn write_utf8(&mut self, buf: &[u8]) -> io::Result<()> {
+        match std::str::from_utf8(buf) {
+            Ok(s) => self.write_str(s),
+            Err(e) if e.error_len().is_none() => {
+                let (valid, incomplete) = buf.split_at(e.valid_up_to());
+                self.write_str(std::str::from_utf8(valid).map_err(invalid_data)?)?;
+                self.pending.extend_from_slice(incomplete);
+                Ok(())
+            }
+            Err(e) => Err(invalid_data(e)),
+        }
+    }
+
+    fn write_str(&mut self, s: &str) -> io::Result<()> {
+        self.write.write_str(s).map_err(io::Error::other)
+    }
+}
+impl<W: fmt::Write> io::Write for FmtWriter<W> {
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len();
+        while let (false, Some((&head, tail))) = (self.pending.is_empty(), buf.split_first()) {
+            self.pending.push(head);
+            buf = tail;
+            match std::str::from_utf8(&self.pending) {
+                Ok(_) => {
+                    let pending = std::mem::take(&mut self.pending);
+                    self.write_utf8(&pending)?;
+                }
+                Err(e) if e.error_len().is_none() => {}
+                Err(e) => return Err(invalid_data(e)),
+            }
+        }
+        self.write_utf8(buf)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn invalid_data(err: std::str::Utf8Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_split_characters() {
+        let source = "ascii, 日本語, 🦀";
+        for chunk in 1..=5 {
+            let mut text = String::new();
+            let mut write = FmtWriter::new(&mut text);
+            for bytes in source.as_bytes().chunks(chunk) {
+                write.write_all(bytes).unwrap();
+            }
+            write.finish().unwrap();
+            assert_eq!(text, source);
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut text = String::new();
+        let mut write = FmtWriter::new(&mut text);
+        assert!(write.write_all(b"valid").is_ok());
+        assert_eq!(write.write_all(b"\xff").unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert!(write.write_all(b"\xe6").is_ok());
+        assert_eq!(write.write_all(b"A").unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        let mut text = String::new();
+        let mut write = FmtWriter::new(&mut text);
+        write.write_all("日".as_bytes().split_last().unwrap().1).unwrap();
+        assert!(write.finish().is_err());
+    }
+
+    #[test]
+    fn test_invalid_formatter() {
+        use crate::ser::formatter::JsoncFormatter;
+        struct Latin1Formatter;
+        impl JsoncFormatter for Latin1Formatter {
+            fn write_str<W: io::Write>(&mut self, write: &mut W, value: &str) -> crate::Result<()> {
+                let latin1: Vec<_> = value.chars().map(|c| c as u8).collect();
+                Ok(write.write_all(&latin1)?)
+            }
+        }
+        assert_eq!(crate::to_string_with("ascii", Latin1Formatter).unwrap(), "ascii");
+        assert!(crate::to_string_with("é", Latin1Formatter).is_err());
+    }
+}
@@ -1,10 +1,25 @@
+pub mod borrowed;
+mod cmp;
 pub mod de;
+pub mod diff;
 pub mod from;
 pub mod index;
 pub mod into;
 pub mod macros;
+pub mod map;
+pub mod merge;
 pub mod number;
+pub mod patch;
+pub mod path;
+pub mod pointer;
+pub mod raw;
 pub mod ser;
+pub mod walk;
+
+pub use borrowed::BorrowedValue;
+pub use diff::diff;
+pub use map::{DefaultMap, JsoncMap, MapBackend};
+pub use raw::RawValue;
 
 #[cfg(not(feature = "preserve_order"))]
 pub type MapImpl<K, V> = std::collections::HashMap<K, V>;
@@ -58,21 +73,22 @@ pub type MapImpl<K, V> = indexmap::IndexMap<K, V>;
 /// assert_eq!(iter.next().unwrap().as_str().unwrap(), "trailing comma");
 /// assert_eq!(iter.next(), None);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Default)]
 // if JsoncValue<'a, I, F>, cannot implement FromStr
-pub enum JsoncValue<I, F> {
+pub enum JsoncValue<I, F, M: MapBackend = DefaultMap> {
     /// Represents any valid JSON with comments object.
-    /// Default implementation is `HashMap`. If `preserve_order` feature is enabled, that will be `IndexMap`.
+    /// Map type is decided by [`MapBackend`], default is `HashMap`.
+    /// If `preserve_order` feature is enabled, that will be `IndexMap`.
     /// ```
     /// let v = json_with_comments::jsonc!({"key": "value"});
     /// ```
-    Object(MapImpl<String, JsoncValue<I, F>>),
+    Object(M::Map<JsoncValue<I, F, M>>),
 
     /// Represents any valid JSON with comments array.
     /// ```
     /// let v = json_with_comments::jsonc!([1, 2, 3]);
     /// ```
-    Array(Vec<JsoncValue<I, F>>),
+    Array(Vec<JsoncValue<I, F, M>>),
 
     /// Represents any valid JSON with comments boolean.
     /// ```
@@ -84,6 +100,7 @@ pub enum JsoncValue<I, F> {
     /// ```
     /// let v = json_with_comments::jsonc!(null);
     /// ```
+    #[default]
     Null,
 
     /// Represents any valid JSON with comments string.
@@ -99,20 +116,62 @@ pub enum JsoncValue<I, F> {
     Number(number::Number<I, F>),
 }
 
-impl<I, F> Default for JsoncValue<I, F> {
-    fn default() -> Self {
-        Self::Null
+impl<I: std::fmt::Debug, F: std::fmt::Debug, M: MapBackend> std::fmt::Debug for JsoncValue<I, F, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        struct Entries<'a, I, F, M: MapBackend>(&'a M::Map<JsoncValue<I, F, M>>);
+        impl<I: std::fmt::Debug, F: std::fmt::Debug, M: MapBackend> std::fmt::Debug for Entries<'_, I, F, M> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_map().entries(self.0.iter()).finish()
+            }
+        }
+        match self {
+            JsoncValue::Object(map) => f.debug_tuple("Object").field(&Entries::<I, F, M>(map)).finish(),
+            JsoncValue::Array(vec) => f.debug_tuple("Array").field(vec).finish(),
+            JsoncValue::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            JsoncValue::Null => f.write_str("Null"),
+            JsoncValue::String(s) => f.debug_tuple("String").field(s).finish(),
+            JsoncValue::Number(n) => f.debug_tuple("Number").field(n).finish(),
+        }
+    }
+}
+impl<I: Clone, F: Clone, M: MapBackend> Clone for JsoncValue<I, F, M> {
+    fn clone(&self) -> Self {
+        match self {
+            JsoncValue::Object(map) => JsoncValue::Object(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect()),
+            JsoncValue::Array(vec) => JsoncValue::Array(vec.clone()),
+            JsoncValue::Bool(b) => JsoncValue::Bool(*b),
+            JsoncValue::Null => JsoncValue::Null,
+            JsoncValue::String(s) => JsoncValue::String(s.clone()),
+            JsoncValue::Number(n) => JsoncValue::Number(n.clone()),
+        }
+    }
+}
+impl<I: PartialEq, F: PartialEq, M: MapBackend> PartialEq for JsoncValue<I, F, M> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (JsoncValue::Object(m1), JsoncValue::Object(m2)) => {
+                m1.len() == m2.len() && m1.iter().all(|(k, v1)| m2.get(k).is_some_and(|v2| v1 == v2))
+            }
+            (JsoncValue::Array(v1), JsoncValue::Array(v2)) => v1 == v2,
+            (JsoncValue::Bool(b1), JsoncValue::Bool(b2)) => b1 == b2,
+            (JsoncValue::Null, JsoncValue::Null) => true,
+            (JsoncValue::String(s1), JsoncValue::String(s2)) => s1 == s2,
+            (JsoncValue::Number(n1), JsoncValue::Number(n2)) => n1 == n2,
+            _ => false,
+        }
     }
 }
-impl<I: num::FromPrimitive, F: num::FromPrimitive> std::str::FromStr for JsoncValue<I, F> {
+
+impl<I: num::FromPrimitive, F: num::FromPrimitive, M: MapBackend> std::str::FromStr for JsoncValue<I, F, M> {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         crate::from_str(s)
     }
 }
-impl<I, F> JsoncValue<I, F> {
-    /// TODO doc
-    pub fn query(&self, query: &str) -> Option<&JsoncValue<I, F>> {
+impl<I, F, M: MapBackend> JsoncValue<I, F, M> {
+    /// Returns a reference to the value that is pointed by dot separated keys and indices.
+    #[deprecated(note = "use `select` for JSONPath or `pointer` for JSON Pointer instead")]
+    pub fn query(&self, query: &str) -> Option<&JsoncValue<I, F, M>> {
         // TODO better implement, tests
         query.split('.').try_fold(self, |value, key| match value {
             JsoncValue::Object(map) => map.get(key),
@@ -242,3 +301,33 @@ impl<I, F> JsoncValue<I, F> {
         .to_string()
     }
 }
+impl<I: serde::Serialize, F: serde::Serialize, M: MapBackend> JsoncValue<I, F, M> {
+    /// Hash of the canonical form of this value (see [`crate::to_string_canonical`]).
+    /// It does not depend on comments, whitespace, trailing commas, key order or number notation of the source text.
+    /// The hash is 64-bit FNV-1a, it is stable across versions and platforms, but it is not cryptographic.
+    /// If you need cryptographic digest or signature, use the output of [`crate::to_string_canonical`] instead.
+    ///
+    /// # Examples
+    /// ```
+    /// let a: json_with_comments::Value = json_with_comments::from_str(r#"{"name": "app", "ports": [80, 443]}"#).unwrap();
+    /// let b: json_with_comments::Value = json_with_comments::from_str(r#"
+    /// {
+    ///     "ports": [
+    ///         80.0,
+    ///         4.43e2, // https
+    ///     ],
+    ///     /* application name */
+    ///     "name": "app",
+    /// }"#).unwrap();
+    /// assert_eq!(a.canonical_hash().unwrap(), b.canonical_hash().unwrap());
+    ///
+    /// let c = json_with_comments::jsonc!({"name": "app", "ports": [80, 8080]});
+    /// assert_ne!(a.canonical_hash().unwrap(), c.canonical_hash().unwrap());
+    /// ```
+    pub fn canonical_hash(&self) -> crate::Result<u64> {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+        let canonical = crate::to_string_canonical(self)?;
+        Ok(canonical.bytes().fold(OFFSET_BASIS, |hash, b| (hash ^ b as u64).wrapping_mul(PRIME)))
+    }
+}
@@ -0,0 +1,441 @@
+use std::{borrow::Cow, fmt};
+
+use serde::{
+    de::{Error as _, Visitor},
+    Deserialize, Serialize,
+};
+
+use crate::{
+    error::{Ensure, SemanticError},
+    ser::formatter::{minify::MinifyFormatter, pretty::PrettyFormatter},
+};
+
+use super::{
+    map::{BorrowedMap, DefaultMap},
+    number::Number,
+    JsoncValue, MapBackend,
+};
+
+/// Represents any valid JSON with comments value that borrows strings and object keys from the source text.
+/// Strings without escape sequence are [`Cow::Borrowed`], so parsing does not allocate them.
+/// Use [`BorrowedValue::into_owned`] or [`JsoncValue::from`] to detach it from the source text.
+/// Map type of objects is decided by [`MapBackend`] same as [`JsoncValue`].
+///
+/// # Examples
+/// ```
+/// use std::borrow::Cow;
+/// use json_with_comments::value::BorrowedValue;
+/// let catalog = r#"{"name": "candy", "description": "sweet\nsnack", "tags": ["sugar"]}"#;
+/// let value: BorrowedValue = json_with_comments::from_str(catalog).unwrap();
+/// assert!(matches!(value["name"], BorrowedValue::String(Cow::Borrowed("candy"))));
+/// assert!(matches!(value["description"], BorrowedValue::String(Cow::Owned(_))));
+/// assert_eq!(value["tags"][0].as_str(), Some("sugar"));
+/// assert_eq!(value["price"], BorrowedValue::Null);
+/// ```
+#[derive(Default)]
+pub enum BorrowedValue<'a, I = i64, F = f64, M: MapBackend = DefaultMap> {
+    /// Represents any valid JSON with comments object, keys are borrowed if possible.
+    Object(M::BorrowedMap<'a, BorrowedValue<'a, I, F, M>>),
+
+    /// Represents any valid JSON with comments array.
+    Array(Vec<BorrowedValue<'a, I, F, M>>),
+
+    /// Represents any valid JSON with comments boolean.
+    Bool(bool),
+
+    /// Represents any valid JSON with comments null.
+    #[default]
+    Null,
+
+    /// Represents any valid JSON with comments string, borrowed if it does not contain escape sequence.
+    String(Cow<'a, str>),
+
+    /// Represents any valid JSON with comments number, whether integer or float.
+    Number(Number<I, F>),
+}
+
+impl<I: fmt::Debug, F: fmt::Debug, M: MapBackend> fmt::Debug for BorrowedValue<'_, I, F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Entries<'a, 'b, I, F, M: MapBackend>(&'b M::BorrowedMap<'a, BorrowedValue<'a, I, F, M>>);
+        impl<I: fmt::Debug, F: fmt::Debug, M: MapBackend> fmt::Debug for Entries<'_, '_, I, F, M> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_map().entries(self.0.iter()).finish()
+            }
+        }
+        match self {
+            BorrowedValue::Object(map) => f.debug_tuple("Object").field(&Entries::<I, F, M>(map)).finish(),
+            BorrowedValue::Array(vec) => f.debug_tuple("Array").field(vec).finish(),
+            BorrowedValue::Bool(b) => f.debug_tuple("Bool").field(b).finish(),
+            BorrowedValue::Null => f.write_str("Null"),
+            BorrowedValue::String(s) => f.debug_tuple("String").field(s).finish(),
+            BorrowedValue::Number(n) => f.debug_tuple("Number").field(n).finish(),
+        }
+    }
+}
+impl<I: Clone, F: Clone, M: MapBackend> Clone for BorrowedValue<'_, I, F, M> {
+    fn clone(&self) -> Self {
+        match self {
+            BorrowedValue::Object(map) => {
+                BorrowedValue::Object(map.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            }
+            BorrowedValue::Array(vec) => BorrowedValue::Array(vec.clone()),
+            BorrowedValue::Bool(b) => BorrowedValue::Bool(*b),
+            BorrowedValue::Null => BorrowedValue::Null,
+            BorrowedValue::String(s) => BorrowedValue::String(s.clone()),
+            BorrowedValue::Number(n) => BorrowedValue::Number(n.clone()),
+        }
+    }
+}
+impl<I: PartialEq, F: PartialEq, M: MapBackend> PartialEq for BorrowedValue<'_, I, F, M> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BorrowedValue::Object(m1), BorrowedValue::Object(m2)) => {
+                m1.len() == m2.len() && m1.iter().all(|(k, v1)| m2.get(k).is_some_and(|v2| v1 == v2))
+            }
+            (BorrowedValue::Array(v1), BorrowedValue::Array(v2)) => v1 == v2,
+            (BorrowedValue::Bool(b1), BorrowedValue::Bool(b2)) => b1 == b2,
+            (BorrowedValue::Null, BorrowedValue::Null) => true,
+            (BorrowedValue::String(s1), BorrowedValue::String(s2)) => s1 == s2,
+            (BorrowedValue::Number(n1), BorrowedValue::Number(n2)) => n1 == n2,
+            _ => false,
+        }
+    }
+}
+
+impl<'a, I, F, M: MapBackend> BorrowedValue<'a, I, F, M> {
+    /// Returns a reference to the value associated with the given key, or `None` if this is not an object.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        self.as_map()?.get(key)
+    }
+
+    /// Returns a reference to the element at the given index, or `None` if this is not an array.
+    pub fn get_index(&self, index: usize) -> Option<&Self> {
+        self.as_vec()?.get(index)
+    }
+
+    pub fn as_map(&self) -> Option<&M::BorrowedMap<'a, Self>> {
+        match self {
+            BorrowedValue::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub fn as_vec(&self) -> Option<&Vec<Self>> {
+        match self {
+            BorrowedValue::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<&bool> {
+        match self {
+            BorrowedValue::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, BorrowedValue::Null)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            BorrowedValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<&Number<I, F>> {
+        match self {
+            BorrowedValue::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Convert into [`BorrowedValue`] that owns all strings and keys, so it no longer borrows the source text.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::value::BorrowedValue;
+    /// let owned: BorrowedValue<'static> = {
+    ///     let source = String::from(r#"{"key": ["value"]}"#);
+    ///     json_with_comments::from_str::<BorrowedValue>(&source).unwrap().into_owned()
+    /// };
+    /// assert_eq!(owned["key"][0].as_str(), Some("value"));
+    /// ```
+    pub fn into_owned(self) -> BorrowedValue<'static, I, F, M> {
+        match self {
+            BorrowedValue::Object(map) => BorrowedValue::Object(
+                map.into_iter().map(|(k, v)| (Cow::Owned(k.into_owned()), v.into_owned())).collect(),
+            ),
+            BorrowedValue::Array(vec) => BorrowedValue::Array(vec.into_iter().map(BorrowedValue::into_owned).collect()),
+            BorrowedValue::Bool(b) => BorrowedValue::Bool(b),
+            BorrowedValue::Null => BorrowedValue::Null,
+            BorrowedValue::String(s) => BorrowedValue::String(Cow::Owned(s.into_owned())),
+            BorrowedValue::Number(n) => BorrowedValue::Number(n),
+        }
+    }
+}
+
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, value::BorrowedValue, Value};
+/// let value: BorrowedValue = json_with_comments::from_str(r#"{"key": [1, 2.5, null]}"#).unwrap();
+/// assert_eq!(Value::from(value), jsonc!({"key": [1, 2.5, null]}));
+/// ```
+impl<I, F, M: MapBackend> From<BorrowedValue<'_, I, F, M>> for JsoncValue<I, F, M> {
+    fn from(value: BorrowedValue<'_, I, F, M>) -> Self {
+        match value {
+            BorrowedValue::Object(map) => {
+                JsoncValue::Object(map.into_iter().map(|(k, v)| (k.into_owned(), v.into())).collect())
+            }
+            BorrowedValue::Array(vec) => JsoncValue::Array(vec.into_iter().map(Into::into).collect()),
+            BorrowedValue::Bool(b) => JsoncValue::Bool(b),
+            BorrowedValue::Null => JsoncValue::Null,
+            BorrowedValue::String(s) => JsoncValue::String(s.into_owned()),
+            BorrowedValue::Number(n) => JsoncValue::Number(n),
+        }
+    }
+}
+
+/// Index access never panics, missing keys and unmatched types return `Null`.
+impl<I, F, M: MapBackend> std::ops::Index<&str> for BorrowedValue<'_, I, F, M> {
+    type Output = Self;
+    fn index(&self, index: &str) -> &Self::Output {
+        self.get(index).unwrap_or(&const { BorrowedValue::Null })
+    }
+}
+/// Index access never panics, out of range indices and unmatched types return `Null`.
+impl<I, F, M: MapBackend> std::ops::Index<usize> for BorrowedValue<'_, I, F, M> {
+    type Output = Self;
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get_index(index).unwrap_or(&const { BorrowedValue::Null })
+    }
+}
+
+impl<I: Serialize, F: Serialize, M: MapBackend> Serialize for BorrowedValue<'_, I, F, M> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            BorrowedValue::Object(map) => serializer.collect_map(map.iter()),
+            BorrowedValue::Array(vec) => vec.serialize(serializer),
+            BorrowedValue::Bool(b) => b.serialize(serializer),
+            BorrowedValue::Null => ().serialize(serializer),
+            BorrowedValue::String(s) => s.serialize(serializer),
+            BorrowedValue::Number(n) => match n {
+                Number::Integer(i) => i.serialize(serializer),
+                Number::Float(f) => f.serialize(serializer),
+            },
+        }
+    }
+}
+
+/// `{}` writes minified JSON with comments text, and `{:#}` writes pretty formatted one.
+impl<I: Serialize, F: Serialize, M: MapBackend> fmt::Display for BorrowedValue<'_, I, F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = if f.alternate() {
+            crate::to_fmt(self, &mut *f, PrettyFormatter::new())
+        } else {
+            crate::to_fmt(self, &mut *f, MinifyFormatter::new())
+        };
+        result.map_err(|_| fmt::Error)
+    }
+}
+
+impl<'de: 'a, 'a, I: num::FromPrimitive, F: num::FromPrimitive, M: MapBackend> Deserialize<'de>
+    for BorrowedValue<'a, I, F, M>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(BorrowedValueVisitor { phantom: std::marker::PhantomData })
+    }
+}
+
+struct BorrowedValueVisitor<'a, I, F, M> {
+    phantom: std::marker::PhantomData<(&'a (), I, F, M)>,
+}
+impl<'de: 'a, 'a, I: num::FromPrimitive, F: num::FromPrimitive, M: MapBackend> Visitor<'de>
+    for BorrowedValueVisitor<'a, I, F, M>
+{
+    type Value = BorrowedValue<'a, I, F, M>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSONC value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Number(Number::Integer(I::from_i64(v).ok_or(E::custom(Ensure::CanConvertAlways))?)))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Number(Number::Integer(I::from_i128(v).ok_or(E::custom(Ensure::CanConvertAlways))?)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Number(Number::Integer(I::from_u64(v).ok_or(E::custom(Ensure::CanConvertAlways))?)))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Number(Number::Integer(I::from_u128(v).ok_or(E::custom(Ensure::CanConvertAlways))?)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Number(Number::Float(F::from_f64(v).ok_or(E::custom(Ensure::CanConvertAlways))?)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::String(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::String(Cow::Borrowed(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::String(Cow::Owned(v)))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(BorrowedValue::Null)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut v = Vec::new();
+        while let Some(elem) = seq.next_element()? {
+            v.push(elem);
+        }
+        Ok(BorrowedValue::Array(v))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut v = M::BorrowedMap::default();
+        while let Some((key, value)) = map.next_entry::<BorrowedValue<I, F, M>, BorrowedValue<I, F, M>>()? {
+            match key {
+                BorrowedValue::String(s) => v.insert(s, value),
+                _ => Err(A::Error::custom(SemanticError::AnyMapKey))?,
+            };
+        }
+        Ok(BorrowedValue::Object(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{
+        jsonc, jsonc_generics,
+        value::{map::BTreeMapBackend, JsoncValue},
+        Value,
+    };
+
+    use super::BorrowedValue;
+
+    #[test]
+    fn test_borrow_from_source() {
+        let source = r#"
+        {
+            // comment
+            "plain": "text",
+            "escaped\tkey": "line\nfeed",
+            "nested": [{"inner": "value"}, 1, -2.5, true, null],
+        }"#;
+        let value: BorrowedValue = crate::from_str(source).unwrap();
+        let map = value.as_map().unwrap();
+        for (key, borrowed) in [("plain", true), ("escaped\tkey", false), ("nested", true)] {
+            let (k, _) = map.get_key_value(key).unwrap();
+            assert_eq!(matches!(k, Cow::Borrowed(_)), borrowed);
+        }
+        assert!(matches!(value["plain"], BorrowedValue::String(Cow::Borrowed("text"))));
+        assert!(matches!(&value["escaped\tkey"], BorrowedValue::String(Cow::Owned(s)) if s == "line\nfeed"));
+        assert!(matches!(value["nested"][0]["inner"], BorrowedValue::String(Cow::Borrowed("value"))));
+        assert_eq!(value["nested"][2].as_number(), Some(&crate::value::number::Number::Float(-2.5)));
+        assert_eq!(value["nested"][100], BorrowedValue::Null);
+        assert_eq!(value["plain"]["unmatched"], BorrowedValue::Null);
+    }
+
+    #[test]
+    fn test_borrowed_map_backend() {
+        let source = r#"{"c": {"z": 1, "y": "s"}, "a": null, "b": [true]}"#;
+        let value: BorrowedValue<i64, f64, BTreeMapBackend> = crate::from_str(source).unwrap();
+        assert_eq!(value.as_map().unwrap().keys().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(value.to_string(), r#"{"a":null,"b":[true],"c":{"y":"s","z":1}}"#);
+        let owned: JsoncValue<i64, f64, BTreeMapBackend> = value.into();
+        assert_eq!(owned, jsonc_generics!({"a": null, "b": [true], "c": {"y": "s", "z": 1}}));
+    }
+
+    #[test]
+    fn test_borrowed_raw_strings() {
+        let value: BorrowedValue = crate::from_str_raw(r#"["raw\nstring"]"#).unwrap();
+        assert!(matches!(value[0], BorrowedValue::String(Cow::Borrowed(r"raw\nstring"))));
+    }
+
+    #[test]
+    fn test_borrowed_roundtrip() {
+        let source = r#"{"a":[1,2.5,"s\"q"],"b":{"c":null,"d":false}}"#;
+        let value: BorrowedValue = crate::from_str(source).unwrap();
+        let expected = jsonc!({"a": [1, 2.5, "s\"q"], "b": {"c": null, "d": false}});
+        assert_eq!(crate::from_str::<Value>(&value.to_string()).unwrap(), expected);
+        assert_eq!(crate::from_str::<Value>(&format!("{value:#}")).unwrap(), expected);
+        assert_eq!(Value::from(value.clone().into_owned()), expected);
+        assert_eq!(value.into_owned(), crate::from_str::<BorrowedValue>(source).unwrap());
+    }
+}
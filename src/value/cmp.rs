@@ -0,0 +1,195 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use super::{JsoncMap, JsoncValue, MapBackend};
+
+impl<I: Eq, F: num::Float, M: MapBackend> Eq for JsoncValue<I, F, M> {}
+
+/// Values of different types are ordered as `null < bool < number < string < array < object`.
+/// Numbers are ordered as [`super::number::Number`], arrays are ordered lexicographically,
+/// and objects are ordered lexicographically as key-sorted entries, regardless of the insertion order.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::jsonc;
+/// let mut values = vec![jsonc!({"b": 1, "a": 2}), jsonc!([1, 2]), jsonc!("str"), jsonc!(1.5), jsonc!(null)];
+/// values.sort();
+/// assert_eq!(values, vec![jsonc!(null), jsonc!(1.5), jsonc!("str"), jsonc!([1, 2]), jsonc!({"a": 2, "b": 1})]);
+///
+/// let mut configs = vec![jsonc!({"a": 1, "b": 2}), jsonc!({"b": 2, "a": 1}), jsonc!({"a": 2})];
+/// configs.sort();
+/// configs.dedup();
+/// assert_eq!(configs, vec![jsonc!({"a": 1, "b": 2}), jsonc!({"a": 2})]);
+/// ```
+impl<I: PartialOrd + num::ToPrimitive, F: PartialOrd + num::ToPrimitive, M: MapBackend> PartialOrd
+    for JsoncValue<I, F, M>
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (JsoncValue::Object(m1), JsoncValue::Object(m2)) => sorted_entries(m1).partial_cmp(&sorted_entries(m2)),
+            (JsoncValue::Array(v1), JsoncValue::Array(v2)) => v1.partial_cmp(v2),
+            (JsoncValue::Bool(b1), JsoncValue::Bool(b2)) => b1.partial_cmp(b2),
+            (JsoncValue::Null, JsoncValue::Null) => Some(Ordering::Equal),
+            (JsoncValue::String(s1), JsoncValue::String(s2)) => s1.partial_cmp(s2),
+            (JsoncValue::Number(n1), JsoncValue::Number(n2)) => n1.partial_cmp(n2),
+            _ => Some(rank(self).cmp(&rank(other))),
+        }
+    }
+}
+impl<I: Ord + num::ToPrimitive, F: num::Float, M: MapBackend> Ord for JsoncValue<I, F, M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (JsoncValue::Object(m1), JsoncValue::Object(m2)) => sorted_entries(m1).cmp(&sorted_entries(m2)),
+            (JsoncValue::Array(v1), JsoncValue::Array(v2)) => v1.cmp(v2),
+            (JsoncValue::Bool(b1), JsoncValue::Bool(b2)) => b1.cmp(b2),
+            (JsoncValue::Null, JsoncValue::Null) => Ordering::Equal,
+            (JsoncValue::String(s1), JsoncValue::String(s2)) => s1.cmp(s2),
+            (JsoncValue::Number(n1), JsoncValue::Number(n2)) => n1.cmp(n2),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+}
+
+/// Hash of objects does not depend on the order of entries, so equal objects have the same hash
+/// even if they are `HashMap` with different iteration order.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use json_with_comments::jsonc;
+/// let configs = [jsonc!({"a": 1, "b": [2.5]}), jsonc!({"b": [2.5], "a": 1}), jsonc!({"a": 1})];
+/// let unique: HashSet<_> = configs.iter().collect();
+/// assert_eq!(unique.len(), 2);
+/// ```
+impl<I: Hash, F: num::Float, M: MapBackend> Hash for JsoncValue<I, F, M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        rank(self).hash(state);
+        match self {
+            JsoncValue::Object(map) => sorted_entries(map).hash(state),
+            JsoncValue::Array(vec) => vec.hash(state),
+            JsoncValue::Bool(b) => b.hash(state),
+            JsoncValue::Null => {}
+            JsoncValue::String(s) => s.hash(state),
+            JsoncValue::Number(n) => n.hash(state),
+        }
+    }
+}
+
+fn rank<I, F, M: MapBackend>(value: &JsoncValue<I, F, M>) -> u8 {
+    match value {
+        JsoncValue::Null => 0,
+        JsoncValue::Bool(_) => 1,
+        JsoncValue::Number(_) => 2,
+        JsoncValue::String(_) => 3,
+        JsoncValue::Array(_) => 4,
+        JsoncValue::Object(_) => 5,
+    }
+}
+
+fn sorted_entries<V, M: JsoncMap<V>>(map: &M) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by_key(|&(k, _)| k);
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{BTreeSet, HashSet},
+        hash::{BuildHasher, RandomState},
+    };
+
+    use crate::{jsonc, Value};
+
+    #[test]
+    fn test_eq_float_semantics() {
+        assert_eq!(Value::from(f64::NAN), Value::from(f64::NAN));
+        assert_eq!(Value::from(-0.0), Value::from(0.0));
+        assert_ne!(Value::from(1), Value::from(1.0));
+        assert!(Value::from(1.0) < Value::from(f64::NAN));
+        assert!(Value::from(f64::NEG_INFINITY) < Value::from(-1.0));
+    }
+
+    #[test]
+    fn test_ord_total() {
+        let mut values = vec![
+            jsonc!({"a": [1, 2]}),
+            jsonc!({"a": [1]}),
+            jsonc!([[], {}]),
+            jsonc!([null]),
+            jsonc!("b"),
+            jsonc!("a"),
+            jsonc!(f64::NAN),
+            jsonc!(-1.0),
+            jsonc!(3),
+            jsonc!(false),
+            jsonc!(null),
+        ];
+        values.sort();
+        assert_eq!(
+            values,
+            vec![
+                jsonc!(null),
+                jsonc!(false),
+                jsonc!(-1.0),
+                jsonc!(3),
+                jsonc!(f64::NAN),
+                jsonc!("a"),
+                jsonc!("b"),
+                jsonc!([null]),
+                jsonc!([[], {}]),
+                jsonc!({"a": [1]}),
+                jsonc!({"a": [1, 2]}),
+            ]
+        );
+        for w in values.windows(2) {
+            assert_eq!(w[0].partial_cmp(&w[1]), Some(w[0].cmp(&w[1])));
+        }
+    }
+
+    #[test]
+    fn test_ord_mixed_numbers() {
+        let mut values =
+            vec![jsonc!(10), jsonc!(2.5), jsonc!(1), jsonc!(1.0), jsonc!(-0.5), jsonc!(f64::NAN), jsonc!(0)];
+        values.sort();
+        let expected = vec![jsonc!(-0.5), jsonc!(0), jsonc!(1), jsonc!(1.0), jsonc!(2.5), jsonc!(10), jsonc!(f64::NAN)];
+        assert_eq!(values, expected);
+        assert!(Value::from(1) < Value::from(1.0) && Value::from(1.0) < Value::from(2));
+        assert!(Value::from(f64::INFINITY) > Value::from(i64::MAX));
+        for w in values.windows(2) {
+            assert_eq!(w[0].partial_cmp(&w[1]), Some(w[0].cmp(&w[1])));
+            assert_eq!(w[0].cmp(&w[1]), std::cmp::Ordering::Less);
+        }
+    }
+
+    #[test]
+    fn test_object_order_independent() {
+        let keys = ["k0", "k1", "k2", "k3", "k4", "k5", "k6", "k7"];
+        let (mut forward, mut backward) = (jsonc!({}), jsonc!({}));
+        for (i, k) in keys.iter().enumerate() {
+            forward[*k] = (i as i64).into();
+        }
+        for (i, k) in keys.iter().enumerate().rev() {
+            backward[*k] = (i as i64).into();
+        }
+        assert_eq!(forward, backward);
+        assert_eq!(forward.cmp(&backward), std::cmp::Ordering::Equal);
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&forward), state.hash_one(&backward));
+    }
+
+    #[test]
+    fn test_dedupe_collections() {
+        let entries = [
+            jsonc!({"name": "a", "port": 80, "ratio": 0.0}),
+            jsonc!({"port": 80, "ratio": -0.0, "name": "a"}),
+            jsonc!({"name": "a", "port": 443, "ratio": 0.0}),
+            jsonc!({"name": "b", "port": 80, "ratio": f64::NAN}),
+            jsonc!({"name": "b", "port": 80, "ratio": f64::NAN}),
+        ];
+        assert_eq!(entries.iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(entries.iter().collect::<BTreeSet<_>>().len(), 3);
+    }
+}
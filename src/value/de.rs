@@ -7,11 +7,9 @@ pub mod visitor;
 
 use serde::de;
 
-use crate::value::JsoncValue;
+use crate::value::{JsoncValue, MapBackend};
 
-use super::MapImpl;
-
-impl<'de, I: num::FromPrimitive, F: num::FromPrimitive> de::Deserialize<'de> for JsoncValue<I, F> {
+impl<'de, I: num::FromPrimitive, F: num::FromPrimitive, M: MapBackend> de::Deserialize<'de> for JsoncValue<I, F, M> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -20,7 +18,85 @@ impl<'de, I: num::FromPrimitive, F: num::FromPrimitive> de::Deserialize<'de> for
     }
 }
 
-impl<'de, I, F> JsoncValue<I, F>
+macro_rules! forward_to_owned_deserializer {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {$(
+        fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error>
+        where
+            V: de::Visitor<'de>,
+        {
+            deserializer::OwnedValueDeserializer::new(self).$method($($arg,)* visitor)
+        }
+    )*};
+}
+
+/// Consume the value as [`serde::Deserializer`], strings, arrays and objects are moved instead of cloned.
+///
+/// # Examples
+/// ```
+/// use serde::Deserialize;
+/// #[derive(Deserialize)]
+/// struct Config {
+///     name: String,
+///     hosts: Vec<String>,
+/// }
+/// let merged = json_with_comments::jsonc!({"name": "app", "hosts": ["localhost"]});
+/// let config = Config::deserialize(merged).unwrap();
+/// assert_eq!((config.name, config.hosts), ("app".to_string(), vec!["localhost".to_string()]));
+/// ```
+impl<'de, I, F, M: MapBackend> de::Deserializer<'de> for JsoncValue<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+
+    forward_to_owned_deserializer! {
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_i128()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_u128()
+        deserialize_f32()
+        deserialize_f64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_option()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_map()
+        deserialize_struct(name: &'static str, fields: &'static [&'static str])
+        deserialize_enum(name: &'static str, variants: &'static [&'static str])
+        deserialize_identifier()
+        deserialize_ignored_any()
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::IntoDeserializer<'de, crate::Error> for JsoncValue<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Deserializer = deserializer::OwnedValueDeserializer<I, F, M>;
+    fn into_deserializer(self) -> Self::Deserializer {
+        deserializer::OwnedValueDeserializer::new(self)
+    }
+}
+
+impl<'de, I, F, M: MapBackend> JsoncValue<I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -53,7 +129,7 @@ mod tests {
 
     use std::collections::HashMap;
 
-    use serde::Deserialize;
+    use serde::{de::IntoDeserializer, Deserialize};
 
     use crate::{from_str, jsonc};
 
@@ -178,4 +254,56 @@ mod tests {
         let bird: Animal = target.into_deserialize().unwrap();
         assert!(matches!(bird, Animal::Bird { name: "Pigeon" }));
     }
+
+    #[test]
+    fn test_owned_deserialize_moves_strings() {
+        #[derive(Deserialize)]
+        struct Catalog {
+            name: String,
+            items: Vec<String>,
+            index: HashMap<u32, String>,
+        }
+
+        let target = jsonc!({"name": "catalog", "items": ["apple", "banana"], "index": {"1": "apple"}});
+        let name_ptr = target["name"].as_str().unwrap().as_ptr();
+        let item_ptr = target["items"][1].as_str().unwrap().as_ptr();
+        let catalog = Catalog::deserialize(target).unwrap();
+        assert_eq!(catalog.name.as_ptr(), name_ptr);
+        assert_eq!(catalog.items[1].as_ptr(), item_ptr);
+        assert_eq!(catalog.index, HashMap::from([(1, "apple".to_string())]));
+    }
+
+    #[test]
+    fn test_owned_deserialize_as_value() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum Animal {
+            Dog,
+            Cat(u8),
+            Fish(String, u8),
+            Bird { name: String },
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Zoo {
+            animals: Vec<Animal>,
+            keeper: Option<String>,
+            open: bool,
+            area: (f64, f64),
+        }
+
+        let target = jsonc!({
+            "animals": ["Dog", {"Cat": 2}, {"Fish": ["Tuna", 3]}, {"Bird": {"name": "Pigeon"}}],
+            "keeper": null,
+            "open": true,
+            "area": [1.5, 2.0],
+        });
+        let zoo: Zoo = Zoo::deserialize(target.into_deserializer()).unwrap();
+        let animals =
+            vec![Animal::Dog, Animal::Cat(2), Animal::Fish("Tuna".into(), 3), Animal::Bird { name: "Pigeon".into() }];
+        assert_eq!(zoo, Zoo { animals, keeper: None, open: true, area: (1.5, 2.0) });
+
+        assert!(u8::deserialize(jsonc!(256)).is_err());
+        assert!(String::deserialize(jsonc!(1)).is_err());
+        assert!(Animal::deserialize(jsonc!({"Cat": 1, "Dog": null})).is_err());
+        assert_eq!(JsoncValue::<i64, f64>::deserialize(jsonc!({"k": [1, "v"]})).unwrap(), jsonc!({"k": [1, "v"]}));
+    }
 }
@@ -1,19 +1,27 @@
 use serde::de::{self, IgnoredAny};
 
-use crate::value::{number::Number, JsoncValue};
-
-use super::{map::ValueMapDeserializer, number::FromNumber, r#enum::ValueEnumDeserializer, seq::ValueSeqDeserializer};
-
-pub struct ValueDeserializer<'de, I, F> {
-    pub(crate) value: &'de JsoncValue<I, F>,
+use crate::{
+    error::Ensure,
+    value::{number::Number, DefaultMap, JsoncMap, JsoncValue, MapBackend},
+};
+
+use super::{
+    map::{OwnedValueMapDeserializer, ValueMapDeserializer},
+    number::FromNumber,
+    r#enum::{OwnedValueEnumDeserializer, ValueEnumDeserializer},
+    seq::{OwnedValueSeqDeserializer, ValueSeqDeserializer},
+};
+
+pub struct ValueDeserializer<'de, I, F, M: MapBackend = DefaultMap> {
+    pub(crate) value: &'de JsoncValue<I, F, M>,
 }
 
-impl<'de, I, F> ValueDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> ValueDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
 {
-    pub fn new(value: &'de JsoncValue<I, F>) -> Self {
+    pub fn new(value: &'de JsoncValue<I, F, M>) -> Self {
         Self { value }
     }
 
@@ -35,7 +43,7 @@ where
         E::invalid_type(self.unexpected(), exp)
     }
 
-    pub fn unexpected(&self) -> de::Unexpected {
+    pub fn unexpected(&self) -> de::Unexpected<'_> {
         match &self.value {
             JsoncValue::Object(_) => de::Unexpected::Map,
             JsoncValue::Array(_) => de::Unexpected::Seq,
@@ -56,7 +64,7 @@ where
     }
 }
 
-impl<'de, I, F> de::Deserializer<'de> for ValueDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> de::Deserializer<'de> for ValueDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -264,7 +272,7 @@ where
         V: de::Visitor<'de>,
     {
         match self.value.as_map() {
-            Some(m) => visitor.visit_map(ValueMapDeserializer::new(m)),
+            Some(m) => visitor.visit_map(ValueMapDeserializer::<I, F, M>::new(m)),
             None => Err(self.invalid_type(&visitor)),
         }
     }
@@ -302,7 +310,7 @@ where
                     _ => Err(self.invalid_type(&visitor)),
                 }
             }
-            JsoncValue::String(s) => visitor.visit_enum(ValueEnumDeserializer::<I, F>::new(s, None)),
+            JsoncValue::String(s) => visitor.visit_enum(ValueEnumDeserializer::<I, F, M>::new(s, None)),
             _ => Err(self.invalid_type(&visitor)),
         }
     }
@@ -322,3 +330,312 @@ where
         visitor.visit_unit()
     }
 }
+
+/// Owned version of [`ValueDeserializer`], that consumes the value and moves strings, arrays and objects
+/// into the deserialized value instead of cloning them.
+///
+/// # Examples
+/// ```
+/// use serde::Deserialize;
+/// use json_with_comments::value::de::deserializer::OwnedValueDeserializer;
+/// #[derive(Deserialize)]
+/// struct Person {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+/// let target = json_with_comments::jsonc!({"name": "John", "tags": ["admin"]});
+/// let person = Person::deserialize(OwnedValueDeserializer::new(target)).unwrap();
+/// assert_eq!((person.name, person.tags), ("John".to_string(), vec!["admin".to_string()]));
+/// ```
+pub struct OwnedValueDeserializer<I, F, M: MapBackend = DefaultMap> {
+    pub(crate) value: JsoncValue<I, F, M>,
+}
+
+impl<I, F, M: MapBackend> OwnedValueDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    pub fn new(value: JsoncValue<I, F, M>) -> Self {
+        Self { value }
+    }
+
+    pub fn deserialize_number_value<'de, V, Fn, N>(self, visitor: V, f: Fn) -> crate::Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+        N: FromNumber<I, F>,
+        N::Err: de::Error,
+        crate::Error: From<N::Err>,
+        Fn: FnOnce(V, N) -> Result<V::Value, N::Err>,
+    {
+        match self.value.as_number() {
+            Some(number) => Ok(f(visitor, FromNumber::from_number(number)?)?),
+            _ => Err(self.invalid_type::<crate::Error>(&visitor))?,
+        }
+    }
+
+    pub fn invalid_type<E: de::Error>(&self, exp: &dyn de::Expected) -> E {
+        ValueDeserializer::new(&self.value).invalid_type(exp)
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::Deserializer<'de> for OwnedValueDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match &self.value {
+            JsoncValue::Object(_) => self.deserialize_map(visitor),
+            JsoncValue::Array(_) => self.deserialize_seq(visitor),
+            JsoncValue::Bool(_) => self.deserialize_bool(visitor),
+            JsoncValue::Null => self.deserialize_unit(visitor),
+            JsoncValue::String(_) => self.deserialize_string(visitor),
+            JsoncValue::Number(n) => match n {
+                Number::Integer(_) => self.deserialize_i64(visitor),
+                Number::Float(_) => self.deserialize_f64(visitor),
+            },
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value.as_bool() {
+            Some(&b) => visitor.visit_bool(b),
+            None => Err(self.invalid_type::<crate::Error>(&visitor))?,
+        }
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_i8(n))
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_i16(n))
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_i32(n))
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_i64(n))
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_u8(n))
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_u16(n))
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_u32(n))
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_u64(n))
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_f32(n))
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_number_value(visitor, |v, n| v.visit_f64(n))
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            JsoncValue::String(s) => visitor.visit_string(s),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value.as_unit() {
+            Some(()) => visitor.visit_none(),
+            None => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value.as_unit() {
+            Some(()) => visitor.visit_unit(),
+            None => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            JsoncValue::Array(v) => visitor.visit_seq(OwnedValueSeqDeserializer::new(v)),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            JsoncValue::Object(m) => visitor.visit_map(OwnedValueMapDeserializer::<I, F, M>::new(m)),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            JsoncValue::Array(_) => self.deserialize_seq(visitor),
+            JsoncValue::Object(_) => self.deserialize_map(visitor),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.value {
+            JsoncValue::Object(m) if m.len() == 1 => {
+                let (key, value) = m.into_iter().next().ok_or(Ensure::NextValue)?;
+                visitor.visit_enum(OwnedValueEnumDeserializer::new(key, Some(value)))
+            }
+            JsoncValue::String(s) => visitor.visit_enum(OwnedValueEnumDeserializer::<I, F, M>::new(s, None)),
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        drop(self);
+        visitor.visit_unit()
+    }
+}
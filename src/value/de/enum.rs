@@ -1,25 +1,28 @@
 use serde::de::{self, IntoDeserializer};
 
-use crate::{error::Ensure, value::JsoncValue};
+use crate::{
+    error::Ensure,
+    value::{DefaultMap, JsoncValue, MapBackend},
+};
 
-use super::deserializer::ValueDeserializer;
+use super::deserializer::{OwnedValueDeserializer, ValueDeserializer};
 
-pub struct ValueEnumDeserializer<'de, I, F> {
+pub struct ValueEnumDeserializer<'de, I, F, M: MapBackend = DefaultMap> {
     variant: &'de str,
-    value: Option<&'de JsoncValue<I, F>>,
+    value: Option<&'de JsoncValue<I, F, M>>,
 }
 
-impl<'de, I, F> ValueEnumDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> ValueEnumDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
 {
-    pub fn new(variant: &'de str, value: Option<&'de JsoncValue<I, F>>) -> Self {
+    pub fn new(variant: &'de str, value: Option<&'de JsoncValue<I, F, M>>) -> Self {
         ValueEnumDeserializer { variant, value }
     }
 }
 
-impl<'de, I, F> de::EnumAccess<'de> for ValueEnumDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> de::EnumAccess<'de> for ValueEnumDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -36,7 +39,7 @@ where
     }
 }
 
-impl<'de, I, F> de::VariantAccess<'de> for ValueEnumDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> de::VariantAccess<'de> for ValueEnumDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -74,3 +77,75 @@ where
         })
     }
 }
+
+/// Owned version of [`ValueEnumDeserializer`], that moves the variant and its content into the deserialized value.
+pub struct OwnedValueEnumDeserializer<I, F, M: MapBackend = DefaultMap> {
+    variant: String,
+    value: Option<JsoncValue<I, F, M>>,
+}
+
+impl<I, F, M: MapBackend> OwnedValueEnumDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    pub fn new(variant: String, value: Option<JsoncValue<I, F, M>>) -> Self {
+        OwnedValueEnumDeserializer { variant, value }
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::EnumAccess<'de> for OwnedValueEnumDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = std::mem::take(&mut self.variant).into_deserializer();
+        seed.deserialize(variant).map(|v| (v, self))
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::VariantAccess<'de> for OwnedValueEnumDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(self.value.is_none().then_some(()).ok_or(Ensure::UnitVariant)?)
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.value.map_or(Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"newtype variant")), |v| {
+            seed.deserialize(OwnedValueDeserializer::new(v))
+        })
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.value.map_or(Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"tuple variant")), |v| {
+            de::Deserializer::deserialize_seq(OwnedValueDeserializer::new(v), visitor)
+        })
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.value.map_or(Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"struct variant")), |v| {
+            de::Deserializer::deserialize_map(OwnedValueDeserializer::new(v), visitor)
+        })
+    }
+}
@@ -1,29 +1,32 @@
+use std::borrow::Cow;
+
 use serde::de::{self, IgnoredAny};
 
 use crate::{
     error::{Ensure, SemanticError},
     from_str,
-    value::{JsoncValue, MapImpl},
+    value::{DefaultMap, JsoncMap, JsoncValue, MapBackend},
 };
 
-use super::deserializer::ValueDeserializer;
+use super::deserializer::{OwnedValueDeserializer, ValueDeserializer};
 
-pub struct ValueMapDeserializer<'de, I, F> {
-    iter: Box<dyn Iterator<Item = (&'de String, &'de JsoncValue<I, F>)> + 'de>,
-    next: Option<&'de JsoncValue<I, F>>,
+type Entries<'de, I, F, M> = Box<dyn Iterator<Item = (&'de String, &'de JsoncValue<I, F, M>)> + 'de>;
+pub struct ValueMapDeserializer<'de, I, F, M: MapBackend = DefaultMap> {
+    iter: Entries<'de, I, F, M>,
+    next: Option<&'de JsoncValue<I, F, M>>,
 }
 
-impl<'de, I, F> ValueMapDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> ValueMapDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
 {
-    pub fn new(map: &'de MapImpl<String, JsoncValue<I, F>>) -> Self {
+    pub fn new(map: &'de M::Map<JsoncValue<I, F, M>>) -> Self {
         ValueMapDeserializer { iter: Box::new(map.iter()), next: None }
     }
 }
 
-impl<'de, I, F> de::MapAccess<'de> for ValueMapDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> de::MapAccess<'de> for ValueMapDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -52,13 +55,60 @@ where
     }
 }
 
+type IntoEntries<I, F, M> = <<M as MapBackend>::Map<JsoncValue<I, F, M>> as IntoIterator>::IntoIter;
+/// Owned version of [`ValueMapDeserializer`], that moves keys and values into the deserialized value.
+pub struct OwnedValueMapDeserializer<I, F, M: MapBackend = DefaultMap> {
+    iter: IntoEntries<I, F, M>,
+    next: Option<JsoncValue<I, F, M>>,
+}
+
+impl<I, F, M: MapBackend> OwnedValueMapDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    pub fn new(map: M::Map<JsoncValue<I, F, M>>) -> Self {
+        OwnedValueMapDeserializer { iter: map.into_iter(), next: None }
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::MapAccess<'de> for OwnedValueMapDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.iter.next().map_or(Ok(None), |(k, v)| {
+            self.next = Some(v);
+            seed.deserialize(ValueMapKeyDeserializer::new(k)).map(Some)
+        })
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.next.take().map(|v| seed.deserialize(OwnedValueDeserializer::new(v))).ok_or(Ensure::NextValue)?
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.iter.size_hint().1
+    }
+}
+
+/// Deserializer of object keys, borrowed keys are visited as borrowed str and owned keys are moved.
 pub struct ValueMapKeyDeserializer<'de> {
-    key: &'de str,
+    key: Cow<'de, str>,
 }
 
 impl<'de> ValueMapKeyDeserializer<'de> {
-    pub fn new(key: &'de str) -> Self {
-        ValueMapKeyDeserializer { key }
+    pub fn new(key: impl Into<Cow<'de, str>>) -> Self {
+        ValueMapKeyDeserializer { key: key.into() }
     }
 }
 
@@ -76,98 +126,101 @@ impl<'de> de::Deserializer<'de> for ValueMapKeyDeserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_bool(from_str(self.key)?)
+        visitor.visit_bool(from_str(&self.key)?)
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i8(from_str(self.key)?)
+        visitor.visit_i8(from_str(&self.key)?)
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i16(from_str(self.key)?)
+        visitor.visit_i16(from_str(&self.key)?)
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i32(from_str(self.key)?)
+        visitor.visit_i32(from_str(&self.key)?)
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i64(from_str(self.key)?)
+        visitor.visit_i64(from_str(&self.key)?)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u8(from_str(self.key)?)
+        visitor.visit_u8(from_str(&self.key)?)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u16(from_str(self.key)?)
+        visitor.visit_u16(from_str(&self.key)?)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u32(from_str(self.key)?)
+        visitor.visit_u32(from_str(&self.key)?)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u64(from_str(self.key)?)
+        visitor.visit_u64(from_str(&self.key)?)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_f32(from_str(self.key)?)
+        visitor.visit_f32(from_str(&self.key)?)
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_f64(from_str(self.key)?)
+        visitor.visit_f64(from_str(&self.key)?)
     }
 
     fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_char(from_str(self.key)?)
+        visitor.visit_char(from_str(&self.key)?)
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_borrowed_str(self.key)
+        match self.key {
+            Cow::Borrowed(key) => visitor.visit_borrowed_str(key),
+            Cow::Owned(key) => visitor.visit_string(key),
+        }
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_borrowed_str(self.key)
+        self.deserialize_str(visitor)
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -181,7 +234,7 @@ impl<'de> de::Deserializer<'de> for ValueMapKeyDeserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_byte_buf(self.key.as_bytes().to_vec())
+        visitor.visit_byte_buf(self.key.into_owned().into_bytes())
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -11,6 +11,8 @@ where
 
 pub enum IntegerConverter {}
 pub enum FloatConverter {}
+/// Converts integers and floats into any [`Converted`] type, only if the value is kept exactly.
+pub enum LosslessConverter {}
 
 pub trait Converter<I, F>
 where
@@ -49,7 +51,40 @@ where
     }
 }
 
-pub trait Converted<I, F>: Sized
+impl<I, F> Converter<I, F> for LosslessConverter
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Err = crate::Error;
+    fn convert<N: Converted<I, F>>(n: &Number<I, F>) -> Result<N, Self::Err> {
+        let target = std::any::type_name::<N>();
+        let (converted, lossless) = match n {
+            Number::Integer(i) => {
+                let converted: N = Converted::<I, F>::to_self(i).ok_or(ConvertError::OutOfRange { target })?;
+                let lossless = match (i.to_i128(), converted.to_i128()) {
+                    (Some(src), Some(dst)) => src == dst,
+                    _ => i.to_u128().is_some_and(|src| converted.to_u128() == Some(src)),
+                };
+                (converted, lossless)
+            }
+            Number::Float(f) => {
+                let converted: N = Converted::<I, F>::to_self(f).ok_or(ConvertError::OutOfRange { target })?;
+                let lossless = match (f.to_f64(), converted.to_f64()) {
+                    (Some(src), Some(dst)) => src == dst || (src.is_nan() && dst.is_nan()),
+                    _ => false,
+                };
+                (converted, lossless)
+            }
+        };
+        match lossless {
+            true => Ok(converted),
+            false => Err(ConvertError::PrecisionLoss { target })?,
+        }
+    }
+}
+
+pub trait Converted<I, F>: Sized + num::ToPrimitive
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -103,6 +138,12 @@ impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for u128 {
         p.to_u128()
     }
 }
+impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for usize {
+    type Converter = IntegerConverter;
+    fn to_self<P: num::ToPrimitive>(p: &P) -> Option<Self> {
+        p.to_usize()
+    }
+}
 impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for i8 {
     type Converter = IntegerConverter;
     fn to_self<P: num::ToPrimitive>(p: &P) -> Option<Self> {
@@ -133,6 +174,12 @@ impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for i128 {
         p.to_i128()
     }
 }
+impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for isize {
+    type Converter = IntegerConverter;
+    fn to_self<P: num::ToPrimitive>(p: &P) -> Option<Self> {
+        p.to_isize()
+    }
+}
 impl<I: num::ToPrimitive, F: num::ToPrimitive> Converted<I, F> for f32 {
     type Converter = FloatConverter;
     fn to_self<P: num::ToPrimitive>(p: &P) -> Option<Self> {
@@ -1,24 +1,24 @@
 use serde::de;
 
-use crate::value::JsoncValue;
+use crate::value::{DefaultMap, JsoncValue, MapBackend};
 
-use super::deserializer::ValueDeserializer;
+use super::deserializer::{OwnedValueDeserializer, ValueDeserializer};
 
-pub struct ValueSeqDeserializer<'de, I, F> {
-    iter: Box<dyn Iterator<Item = &'de JsoncValue<I, F>> + 'de>,
+pub struct ValueSeqDeserializer<'de, I, F, M: MapBackend = DefaultMap> {
+    iter: Box<dyn Iterator<Item = &'de JsoncValue<I, F, M>> + 'de>,
 }
 
-impl<'de, I, F> ValueSeqDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> ValueSeqDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
 {
-    pub fn new(seq: &'de [JsoncValue<I, F>]) -> Self {
+    pub fn new(seq: &'de [JsoncValue<I, F, M>]) -> Self {
         ValueSeqDeserializer { iter: Box::new(seq.iter()) }
     }
 }
 
-impl<'de, I, F> de::SeqAccess<'de> for ValueSeqDeserializer<'de, I, F>
+impl<'de, I, F, M: MapBackend> de::SeqAccess<'de> for ValueSeqDeserializer<'de, I, F, M>
 where
     I: num::ToPrimitive,
     F: num::ToPrimitive,
@@ -36,3 +36,37 @@ where
         self.iter.size_hint().1
     }
 }
+
+/// Owned version of [`ValueSeqDeserializer`], that moves elements into the deserialized value.
+pub struct OwnedValueSeqDeserializer<I, F, M: MapBackend = DefaultMap> {
+    iter: std::vec::IntoIter<JsoncValue<I, F, M>>,
+}
+
+impl<I, F, M: MapBackend> OwnedValueSeqDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    pub fn new(seq: Vec<JsoncValue<I, F, M>>) -> Self {
+        OwnedValueSeqDeserializer { iter: seq.into_iter() }
+    }
+}
+
+impl<'de, I, F, M: MapBackend> de::SeqAccess<'de> for OwnedValueSeqDeserializer<I, F, M>
+where
+    I: num::ToPrimitive,
+    F: num::ToPrimitive,
+{
+    type Error = crate::Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: de::DeserializeSeed<'de>,
+    {
+        self.iter.next().map_or(Ok(None), |v| seed.deserialize(OwnedValueDeserializer::new(v)).map(Some))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.iter.size_hint().1
+    }
+}
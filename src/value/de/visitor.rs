@@ -5,26 +5,24 @@ use serde::{
 
 use crate::{
     error::{Ensure, SemanticError},
-    value::{number::Number, JsoncValue},
+    value::{number::Number, DefaultMap, JsoncMap, JsoncValue, MapBackend},
 };
 
-use super::MapImpl;
-
-pub struct JsoncValueVisitor<I, F> {
-    phantom: std::marker::PhantomData<(I, F)>,
+pub struct JsoncValueVisitor<I, F, M: MapBackend = DefaultMap> {
+    phantom: std::marker::PhantomData<(I, F, M)>,
 }
-impl<I, F> JsoncValueVisitor<I, F> {
+impl<I, F, M: MapBackend> JsoncValueVisitor<I, F, M> {
     pub fn new() -> Self {
         Self { phantom: std::marker::PhantomData }
     }
 }
-impl<I, F> Default for JsoncValueVisitor<I, F> {
+impl<I, F, M: MapBackend> Default for JsoncValueVisitor<I, F, M> {
     fn default() -> Self {
         Self::new()
     }
 }
-impl<'de, I: num::FromPrimitive, F: num::FromPrimitive> Visitor<'de> for JsoncValueVisitor<I, F> {
-    type Value = JsoncValue<I, F>;
+impl<'de, I: num::FromPrimitive, F: num::FromPrimitive, M: MapBackend> Visitor<'de> for JsoncValueVisitor<I, F, M> {
+    type Value = JsoncValue<I, F, M>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("any valid JSONC value")
@@ -193,8 +191,8 @@ impl<'de, I: num::FromPrimitive, F: num::FromPrimitive> Visitor<'de> for JsoncVa
     where
         A: serde::de::MapAccess<'de>,
     {
-        let mut v = MapImpl::new();
-        while let Some((key, value)) = map.next_entry::<JsoncValue<I, F>, JsoncValue<I, F>>()? {
+        let mut v: M::Map<JsoncValue<I, F, M>> = Default::default();
+        while let Some((key, value)) = map.next_entry::<JsoncValue<I, F, M>, JsoncValue<I, F, M>>()? {
             match key {
                 JsoncValue::String(s) => v.insert(s, value),
                 _ => Err(A::Error::custom(SemanticError::AnyMapKey))?,
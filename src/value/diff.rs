@@ -0,0 +1,337 @@
+use std::fmt;
+
+use super::{number::Number, pointer::to_pointer, DefaultMap, JsoncMap, JsoncValue, MapBackend};
+
+/// Compare two values structurally and returns the changes from `a` to `b`, with default [`DiffOptions`].
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, value::{self, diff::Change}};
+/// let a = jsonc!({"name": "app", "port": 80, "tags": ["a"]});
+/// let b = jsonc!({"name": "app", "port": "80", "tags": ["a", "b"], "debug": true});
+/// let mut changes = value::diff(&a, &b);
+/// changes.sort_by(|c1, c2| c1.pointer().cmp(c2.pointer()));
+/// assert_eq!(changes, vec![
+///     Change::Added { pointer: "/debug".to_string(), value: &true.into() },
+///     Change::TypeChanged { pointer: "/port".to_string(), from: &80.into(), to: &"80".into() },
+///     Change::Added { pointer: "/tags/1".to_string(), value: &"b".into() },
+/// ]);
+/// ```
+pub fn diff<'a, I, F, M: MapBackend>(a: &'a JsoncValue<I, F, M>, b: &'a JsoncValue<I, F, M>) -> Vec<Change<'a, I, F, M>>
+where
+    I: PartialEq + num::ToPrimitive,
+    F: PartialEq + num::ToPrimitive,
+{
+    diff_with(a, b, &DiffOptions::default())
+}
+
+/// Compare two values structurally with [`DiffOptions`], see [`diff`].
+/// Pointers of removed values point into `a`, and pointers of the other changes point into `b`.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, value::diff::{diff_with, render, ArrayDiff, DiffOptions}};
+/// let a = jsonc!({"users": [{"id": 1, "name": "alice"}, {"id": 2, "name": "bob"}], "ratio": 0.3});
+/// let b = jsonc!({"users": [{"id": 2, "name": "bob"}, {"id": 1, "name": "Alice"}], "ratio": 0.30000000001});
+/// let options = DiffOptions::new().array(ArrayDiff::Key("id".to_string())).epsilon(1e-9);
+/// let changes = diff_with(&a, &b, &options);
+/// assert_eq!(render(&changes), "~ /users/1/name: \"alice\" -> \"Alice\"\n");
+/// ```
+pub fn diff_with<'a, I, F, M: MapBackend>(
+    a: &'a JsoncValue<I, F, M>,
+    b: &'a JsoncValue<I, F, M>,
+    options: &DiffOptions,
+) -> Vec<Change<'a, I, F, M>>
+where
+    I: PartialEq + num::ToPrimitive,
+    F: PartialEq + num::ToPrimitive,
+{
+    let mut differ = Differ { options, path: Vec::new(), changes: Vec::new() };
+    differ.diff(a, b);
+    differ.changes
+}
+
+/// Render changes as human-readable lines, such as `+ /a: 1`, `- /b: 2`, `~ /c: 3 -> 4` and `! /d: 5 -> "5"`.
+pub fn render<I: serde::Serialize, F: serde::Serialize, M: MapBackend>(changes: &[Change<'_, I, F, M>]) -> String {
+    changes.iter().map(|change| format!("{change}\n")).collect()
+}
+
+/// A change between two values, that has JSON Pointer of its location.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<'a, I = i64, F = f64, M: MapBackend = DefaultMap> {
+    /// The value exists only in `b`.
+    Added { pointer: String, value: &'a JsoncValue<I, F, M> },
+    /// The value exists only in `a`.
+    Removed { pointer: String, value: &'a JsoncValue<I, F, M> },
+    /// The values have the same type but are different.
+    Modified { pointer: String, from: &'a JsoncValue<I, F, M>, to: &'a JsoncValue<I, F, M> },
+    /// The values have different types.
+    TypeChanged { pointer: String, from: &'a JsoncValue<I, F, M>, to: &'a JsoncValue<I, F, M> },
+}
+impl<I, F, M: MapBackend> Change<'_, I, F, M> {
+    pub fn pointer(&self) -> &str {
+        match self {
+            Change::Added { pointer, .. }
+            | Change::Removed { pointer, .. }
+            | Change::Modified { pointer, .. }
+            | Change::TypeChanged { pointer, .. } => pointer,
+        }
+    }
+}
+impl<I: serde::Serialize, F: serde::Serialize, M: MapBackend> fmt::Display for Change<'_, I, F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { pointer, value } => write!(f, "+ {pointer}: {value}"),
+            Change::Removed { pointer, value } => write!(f, "- {pointer}: {value}"),
+            Change::Modified { pointer, from, to } => write!(f, "~ {pointer}: {from} -> {to}"),
+            Change::TypeChanged { pointer, from, to } => write!(f, "! {pointer}: {from} -> {to}"),
+        }
+    }
+}
+
+/// Options of [`diff_with`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DiffOptions {
+    array: ArrayDiff,
+    epsilon: Option<f64>,
+}
+impl DiffOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn array(self, array: ArrayDiff) -> Self {
+        Self { array, ..self }
+    }
+
+    /// Numbers whose difference is less than or equal to `epsilon` are treated as equal.
+    pub fn epsilon(self, epsilon: f64) -> Self {
+        Self { epsilon: Some(epsilon), ..self }
+    }
+}
+
+/// How elements of two arrays are matched by [`diff_with`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ArrayDiff {
+    /// Elements at the same index are compared.
+    #[default]
+    Position,
+    /// Elements are matched by the longest common subsequence, unmatched elements are compared in order.
+    Lcs,
+    /// Object elements that have the same value of the key field are compared.
+    Key(String),
+}
+
+struct Differ<'a, 'o, I, F, M: MapBackend> {
+    options: &'o DiffOptions,
+    path: Vec<String>,
+    changes: Vec<Change<'a, I, F, M>>,
+}
+impl<'a, I, F, M: MapBackend> Differ<'a, '_, I, F, M>
+where
+    I: PartialEq + num::ToPrimitive,
+    F: PartialEq + num::ToPrimitive,
+{
+    fn diff(&mut self, a: &'a JsoncValue<I, F, M>, b: &'a JsoncValue<I, F, M>) {
+        match (a, b) {
+            (JsoncValue::Object(a), JsoncValue::Object(b)) => {
+                for (key, a) in a.iter() {
+                    self.path.push(key.clone());
+                    match b.get(key) {
+                        Some(b) => self.diff(a, b),
+                        None => self.changes.push(Change::Removed { pointer: to_pointer(&self.path), value: a }),
+                    }
+                    self.path.pop();
+                }
+                for (key, b) in b.iter().filter(|(key, _)| !a.contains_key(key)) {
+                    self.path.push(key.clone());
+                    self.changes.push(Change::Added { pointer: to_pointer(&self.path), value: b });
+                    self.path.pop();
+                }
+            }
+            (JsoncValue::Array(a), JsoncValue::Array(b)) => match &self.options.array {
+                ArrayDiff::Position => self.diff_position(a, 0, b, 0),
+                ArrayDiff::Lcs => self.diff_lcs(a, b),
+                ArrayDiff::Key(key) => self.diff_key(a, b, key),
+            },
+            (JsoncValue::Number(a), JsoncValue::Number(b)) if self.number_equal(a, b) => {}
+            (a, b) if a == b => {}
+            (a, b) if a.value_type() == b.value_type() => {
+                self.changes.push(Change::Modified { pointer: to_pointer(&self.path), from: a, to: b })
+            }
+            (a, b) => self.changes.push(Change::TypeChanged { pointer: to_pointer(&self.path), from: a, to: b }),
+        }
+    }
+
+    /// Compare elements by position, `a[i]` is compared with `b[i]` for each offset `i`.
+    fn diff_position(
+        &mut self,
+        a: &'a [JsoncValue<I, F, M>],
+        a_offset: usize,
+        b: &'a [JsoncValue<I, F, M>],
+        b_offset: usize,
+    ) {
+        for (i, (a, b)) in a.iter().zip(b).enumerate() {
+            self.path.push((b_offset + i).to_string());
+            self.diff(a, b);
+            self.path.pop();
+        }
+        for (i, a) in a.iter().enumerate().skip(b.len()) {
+            self.path.push((a_offset + i).to_string());
+            self.changes.push(Change::Removed { pointer: to_pointer(&self.path), value: a });
+            self.path.pop();
+        }
+        for (i, b) in b.iter().enumerate().skip(a.len()) {
+            self.path.push((b_offset + i).to_string());
+            self.changes.push(Change::Added { pointer: to_pointer(&self.path), value: b });
+            self.path.pop();
+        }
+    }
+
+    fn diff_lcs(&mut self, a: &'a [JsoncValue<I, F, M>], b: &'a [JsoncValue<I, F, M>]) {
+        // table[i][j] is the length of LCS of a[i..] and b[j..]
+        let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                table[i][j] = match self.equal(&a[i], &b[j]) {
+                    true => table[i + 1][j + 1] + 1,
+                    false => table[i + 1][j].max(table[i][j + 1]),
+                };
+            }
+        }
+
+        let (mut i, mut j, mut gap) = (0, 0, (0, 0));
+        while i < a.len() && j < b.len() {
+            if self.equal(&a[i], &b[j]) {
+                self.diff_position(&a[gap.0..i], gap.0, &b[gap.1..j], gap.1);
+                (i, j) = (i + 1, j + 1);
+                gap = (i, j);
+            } else if table[i + 1][j] >= table[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        self.diff_position(&a[gap.0..], gap.0, &b[gap.1..], gap.1);
+    }
+
+    fn diff_key(&mut self, a: &'a [JsoncValue<I, F, M>], b: &'a [JsoncValue<I, F, M>], key: &str) {
+        let position = |elements: &'a [JsoncValue<I, F, M>], k: &JsoncValue<I, F, M>| {
+            elements.iter().position(|e| e.get(key).is_some_and(|ek| self.equal(ek, k)))
+        };
+        let matched: Vec<_> = a.iter().map(|a| a.get(key).and_then(|k| position(b, k))).collect();
+        for (i, a) in a.iter().enumerate() {
+            match matched[i] {
+                Some(j) => {
+                    self.path.push(j.to_string());
+                    self.diff(a, &b[j]);
+                }
+                None => {
+                    self.path.push(i.to_string());
+                    self.changes.push(Change::Removed { pointer: to_pointer(&self.path), value: a });
+                }
+            }
+            self.path.pop();
+        }
+        for (j, b) in b.iter().enumerate().filter(|(j, _)| !matched.contains(&Some(*j))) {
+            self.path.push(j.to_string());
+            self.changes.push(Change::Added { pointer: to_pointer(&self.path), value: b });
+            self.path.pop();
+        }
+    }
+
+    fn equal(&self, a: &JsoncValue<I, F, M>, b: &JsoncValue<I, F, M>) -> bool {
+        match (a, b) {
+            (JsoncValue::Object(a), JsoncValue::Object(b)) => {
+                a.len() == b.len() && a.iter().all(|(k, a)| b.get(k).is_some_and(|b| self.equal(a, b)))
+            }
+            (JsoncValue::Array(a), JsoncValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| self.equal(a, b))
+            }
+            (JsoncValue::Number(a), JsoncValue::Number(b)) => self.number_equal(a, b),
+            (a, b) => a == b,
+        }
+    }
+
+    fn number_equal(&self, a: &Number<I, F>, b: &Number<I, F>) -> bool {
+        match (self.options.epsilon, a, b) {
+            (_, Number::Integer(a), Number::Integer(b)) => a == b,
+            (Some(epsilon), a, b) => {
+                let to_f64 = |n: &Number<I, F>| match n {
+                    Number::Integer(i) => i.to_f64(),
+                    Number::Float(f) => f.to_f64(),
+                };
+                matches!((to_f64(a), to_f64(b)), (Some(a), Some(b)) if (a - b).abs() <= epsilon)
+            }
+            (None, a, b) => a == b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc, Value};
+
+    use super::*;
+
+    fn sorted<'a>(mut changes: Vec<Change<'a, i64, f64>>) -> Vec<Change<'a, i64, f64>> {
+        changes.sort_by(|c1, c2| c1.pointer().cmp(c2.pointer()));
+        changes
+    }
+
+    #[test]
+    fn test_diff_object() {
+        let (a, b) = (jsonc!({"a": 1, "b": {"c": true, "d": [1]}, "e": null}), jsonc!({"a": 1.0, "b": {"c": false}}));
+        assert_eq!(
+            sorted(diff(&a, &b)),
+            vec![
+                Change::Modified { pointer: "/a".to_string(), from: &1.into(), to: &1.0.into() },
+                Change::Modified { pointer: "/b/c".to_string(), from: &true.into(), to: &false.into() },
+                Change::Removed { pointer: "/b/d".to_string(), value: &jsonc!([1]) },
+                Change::Removed { pointer: "/e".to_string(), value: &Value::Null },
+            ]
+        );
+        assert_eq!(diff(&a, &a), vec![]);
+        assert_eq!(diff_with(&jsonc!(1), &jsonc!(1.0), &DiffOptions::new().epsilon(0.0)), vec![]);
+        assert_eq!(diff_with(&jsonc!(0.1), &jsonc!(0.2), &DiffOptions::new().epsilon(0.01)).len(), 1);
+    }
+
+    #[test]
+    fn test_diff_array() {
+        let (a, b) = (jsonc!([1, 2, 3, 4]), jsonc!([0, 1, 3, 4, 5]));
+        assert_eq!(diff(&a, &b).len(), 3);
+        assert_eq!(
+            diff_with(&a, &b, &DiffOptions::new().array(ArrayDiff::Lcs)),
+            vec![
+                Change::Added { pointer: "/0".to_string(), value: &0.into() },
+                Change::Removed { pointer: "/1".to_string(), value: &2.into() },
+                Change::Added { pointer: "/4".to_string(), value: &5.into() },
+            ]
+        );
+        let (a, b) = (jsonc!(["a", "b", "c"]), jsonc!(["a", "x", "c"]));
+        assert_eq!(
+            diff_with(&a, &b, &DiffOptions::new().array(ArrayDiff::Lcs)),
+            vec![Change::Modified { pointer: "/1".to_string(), from: &"b".into(), to: &"x".into() }]
+        );
+
+        let (a, b) = (jsonc!([{"id": 1, "v": 1}, {"id": 2, "v": 2}, 3]), jsonc!([{"id": 3}, {"id": 2, "v": 0}]));
+        assert_eq!(
+            sorted(diff_with(&a, &b, &DiffOptions::new().array(ArrayDiff::Key("id".to_string())))),
+            vec![
+                Change::Removed { pointer: "/0".to_string(), value: &jsonc!({"id": 1, "v": 1}) },
+                Change::Added { pointer: "/0".to_string(), value: &jsonc!({"id": 3}) },
+                Change::Modified { pointer: "/1/v".to_string(), from: &2.into(), to: &0.into() },
+                Change::Removed { pointer: "/2".to_string(), value: &3.into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let (a, b) = (jsonc!({"a": [1, 2], "b": "x", "c": {}}), jsonc!({"a": [1], "b": 1, "d": [null]}));
+        assert_eq!(
+            render(&sorted(diff(&a, &b))),
+            ["- /a/1: 2", "! /b: \"x\" -> 1", "- /c: {}", "+ /d: [null]", ""].join("\n")
+        );
+    }
+}
@@ -1,51 +1,70 @@
-use super::{number::Number, JsoncValue, MapImpl};
+use std::collections::{BTreeMap, HashMap};
 
-impl<I, F> From<MapImpl<String, JsoncValue<I, F>>> for JsoncValue<I, F> {
-    fn from(value: MapImpl<String, JsoncValue<I, F>>) -> Self {
+use super::{
+    map::{BTreeMapBackend, HashMapBackend},
+    number::Number,
+    JsoncValue, MapBackend,
+};
+
+impl<I, F> From<HashMap<String, JsoncValue<I, F, HashMapBackend>>> for JsoncValue<I, F, HashMapBackend> {
+    fn from(value: HashMap<String, JsoncValue<I, F, HashMapBackend>>) -> Self {
+        JsoncValue::Object(value)
+    }
+}
+impl<I, F> From<BTreeMap<String, JsoncValue<I, F, BTreeMapBackend>>> for JsoncValue<I, F, BTreeMapBackend> {
+    fn from(value: BTreeMap<String, JsoncValue<I, F, BTreeMapBackend>>) -> Self {
+        JsoncValue::Object(value)
+    }
+}
+#[cfg(feature = "indexmap")]
+impl<I, F> From<indexmap::IndexMap<String, JsoncValue<I, F, super::map::IndexMapBackend>>>
+    for JsoncValue<I, F, super::map::IndexMapBackend>
+{
+    fn from(value: indexmap::IndexMap<String, JsoncValue<I, F, super::map::IndexMapBackend>>) -> Self {
         JsoncValue::Object(value)
     }
 }
-impl<I, F> FromIterator<(String, JsoncValue<I, F>)> for JsoncValue<I, F> {
-    fn from_iter<T: IntoIterator<Item = (String, JsoncValue<I, F>)>>(iter: T) -> Self {
+impl<I, F, M: MapBackend> FromIterator<(String, JsoncValue<I, F, M>)> for JsoncValue<I, F, M> {
+    fn from_iter<T: IntoIterator<Item = (String, JsoncValue<I, F, M>)>>(iter: T) -> Self {
         JsoncValue::Object(iter.into_iter().collect())
     }
 }
 
-impl<I, F> From<Vec<JsoncValue<I, F>>> for JsoncValue<I, F> {
-    fn from(value: Vec<JsoncValue<I, F>>) -> Self {
+impl<I, F, M: MapBackend> From<Vec<JsoncValue<I, F, M>>> for JsoncValue<I, F, M> {
+    fn from(value: Vec<JsoncValue<I, F, M>>) -> Self {
         JsoncValue::Array(value)
     }
 }
-impl<I, F> FromIterator<JsoncValue<I, F>> for JsoncValue<I, F> {
-    fn from_iter<T: IntoIterator<Item = JsoncValue<I, F>>>(iter: T) -> Self {
+impl<I, F, M: MapBackend> FromIterator<JsoncValue<I, F, M>> for JsoncValue<I, F, M> {
+    fn from_iter<T: IntoIterator<Item = JsoncValue<I, F, M>>>(iter: T) -> Self {
         JsoncValue::Array(iter.into_iter().collect())
     }
 }
 
-impl<I, F> From<bool> for JsoncValue<I, F> {
+impl<I, F, M: MapBackend> From<bool> for JsoncValue<I, F, M> {
     fn from(value: bool) -> Self {
         JsoncValue::Bool(value)
     }
 }
 
-impl<I, F> From<()> for JsoncValue<I, F> {
+impl<I, F, M: MapBackend> From<()> for JsoncValue<I, F, M> {
     fn from(_: ()) -> Self {
         JsoncValue::Null
     }
 }
 
-impl<I, F> From<String> for JsoncValue<I, F> {
+impl<I, F, M: MapBackend> From<String> for JsoncValue<I, F, M> {
     fn from(value: String) -> Self {
         JsoncValue::String(value)
     }
 }
-impl<I, F> From<&str> for JsoncValue<I, F> {
+impl<I, F, M: MapBackend> From<&str> for JsoncValue<I, F, M> {
     fn from(value: &str) -> Self {
         JsoncValue::String(value.to_owned())
     }
 }
 
-impl<I, F> From<Number<I, F>> for JsoncValue<I, F> {
+impl<I, F, M: MapBackend> From<Number<I, F>> for JsoncValue<I, F, M> {
     fn from(value: Number<I, F>) -> Self {
         JsoncValue::Number(value)
     }
@@ -64,62 +83,62 @@ impl<I, F> From<Number<I, F>> for JsoncValue<I, F> {
 // }
 
 // TODO macro?
-impl<F> From<u8> for JsoncValue<u8, F> {
+impl<F, M: MapBackend> From<u8> for JsoncValue<u8, F, M> {
     fn from(value: u8) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<u16> for JsoncValue<u16, F> {
+impl<F, M: MapBackend> From<u16> for JsoncValue<u16, F, M> {
     fn from(value: u16) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<u32> for JsoncValue<u32, F> {
+impl<F, M: MapBackend> From<u32> for JsoncValue<u32, F, M> {
     fn from(value: u32) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<u64> for JsoncValue<u64, F> {
+impl<F, M: MapBackend> From<u64> for JsoncValue<u64, F, M> {
     fn from(value: u64) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<u128> for JsoncValue<u128, F> {
+impl<F, M: MapBackend> From<u128> for JsoncValue<u128, F, M> {
     fn from(value: u128) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<i8> for JsoncValue<i8, F> {
+impl<F, M: MapBackend> From<i8> for JsoncValue<i8, F, M> {
     fn from(value: i8) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<i16> for JsoncValue<i16, F> {
+impl<F, M: MapBackend> From<i16> for JsoncValue<i16, F, M> {
     fn from(value: i16) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<i32> for JsoncValue<i32, F> {
+impl<F, M: MapBackend> From<i32> for JsoncValue<i32, F, M> {
     fn from(value: i32) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<i64> for JsoncValue<i64, F> {
+impl<F, M: MapBackend> From<i64> for JsoncValue<i64, F, M> {
     fn from(value: i64) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<F> From<i128> for JsoncValue<i128, F> {
+impl<F, M: MapBackend> From<i128> for JsoncValue<i128, F, M> {
     fn from(value: i128) -> Self {
         JsoncValue::Number(Number::Integer(value))
     }
 }
-impl<I> From<f32> for JsoncValue<I, f32> {
+impl<I, M: MapBackend> From<f32> for JsoncValue<I, f32, M> {
     fn from(value: f32) -> Self {
         JsoncValue::Number(Number::Float(value))
     }
 }
-impl<I> From<f64> for JsoncValue<I, f64> {
+impl<I, M: MapBackend> From<f64> for JsoncValue<I, f64, M> {
     fn from(value: f64) -> Self {
         JsoncValue::Number(Number::Float(value))
     }
@@ -1,23 +1,52 @@
 use crate::error::IndexError;
 
-use super::JsoncValue;
+use super::{JsoncMap, JsoncValue, MapBackend};
 
 pub trait JsoncIndex<T>: Sized {
     type Indexer: JsoncIndexer<Self, T>;
 }
 
-impl<I, F, In: JsoncIndex<JsoncValue<I, F>>> std::ops::Index<In> for JsoncValue<I, F> {
-    type Output = <In::Indexer as JsoncIndexer<In, JsoncValue<I, F>>>::Output;
+/// Index access never panics, missing keys, out of range indices and unmatched types return `Null`
+/// (or empty slice for range index).
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, Value};
+/// let value = jsonc!({"name": "json-with-comments", "keywords": ["JSONC"]});
+/// assert_eq!(value["keywords"][0], "JSONC".into());
+/// assert_eq!(value["version"], Value::Null);
+/// assert_eq!(value["keywords"][100], Value::Null);
+/// assert_eq!(value["name"]["first"][0], Value::Null);
+/// assert_eq!(value["keywords"][1..], []);
+/// ```
+impl<I, F, M: MapBackend, In: JsoncIndex<JsoncValue<I, F, M>>> std::ops::Index<In> for JsoncValue<I, F, M> {
+    type Output = <In::Indexer as JsoncIndexer<In, JsoncValue<I, F, M>>>::Output;
     fn index(&self, index: In) -> &Self::Output {
         In::Indexer::index(self, index)
     }
 }
-impl<I, F, In: JsoncIndex<JsoncValue<I, F>>> std::ops::IndexMut<In> for JsoncValue<I, F> {
+/// Mutable index access creates intermediate values as `serde_json` does.
+/// - `Null` indexed by string becomes an empty object, and a missing key is inserted with `Null`.
+/// - `Null` indexed by usize becomes an empty array, and index equal to its length appends `Null`.
+///
+/// # Panics
+/// Panics if the value is neither `Null` nor the indexable type, or usize index is greater than the length.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, Value};
+/// let mut value = Value::Null;
+/// value["server"]["hosts"][0] = "localhost".into();
+/// value["server"]["hosts"][1] = "127.0.0.1".into();
+/// value["server"]["port"] = 8080.into();
+/// assert_eq!(value, jsonc!({"server": {"hosts": ["localhost", "127.0.0.1"], "port": 8080}}));
+/// ```
+impl<I, F, M: MapBackend, In: JsoncIndex<JsoncValue<I, F, M>>> std::ops::IndexMut<In> for JsoncValue<I, F, M> {
     fn index_mut(&mut self, index: In) -> &mut Self::Output {
         In::Indexer::index_mut(self, index)
     }
 }
-impl<I, F> JsoncValue<I, F> {
+impl<I, F, M: MapBackend> JsoncValue<I, F, M> {
     /// Returns a reference to the value at the given index.
     /// - if the value is an array, returns the element at the given index, or `None` if the index is out of bounds.
     /// - if the value is an object, returns the value associated with the given key, or `None` if the key is not found.
@@ -43,10 +72,7 @@ impl<I, F> JsoncValue<I, F> {
     /// assert_eq!(value["array"][1].get(0), None);
     /// assert_eq!(value["object"]["key"].get(""), None);
     /// ```
-    pub fn get<In: JsoncIndex<Self>>(
-        &self,
-        index: In,
-    ) -> Option<&<In::Indexer as JsoncIndexer<In, JsoncValue<I, F>>>::Output> {
+    pub fn get<In: JsoncIndex<Self>>(&self, index: In) -> Option<&<In::Indexer as JsoncIndexer<In, Self>>::Output> {
         In::Indexer::get(self, index)
     }
 
@@ -81,9 +107,29 @@ impl<I, F> JsoncValue<I, F> {
     pub fn get_mut<In: JsoncIndex<Self>>(
         &mut self,
         index: In,
-    ) -> Option<&mut <In::Indexer as JsoncIndexer<In, JsoncValue<I, F>>>::Output> {
+    ) -> Option<&mut <In::Indexer as JsoncIndexer<In, Self>>::Output> {
         In::Indexer::get_mut(self, index)
     }
+
+    /// Returns the entry of the object for in-place manipulation, `Null` becomes an empty object.
+    /// Returns `None` if the value is neither an object nor `Null`.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, Value};
+    /// let mut value = Value::Null;
+    /// for word in ["a", "b", "a"] {
+    ///     *value.entry(word).unwrap().or_insert(0.into()).as_integer_mut().unwrap() += 1;
+    /// }
+    /// assert_eq!(value, jsonc!({"a": 2, "b": 1}));
+    /// assert!(value["a"].entry("c").is_none());
+    /// ```
+    pub fn entry(&mut self, key: impl Into<String>) -> Option<<M::Map<Self> as JsoncMap<Self>>::Entry<'_>> {
+        if self.is_null() {
+            *self = JsoncValue::Object(Default::default());
+        }
+        self.as_map_mut().map(|map| map.entry(key.into()))
+    }
 }
 
 pub trait JsoncIndexer<T, V>
@@ -98,89 +144,114 @@ where
 }
 
 pub enum StringIndexer {}
-impl<'a, I, F> JsoncIndexer<&'a str, JsoncValue<I, F>> for StringIndexer {
-    type Output = JsoncValue<I, F>;
+impl<'a, I, F, M: MapBackend> JsoncIndexer<&'a str, JsoncValue<I, F, M>> for StringIndexer {
+    type Output = JsoncValue<I, F, M>;
 
-    fn get<'b>(value: &'b JsoncValue<I, F>, index: &'a str) -> Option<&'b Self::Output> {
+    fn get<'b>(value: &'b JsoncValue<I, F, M>, index: &'a str) -> Option<&'b Self::Output> {
         value.as_map().and_then(|map| map.get(index))
     }
 
-    fn get_mut<'b>(value: &'b mut JsoncValue<I, F>, index: &'a str) -> Option<&'b mut Self::Output> {
+    fn get_mut<'b>(value: &'b mut JsoncValue<I, F, M>, index: &'a str) -> Option<&'b mut Self::Output> {
         value.as_map_mut().and_then(|map| map.get_mut(index))
     }
 
-    fn index<'b>(value: &'b JsoncValue<I, F>, index: &'a str) -> &'b Self::Output {
-        &value.as_map().unwrap_or_else(|| panic!("{}", IndexError::StringIndex { value: value.value_type() }))[index]
+    fn index<'b>(value: &'b JsoncValue<I, F, M>, index: &'a str) -> &'b Self::Output {
+        Self::get(value, index).unwrap_or(&const { JsoncValue::Null })
+    }
+
+    fn index_mut<'b>(value: &'b mut JsoncValue<I, F, M>, index: &'a str) -> &'b mut Self::Output {
+        if value.is_null() {
+            *value = JsoncValue::Object(Default::default());
+        }
+        match value {
+            JsoncValue::Object(map) => map.get_or_insert_with(index.to_string(), || JsoncValue::Null),
+            _ => panic!("{}", IndexError::StringIndex { value: value.value_type() }),
+        }
     }
+}
+
+pub enum UsizeIndexer {}
+impl<I, F, M: MapBackend> JsoncIndexer<usize, JsoncValue<I, F, M>> for UsizeIndexer {
+    type Output = JsoncValue<I, F, M>;
 
-    fn index_mut<'b>(value: &'b mut JsoncValue<I, F>, index: &'a str) -> &'b mut Self::Output {
-        //  `IndexMut` is not implemented for `std::collections::HashMap`
+    fn get(value: &JsoncValue<I, F, M>, index: usize) -> Option<&Self::Output> {
+        value.as_vec().and_then(|v| v.get(index))
+    }
+
+    fn get_mut(value: &mut JsoncValue<I, F, M>, index: usize) -> Option<&mut Self::Output> {
+        value.as_vec_mut().and_then(|v| v.get_mut(index))
+    }
 
-        // cannot borrow `*value` as immutable because it is also borrowed as mutable
-        // value
-        //     .as_object_mut()
-        //     .unwrap_or_else(|| panic!("{}", IndexError::StringIndex { value: value.value_type() }))
-        //     .get_mut(index)
-        //     .unwrap_or_else(|| panic!("{}", IndexError::NotExistKey { key: index.to_string() }))
+    fn index(value: &JsoncValue<I, F, M>, index: usize) -> &Self::Output {
+        Self::get(value, index).unwrap_or(&const { JsoncValue::Null })
+    }
+
+    fn index_mut(value: &mut JsoncValue<I, F, M>, index: usize) -> &mut Self::Output {
+        if value.is_null() {
+            *value = JsoncValue::Array(Vec::new());
+        }
         match value {
-            JsoncValue::Object(map) => {
-                map.get_mut(index).unwrap_or_else(|| panic!("{}", IndexError::NotExistKey { key: index.to_string() }))
+            JsoncValue::Array(v) => {
+                if index == v.len() {
+                    v.push(JsoncValue::Null);
+                }
+                let len = v.len();
+                v.get_mut(index).unwrap_or_else(|| panic!("{}", IndexError::OutOfRange { index, len }))
             }
-            _ => panic!("{}", IndexError::StringIndex { value: value.value_type() }),
+            _ => panic!("{}", IndexError::UsizeIndex { value: value.value_type() }),
         }
     }
 }
 
 pub enum SliceIndexer {}
-impl<I, F, S: std::slice::SliceIndex<[JsoncValue<I, F>]> + JsoncIndex<JsoncValue<I, F>>>
-    JsoncIndexer<S, JsoncValue<I, F>> for SliceIndexer
+impl<I, F, M: MapBackend, S> JsoncIndexer<S, JsoncValue<I, F, M>> for SliceIndexer
+where
+    S: std::slice::SliceIndex<[JsoncValue<I, F, M>], Output = [JsoncValue<I, F, M>]> + JsoncIndex<JsoncValue<I, F, M>>,
 {
-    type Output = <S as std::slice::SliceIndex<[JsoncValue<I, F>]>>::Output;
+    type Output = [JsoncValue<I, F, M>];
 
-    fn get(value: &JsoncValue<I, F>, index: S) -> Option<&Self::Output> {
+    fn get(value: &JsoncValue<I, F, M>, index: S) -> Option<&Self::Output> {
         value.as_vec().and_then(|v| v.get(index))
     }
 
-    fn get_mut(value: &mut JsoncValue<I, F>, index: S) -> Option<&mut Self::Output> {
+    fn get_mut(value: &mut JsoncValue<I, F, M>, index: S) -> Option<&mut Self::Output> {
         value.as_vec_mut().and_then(|v| v.get_mut(index))
     }
 
-    fn index(value: &JsoncValue<I, F>, index: S) -> &Self::Output {
-        &value.as_vec().unwrap_or_else(|| panic!("{}", IndexError::StringIndex { value: value.value_type() }))[index]
+    fn index(value: &JsoncValue<I, F, M>, index: S) -> &Self::Output {
+        Self::get(value, index).unwrap_or(&[])
     }
 
-    fn index_mut(value: &mut JsoncValue<I, F>, index: S) -> &mut Self::Output {
-        // cannot borrow `*value` as immutable because it is also borrowed as mutable
-        // &mut value.as_array_mut().unwrap_or_else(|| panic!("{}", IndexError::StringIndex { value: value.value_type() }))[index]
+    fn index_mut(value: &mut JsoncValue<I, F, M>, index: S) -> &mut Self::Output {
         match value {
             JsoncValue::Array(v) => &mut v[index],
-            _ => panic!("{}", IndexError::StringIndex { value: value.value_type() }),
+            _ => panic!("{}", IndexError::SliceIndex { value: value.value_type() }),
         }
     }
 }
 
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for &str {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for &str {
     type Indexer = StringIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for usize {
-    type Indexer = SliceIndexer;
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for usize {
+    type Indexer = UsizeIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::Range<usize> {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::Range<usize> {
     type Indexer = SliceIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::RangeFrom<usize> {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::RangeFrom<usize> {
     type Indexer = SliceIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::RangeFull {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::RangeFull {
     type Indexer = SliceIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::RangeInclusive<usize> {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::RangeInclusive<usize> {
     type Indexer = SliceIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::RangeTo<usize> {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::RangeTo<usize> {
     type Indexer = SliceIndexer;
 }
-impl<I, F> JsoncIndex<JsoncValue<I, F>> for std::ops::RangeToInclusive<usize> {
+impl<I, F, M: MapBackend> JsoncIndex<JsoncValue<I, F, M>> for std::ops::RangeToInclusive<usize> {
     type Indexer = SliceIndexer;
 }
 
@@ -339,16 +410,39 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_index_unmatched_type() {
         let value: JsoncValue<u64, f64> = jsonc_generics!({"version": 1});
-        _ = value[1];
+        assert_eq!(value[1], JsoncValue::Null);
+        assert_eq!(value[1..], []);
     }
 
     #[test]
-    #[should_panic]
     fn test_index_number_by_number() {
         let value: JsoncValue<u64, f64> = jsonc_generics!({"version": 1});
-        _ = value["version"][3];
+        assert_eq!(value["version"][3], JsoncValue::Null);
+        assert_eq!(value["version"]["major"], JsoncValue::Null);
+    }
+
+    #[test]
+    fn test_index_mut_auto_vivification() {
+        let mut value: JsoncValue<u64, f64> = JsoncValue::Null;
+        value["a"]["b"] = 1.into();
+        value["a"]["c"][0]["d"] = true.into();
+        value["a"]["c"][1] = ().into();
+        assert_eq!(value, jsonc_generics!({"a": {"b": 1, "c": [{"d": true}, null]}}));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_unmatched_type() {
+        let mut value: JsoncValue<u64, f64> = jsonc_generics!({"version": 1});
+        value["version"]["major"] = 1.into();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_mut_out_of_range() {
+        let mut value: JsoncValue<u64, f64> = jsonc_generics!([1]);
+        value[2] = 3.into();
     }
 }
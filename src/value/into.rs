@@ -1,8 +1,15 @@
 use crate::error::InvalidRepresentsValue;
 
-use super::{number::Number, JsoncValue, MapImpl};
+use std::collections::{BTreeMap, HashMap};
 
-impl<I, F> JsoncValue<I, F> {
+use super::{
+    de::number::{Converted, Converter, LosslessConverter},
+    map::{BTreeMapBackend, HashMapBackend},
+    number::Number,
+    JsoncValue, MapBackend,
+};
+
+impl<I, F, M: MapBackend> JsoncValue<I, F, M> {
     /// Returns true if the `Value` is an `Object`. Returns false otherwise.
     ///
     /// # Examples
@@ -32,7 +39,7 @@ impl<I, F> JsoncValue<I, F> {
     /// assert_eq!(jsonc!(123).as_map(), None);
     /// assert_eq!(jsonc!(123.45).as_map(), None);
     /// ```
-    pub fn as_map(&self) -> Option<&MapImpl<String, Self>> {
+    pub fn as_map(&self) -> Option<&M::Map<Self>> {
         match self {
             JsoncValue::Object(m) => Some(m),
             _ => None,
@@ -48,7 +55,7 @@ impl<I, F> JsoncValue<I, F> {
     /// map.insert("new_key".to_string(), "new_value".into());
     /// assert_eq!(object, jsonc!({"key": "value", "new_key": "new_value"}));
     /// ```
-    pub fn as_map_mut(&mut self) -> Option<&mut MapImpl<String, Self>> {
+    pub fn as_map_mut(&mut self) -> Option<&mut M::Map<Self>> {
         match self {
             JsoncValue::Object(m) => Some(m),
             _ => None,
@@ -404,54 +411,75 @@ impl<I, F> JsoncValue<I, F> {
     }
 }
 
-impl<I, F> TryFrom<JsoncValue<I, F>> for MapImpl<String, JsoncValue<I, F>> {
+impl<I, F> TryFrom<JsoncValue<I, F, HashMapBackend>> for HashMap<String, JsoncValue<I, F, HashMapBackend>> {
+    type Error = crate::Error;
+    fn try_from(value: JsoncValue<I, F, HashMapBackend>) -> Result<Self, Self::Error> {
+        match value {
+            JsoncValue::Object(m) => Ok(m),
+            _ => Err(InvalidRepresentsValue::ShouldObject)?,
+        }
+    }
+}
+impl<I, F> TryFrom<JsoncValue<I, F, BTreeMapBackend>> for BTreeMap<String, JsoncValue<I, F, BTreeMapBackend>> {
+    type Error = crate::Error;
+    fn try_from(value: JsoncValue<I, F, BTreeMapBackend>) -> Result<Self, Self::Error> {
+        match value {
+            JsoncValue::Object(m) => Ok(m),
+            _ => Err(InvalidRepresentsValue::ShouldObject)?,
+        }
+    }
+}
+#[cfg(feature = "indexmap")]
+impl<I, F> TryFrom<JsoncValue<I, F, super::map::IndexMapBackend>>
+    for indexmap::IndexMap<String, JsoncValue<I, F, super::map::IndexMapBackend>>
+{
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, super::map::IndexMapBackend>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::Object(m) => Ok(m),
             _ => Err(InvalidRepresentsValue::ShouldObject)?,
         }
     }
 }
-impl<I, F> TryFrom<JsoncValue<I, F>> for Vec<JsoncValue<I, F>> {
+impl<I, F, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for Vec<JsoncValue<I, F, M>> {
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::Array(v) => Ok(v),
             _ => Err(InvalidRepresentsValue::ShouldArray)?,
         }
     }
 }
-impl<I, F> TryFrom<JsoncValue<I, F>> for bool {
+impl<I, F, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for bool {
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::Bool(v) => Ok(v),
             _ => Err(InvalidRepresentsValue::ShouldBool)?,
         }
     }
 }
-impl<I, F> TryFrom<JsoncValue<I, F>> for () {
+impl<I, F, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for () {
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::Null => Ok(()),
             _ => Err(InvalidRepresentsValue::ShouldNull)?,
         }
     }
 }
-impl<I, F> TryFrom<JsoncValue<I, F>> for String {
+impl<I, F, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for String {
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::String(v) => Ok(v),
             _ => Err(InvalidRepresentsValue::ShouldString)?,
         }
     }
 }
-impl<I, F> TryFrom<JsoncValue<I, F>> for Number<I, F> {
+impl<I, F, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for Number<I, F> {
     type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+    fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
         match value {
             JsoncValue::Number(v) => Ok(v),
             _ => Err(InvalidRepresentsValue::ShouldNumber)?,
@@ -459,18 +487,18 @@ impl<I, F> TryFrom<JsoncValue<I, F>> for Number<I, F> {
     }
 }
 // TODO implementing a foreign trait is only possible if at least one of the types for which it is implemented is local
-// impl<I, F> TryFrom<JsoncValue<I, F>> for I {
+// impl<I, F> TryFrom<JsoncValue<I, F, M>> for I {
 //     type Error = crate::Error;
-//     fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+//     fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
 //         match value {
 //             JsoncValue::Number(Number::Integer(v)) => Ok(v),
 //             _ => Err(InvalidRepresentsValue::ShouldNumber)?,
 //         }
 //     }
 // }
-// impl<I, F> TryFrom<JsoncValue<I, F>> for F {
+// impl<I, F> TryFrom<JsoncValue<I, F, M>> for F {
 //     type Error = crate::Error;
-//     fn try_from(value: JsoncValue<I, F>) -> Result<Self, Self::Error> {
+//     fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
 //         match value {
 //             JsoncValue::Number(Number::Float(v)) => Ok(v),
 //             _ => Err(InvalidRepresentsValue::ShouldNumber)?,
@@ -478,119 +506,76 @@ impl<I, F> TryFrom<JsoncValue<I, F>> for Number<I, F> {
 //     }
 // }
 
-// TODO macro?
-impl<F> TryFrom<JsoncValue<u8, F>> for u8 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<u8, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<u16, F>> for u16 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<u16, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<u32, F>> for u32 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<u32, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<u64, F>> for u64 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<u64, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<u128, F>> for u128 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<u128, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<i8, F>> for i8 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<i8, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<i16, F>> for i16 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<i16, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<i32, F>> for i32 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<i32, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<i64, F>> for i64 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<i64, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
-}
-impl<F> TryFrom<JsoncValue<i128, F>> for i128 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<i128, F>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Integer(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
+macro_rules! impl_try_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl<I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> TryFrom<JsoncValue<I, F, M>> for $ty {
+                type Error = crate::Error;
+                fn try_from(value: JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
+                    value.as_primitive()
+                }
+            }
+            impl<I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> TryFrom<&JsoncValue<I, F, M>> for $ty {
+                type Error = crate::Error;
+                fn try_from(value: &JsoncValue<I, F, M>) -> Result<Self, Self::Error> {
+                    value.as_primitive()
+                }
+            }
+        )*
+    };
 }
-impl<I> TryFrom<JsoncValue<I, f32>> for f32 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, f32>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Float(v)) => Ok(v),
-            _ => Err(InvalidRepresentsValue::ShouldNumber)?,
-        }
-    }
+impl_try_from_number!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+macro_rules! as_number_accessors {
+    ($($name:ident -> $ty:ty),*) => {
+        $(
+            #[doc = concat!("Converts the number into `", stringify!($ty), "`, see [`JsoncValue::as_primitive`].")]
+            pub fn $name(&self) -> crate::Result<$ty> {
+                self.as_primitive()
+            }
+        )*
+    };
 }
-impl<I> TryFrom<JsoncValue<I, f64>> for f64 {
-    type Error = crate::Error;
-    fn try_from(value: JsoncValue<I, f64>) -> Result<Self, Self::Error> {
-        match value {
-            JsoncValue::Number(Number::Float(v)) => Ok(v),
+impl<I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> JsoncValue<I, F, M> {
+    /// Converts the number into any primitive number type, whether the value is integer or float.
+    /// Returns `ConvertError` if the number is out of range of the type or cannot be represented exactly,
+    /// and `InvalidRepresentsValue` if the value is not a number.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, Value};
+    /// assert_eq!(jsonc!(255).as_primitive::<u8>().unwrap(), 255);
+    /// assert!(jsonc!(256).as_primitive::<u8>().is_err());
+    /// assert!(jsonc!(-1).as_u64().is_err());
+    ///
+    /// assert_eq!(jsonc!(3.0).as_i32().unwrap(), 3);
+    /// assert!(jsonc!(3.5).as_i32().is_err());
+    /// assert_eq!(jsonc!(3).as_f64().unwrap(), 3.0);
+    /// assert!(jsonc!(9007199254740993_i64).as_f64().is_err());
+    /// assert_eq!(jsonc!(0.5).as_f32().unwrap(), 0.5);
+    /// assert!(jsonc!(0.1).as_f32().is_err());
+    ///
+    /// let port: u16 = jsonc!(8080).try_into().unwrap();
+    /// assert_eq!(port, 8080);
+    /// assert!(u16::try_from(&jsonc!("8080")).is_err());
+    /// ```
+    pub fn as_primitive<N: Converted<I, F>>(&self) -> crate::Result<N> {
+        match self {
+            JsoncValue::Number(n) => LosslessConverter::convert(n),
             _ => Err(InvalidRepresentsValue::ShouldNumber)?,
         }
     }
+
+    as_number_accessors!(
+        as_u8 -> u8, as_u16 -> u16, as_u32 -> u32, as_u64 -> u64, as_u128 -> u128, as_usize -> usize,
+        as_i8 -> i8, as_i16 -> i16, as_i32 -> i32, as_i64 -> i64, as_i128 -> i128, as_isize -> isize,
+        as_f32 -> f32, as_f64 -> f64
+    );
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Value;
+    use crate::{jsonc, value::MapImpl, Value};
 
     use super::*;
 
@@ -852,4 +837,51 @@ mod tests {
                 && v.as_float().is_some() // number && float
         )
     }
+
+    #[test]
+    fn test_value_as_primitive_integer() {
+        let v: Value = "300".parse().unwrap();
+        assert!(v.as_u8().is_err() && v.as_i8().is_err());
+        assert_eq!((v.as_u16().unwrap(), v.as_i16().unwrap(), v.as_usize().unwrap()), (300, 300, 300));
+        assert_eq!((v.as_u128().unwrap(), v.as_i128().unwrap(), v.as_f32().unwrap()), (300, 300, 300.0));
+
+        let v: Value = "-9223372036854775808".parse().unwrap();
+        assert_eq!(v.as_i64().unwrap(), i64::MIN);
+        assert_eq!(v.as_f64().unwrap(), -9223372036854775808.0);
+        assert!(v.as_u64().is_err() && v.as_i32().is_err() && v.as_isize().is_ok());
+
+        let v: Value = "9223372036854775807".parse().unwrap();
+        assert!(v.as_f64().is_err() && v.as_f32().is_err());
+        assert_eq!(u64::try_from(v).unwrap(), i64::MAX as u64);
+
+        let v: JsoncValue<u128, f32> = JsoncValue::Number(Number::Integer(u128::MAX));
+        assert_eq!(v.as_u128().unwrap(), u128::MAX);
+        assert!(v.as_i128().is_err() && v.as_u64().is_err());
+    }
+
+    #[test]
+    fn test_value_as_primitive_float() {
+        let v: Value = "-2.0".parse().unwrap();
+        assert_eq!((v.as_i8().unwrap(), v.as_i64().unwrap(), v.as_f32().unwrap()), (-2, -2, -2.0));
+        assert!(v.as_u8().is_err());
+
+        let v: Value = "1.25".parse().unwrap();
+        assert!(v.as_i64().is_err() && v.as_u64().is_err());
+        assert_eq!((v.as_f32().unwrap(), f64::try_from(&v).unwrap()), (1.25, 1.25));
+
+        let v: Value = "1e300".parse().unwrap();
+        assert!(v.as_f32().is_err() && v.as_i128().is_err());
+        assert_eq!(v.as_f64().unwrap(), 1e300);
+
+        assert!(Value::from(f64::NAN).as_f32().unwrap().is_nan());
+        assert!(Value::from(f64::NAN).as_i64().is_err());
+    }
+
+    #[test]
+    fn test_value_as_primitive_not_number() {
+        for v in [jsonc!(null), jsonc!("1"), jsonc!(true), jsonc!([1]), jsonc!({"n": 1})] {
+            assert!(v.as_i64().is_err() && v.as_f64().is_err());
+            assert!(i64::try_from(v).is_err());
+        }
+    }
 }
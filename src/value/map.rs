@@ -0,0 +1,371 @@
+use std::{
+    borrow::Cow,
+    collections::{btree_map, hash_map, BTreeMap, HashMap},
+};
+
+/// Backend of [`super::JsoncValue::Object`], that decides the map type and the order of object entries.
+/// - [`HashMapBackend`]: arbitrary order, default if `preserve_order` feature is disabled.
+/// - [`IndexMapBackend`]: insertion order, default if `preserve_order` feature is enabled.
+/// - [`BTreeMapBackend`]: sorted order by keys.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc_generics, value::{map::BTreeMapBackend, JsoncValue}};
+/// let value: JsoncValue<i64, f64, BTreeMapBackend> = jsonc_generics!({"c": 3, "a": 1, "b": 2});
+/// let keys: Vec<_> = value.as_map().unwrap().keys().cloned().collect();
+/// assert_eq!(keys, vec!["a", "b", "c"]);
+/// assert_eq!(json_with_comments::to_string(&value).unwrap(), r#"{"a":1,"b":2,"c":3}"#);
+/// ```
+pub trait MapBackend {
+    type Map<V>: JsoncMap<V>;
+    /// Map of [`super::BorrowedValue::Object`] with the same order as [`MapBackend::Map`], keys may borrow the source text.
+    type BorrowedMap<'a, V>: BorrowedMap<'a, V>;
+}
+
+/// Default map backend, [`HashMapBackend`] or [`IndexMapBackend`] by `preserve_order` feature.
+#[cfg(not(feature = "preserve_order"))]
+pub type DefaultMap = HashMapBackend;
+/// Default map backend, [`HashMapBackend`] or [`IndexMapBackend`] by `preserve_order` feature.
+#[cfg(feature = "preserve_order")]
+pub type DefaultMap = IndexMapBackend;
+
+/// Map with `String` keys, that can be used as object of [`super::JsoncValue`].
+pub trait JsoncMap<V>: Default + FromIterator<(String, V)> + IntoIterator<Item = (String, V)> {
+    type Iter<'a>: Iterator<Item = (&'a String, &'a V)>
+    where
+        Self: 'a,
+        V: 'a;
+    type IterMut<'a>: Iterator<Item = (&'a String, &'a mut V)>
+    where
+        Self: 'a,
+        V: 'a;
+    type Entry<'a>
+    where
+        Self: 'a,
+        V: 'a;
+
+    fn with_capacity(capacity: usize) -> Self;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn get(&self, key: &str) -> Option<&V>;
+    fn get_mut(&mut self, key: &str) -> Option<&mut V>;
+    fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+    fn insert(&mut self, key: String, value: V) -> Option<V>;
+    fn get_or_insert_with<D: FnOnce() -> V>(&mut self, key: String, default: D) -> &mut V;
+    /// Removes the entry while keeping the order of the other entries.
+    fn remove(&mut self, key: &str) -> Option<V>;
+    fn retain<P: FnMut(&String, &mut V) -> bool>(&mut self, predicate: P);
+    fn iter(&self) -> Self::Iter<'_>;
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+    fn entry(&mut self, key: String) -> Self::Entry<'_>;
+}
+
+/// Map with `Cow<str>` keys, that can be used as object of [`super::BorrowedValue`].
+pub trait BorrowedMap<'a, V>:
+    Default + FromIterator<(Cow<'a, str>, V)> + IntoIterator<Item = (Cow<'a, str>, V)>
+{
+    type Iter<'b>: Iterator<Item = (&'b Cow<'a, str>, &'b V)>
+    where
+        'a: 'b,
+        Self: 'b,
+        V: 'b;
+
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn get(&self, key: &str) -> Option<&V>;
+    fn get_key_value(&self, key: &str) -> Option<(&Cow<'a, str>, &V)>;
+    fn insert(&mut self, key: Cow<'a, str>, value: V) -> Option<V>;
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+/// Object backend of [`std::collections::HashMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HashMapBackend;
+impl MapBackend for HashMapBackend {
+    type Map<V> = HashMap<String, V>;
+    type BorrowedMap<'a, V> = HashMap<Cow<'a, str>, V>;
+}
+impl<V> JsoncMap<V> for HashMap<String, V> {
+    type Iter<'a>
+        = hash_map::Iter<'a, String, V>
+    where
+        V: 'a;
+    type IterMut<'a>
+        = hash_map::IterMut<'a, String, V>
+    where
+        V: 'a;
+    type Entry<'a>
+        = hash_map::Entry<'a, String, V>
+    where
+        V: 'a;
+
+    fn with_capacity(capacity: usize) -> Self {
+        HashMap::with_capacity(capacity)
+    }
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+    fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        HashMap::get_mut(self, key)
+    }
+    fn insert(&mut self, key: String, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+    fn get_or_insert_with<D: FnOnce() -> V>(&mut self, key: String, default: D) -> &mut V {
+        HashMap::entry(self, key).or_insert_with(default)
+    }
+    fn remove(&mut self, key: &str) -> Option<V> {
+        HashMap::remove(self, key)
+    }
+    fn retain<P: FnMut(&String, &mut V) -> bool>(&mut self, predicate: P) {
+        HashMap::retain(self, predicate)
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        HashMap::iter(self)
+    }
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        HashMap::iter_mut(self)
+    }
+    fn entry(&mut self, key: String) -> Self::Entry<'_> {
+        HashMap::entry(self, key)
+    }
+}
+
+impl<'a, V> BorrowedMap<'a, V> for HashMap<Cow<'a, str>, V> {
+    type Iter<'b>
+        = hash_map::Iter<'b, Cow<'a, str>, V>
+    where
+        'a: 'b,
+        V: 'b;
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+    fn get_key_value(&self, key: &str) -> Option<(&Cow<'a, str>, &V)> {
+        HashMap::get_key_value(self, key)
+    }
+    fn insert(&mut self, key: Cow<'a, str>, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        HashMap::iter(self)
+    }
+}
+
+/// Object backend of [`std::collections::BTreeMap`], entries are sorted by keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BTreeMapBackend;
+impl MapBackend for BTreeMapBackend {
+    type Map<V> = BTreeMap<String, V>;
+    type BorrowedMap<'a, V> = BTreeMap<Cow<'a, str>, V>;
+}
+impl<V> JsoncMap<V> for BTreeMap<String, V> {
+    type Iter<'a>
+        = btree_map::Iter<'a, String, V>
+    where
+        V: 'a;
+    type IterMut<'a>
+        = btree_map::IterMut<'a, String, V>
+    where
+        V: 'a;
+    type Entry<'a>
+        = btree_map::Entry<'a, String, V>
+    where
+        V: 'a;
+
+    fn with_capacity(_capacity: usize) -> Self {
+        BTreeMap::new()
+    }
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+    fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        BTreeMap::get_mut(self, key)
+    }
+    fn insert(&mut self, key: String, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+    fn get_or_insert_with<D: FnOnce() -> V>(&mut self, key: String, default: D) -> &mut V {
+        BTreeMap::entry(self, key).or_insert_with(default)
+    }
+    fn remove(&mut self, key: &str) -> Option<V> {
+        BTreeMap::remove(self, key)
+    }
+    fn retain<P: FnMut(&String, &mut V) -> bool>(&mut self, predicate: P) {
+        BTreeMap::retain(self, predicate)
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        BTreeMap::iter(self)
+    }
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        BTreeMap::iter_mut(self)
+    }
+    fn entry(&mut self, key: String) -> Self::Entry<'_> {
+        BTreeMap::entry(self, key)
+    }
+}
+
+impl<'a, V> BorrowedMap<'a, V> for BTreeMap<Cow<'a, str>, V> {
+    type Iter<'b>
+        = btree_map::Iter<'b, Cow<'a, str>, V>
+    where
+        'a: 'b,
+        V: 'b;
+
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+    fn get_key_value(&self, key: &str) -> Option<(&Cow<'a, str>, &V)> {
+        BTreeMap::get_key_value(self, key)
+    }
+    fn insert(&mut self, key: Cow<'a, str>, value: V) -> Option<V> {
+        BTreeMap::insert(self, key, value)
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        BTreeMap::iter(self)
+    }
+}
+
+/// Object backend of [`indexmap::IndexMap`], entries are kept in insertion order.
+#[cfg(feature = "indexmap")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IndexMapBackend;
+#[cfg(feature = "indexmap")]
+impl MapBackend for IndexMapBackend {
+    type Map<V> = indexmap::IndexMap<String, V>;
+    type BorrowedMap<'a, V> = indexmap::IndexMap<Cow<'a, str>, V>;
+}
+#[cfg(feature = "indexmap")]
+impl<V> JsoncMap<V> for indexmap::IndexMap<String, V> {
+    type Iter<'a>
+        = indexmap::map::Iter<'a, String, V>
+    where
+        V: 'a;
+    type IterMut<'a>
+        = indexmap::map::IterMut<'a, String, V>
+    where
+        V: 'a;
+    type Entry<'a>
+        = indexmap::map::Entry<'a, String, V>
+    where
+        V: 'a;
+
+    fn with_capacity(capacity: usize) -> Self {
+        indexmap::IndexMap::with_capacity(capacity)
+    }
+    fn len(&self) -> usize {
+        indexmap::IndexMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        indexmap::IndexMap::get(self, key)
+    }
+    fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        indexmap::IndexMap::get_mut(self, key)
+    }
+    fn insert(&mut self, key: String, value: V) -> Option<V> {
+        indexmap::IndexMap::insert(self, key, value)
+    }
+    fn get_or_insert_with<D: FnOnce() -> V>(&mut self, key: String, default: D) -> &mut V {
+        indexmap::IndexMap::entry(self, key).or_insert_with(default)
+    }
+    fn remove(&mut self, key: &str) -> Option<V> {
+        indexmap::IndexMap::shift_remove(self, key)
+    }
+    fn retain<P: FnMut(&String, &mut V) -> bool>(&mut self, mut predicate: P) {
+        indexmap::IndexMap::retain(self, |k, v| predicate(k, v))
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        indexmap::IndexMap::iter(self)
+    }
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        indexmap::IndexMap::iter_mut(self)
+    }
+    fn entry(&mut self, key: String) -> Self::Entry<'_> {
+        indexmap::IndexMap::entry(self, key)
+    }
+}
+
+#[cfg(feature = "indexmap")]
+impl<'a, V> BorrowedMap<'a, V> for indexmap::IndexMap<Cow<'a, str>, V> {
+    type Iter<'b>
+        = indexmap::map::Iter<'b, Cow<'a, str>, V>
+    where
+        'a: 'b,
+        V: 'b;
+
+    fn len(&self) -> usize {
+        indexmap::IndexMap::len(self)
+    }
+    fn get(&self, key: &str) -> Option<&V> {
+        indexmap::IndexMap::get(self, key)
+    }
+    fn get_key_value(&self, key: &str) -> Option<(&Cow<'a, str>, &V)> {
+        indexmap::IndexMap::get_key_value(self, key)
+    }
+    fn insert(&mut self, key: Cow<'a, str>, value: V) -> Option<V> {
+        indexmap::IndexMap::insert(self, key, value)
+    }
+    fn iter(&self) -> Self::Iter<'_> {
+        indexmap::IndexMap::iter(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jsonc_generics, value::JsoncValue};
+
+    #[test]
+    fn test_btree_map_backend() {
+        let src = r#"{"b": {"y": 2, "x": 1}, "a": [true, null], "c": "str"}"#;
+        let mut value: JsoncValue<i64, f64, BTreeMapBackend> = crate::from_str(src).unwrap();
+        assert_eq!(value, jsonc_generics!({"a": [true, null], "b": {"x": 1, "y": 2}, "c": "str"}));
+        assert_eq!(crate::to_string(&value).unwrap(), r#"{"a":[true,null],"b":{"x":1,"y":2},"c":"str"}"#);
+
+        value["b"]["w"] = JsoncValue::Bool(false);
+        value["d"]["e"] = JsoncValue::Null;
+        assert_eq!(value.as_map().unwrap().keys().collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+        assert_eq!(value["b"].as_map().unwrap().keys().collect::<Vec<_>>(), ["w", "x", "y"]);
+        assert_eq!(value.as_map_mut().unwrap().remove("b"), Some(jsonc_generics!({"w": false, "x": 1, "y": 2})));
+    }
+
+    #[test]
+    fn test_map_backend_entry() {
+        let mut value: JsoncValue<i64, f64, BTreeMapBackend> = JsoncValue::Null;
+        value.entry("count").unwrap().or_insert(jsonc_generics!(0));
+        *value.entry("count").unwrap().or_insert(jsonc_generics!(0)) = jsonc_generics!(1);
+        assert_eq!(value, jsonc_generics!({"count": 1}));
+
+        let mut array: JsoncValue<i64, f64, BTreeMapBackend> = jsonc_generics!([1]);
+        assert!(array.entry("key").is_none());
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_index_map_backend() {
+        let src = r#"{"c": 3, "a": 1, "b": 2}"#;
+        let mut value: JsoncValue<i64, f64, IndexMapBackend> = crate::from_str(src).unwrap();
+        assert_eq!(crate::to_string(&value).unwrap(), r#"{"c":3,"a":1,"b":2}"#);
+        JsoncMap::remove(value.as_map_mut().unwrap(), "c");
+        value["d"] = jsonc_generics!(4);
+        assert_eq!(crate::to_string(&value).unwrap(), r#"{"a":1,"b":2,"d":4}"#);
+    }
+}
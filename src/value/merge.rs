@@ -0,0 +1,344 @@
+use std::fmt;
+
+use crate::error::MergeError;
+
+use super::{pointer::to_pointer, DefaultMap, JsoncMap, JsoncValue, MapBackend};
+
+impl<I: Clone + PartialEq, F: Clone + PartialEq, M: MapBackend> JsoncValue<I, F, M> {
+    /// Apply [RFC 7386](https://www.rfc-editor.org/rfc/rfc7386) JSON Merge Patch.
+    /// Objects are merged recursively, `null` in the patch removes the key, and other values replace the target.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({
+    ///     "title": "Goodbye!",
+    ///     "author": {"givenName": "John", "familyName": "Doe"},
+    ///     "tags": ["example", "sample"],
+    ///     "content": "This will be unchanged",
+    /// });
+    /// value.merge_patch(&jsonc!({
+    ///     "title": "Hello!",
+    ///     "phoneNumber": "+01-123-456-7890",
+    ///     "author": {"familyName": null},
+    ///     "tags": ["example"],
+    /// }));
+    /// assert_eq!(value, jsonc!({
+    ///     "title": "Hello!",
+    ///     "author": {"givenName": "John"},
+    ///     "tags": ["example"],
+    ///     "content": "This will be unchanged",
+    ///     "phoneNumber": "+01-123-456-7890",
+    /// }));
+    /// ```
+    pub fn merge_patch(&mut self, patch: &Self) {
+        let JsoncValue::Object(patch) = patch else {
+            return *self = patch.clone();
+        };
+        if !self.is_object() {
+            *self = JsoncValue::Object(Default::default());
+        }
+        if let JsoncValue::Object(map) = self {
+            for (key, value) in patch.iter() {
+                match value {
+                    JsoncValue::Null => {
+                        map.remove(key);
+                    }
+                    value => map.get_or_insert_with(key.clone(), || JsoncValue::Null).merge_patch(value),
+                }
+            }
+        }
+    }
+
+    /// Merge `other` into `self` recursively, that is for layered configuration such as defaults, environment
+    /// specific files and user overrides. Objects are always merged by keys, and how arrays are merged and how
+    /// conflicts are resolved can be configured by [`MergeOptions`].
+    /// The merge is applied atomically, if a conflict fails, the value is not changed at all.
+    ///
+    /// # Errors
+    /// Returns `Err` if the conflict callback returns [`Resolution::Fail`].
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, value::merge::{ArrayMerge, MergeOptions, Resolution}};
+    /// let mut config = jsonc!({
+    ///     "server": {"host": "localhost", "port": 80},
+    ///     "plugins": [{"name": "auth", "enabled": true}],
+    /// });
+    /// let overrides = jsonc!({
+    ///     "server": {"port": 8080},
+    ///     "plugins": [{"name": "auth", "enabled": false}, {"name": "cache", "enabled": true}],
+    /// });
+    /// let options = MergeOptions::new().array(ArrayMerge::ByKey("name".to_string()));
+    /// config.deep_merge(&overrides, options).unwrap();
+    /// assert_eq!(config, jsonc!({
+    ///     "server": {"host": "localhost", "port": 8080},
+    ///     "plugins": [{"name": "auth", "enabled": false}, {"name": "cache", "enabled": true}],
+    /// }));
+    ///
+    /// let mut conflicts = Vec::new();
+    /// let options = MergeOptions::new().on_conflict(|pointer, _, _| {
+    ///     conflicts.push(pointer.to_string());
+    ///     Resolution::Keep
+    /// });
+    /// config.deep_merge(&jsonc!({"server": {"port": 443, "tls": true}}), options).unwrap();
+    /// assert_eq!(config["server"], jsonc!({"host": "localhost", "port": 8080, "tls": true}));
+    /// assert_eq!(conflicts, vec!["/server/port"]);
+    /// ```
+    pub fn deep_merge(&mut self, other: &Self, mut options: MergeOptions<'_, I, F, M>) -> crate::Result<()> {
+        let mut merged = self.clone();
+        merged.deep_merge_recursive(&mut Vec::new(), other, &mut options)?;
+        *self = merged;
+        Ok(())
+    }
+
+    fn deep_merge_recursive(
+        &mut self,
+        path: &mut Vec<String>,
+        other: &Self,
+        options: &mut MergeOptions<'_, I, F, M>,
+    ) -> crate::Result<()> {
+        match (&mut *self, other) {
+            (JsoncValue::Object(base), JsoncValue::Object(other)) => {
+                for (key, value) in other.iter() {
+                    if options.null_removes && value.is_null() {
+                        base.remove(key);
+                        continue;
+                    }
+                    match base.get_mut(key) {
+                        Some(base) => {
+                            path.push(key.clone());
+                            base.deep_merge_recursive(path, value, options)?;
+                            path.pop();
+                        }
+                        None => {
+                            base.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+                Ok(())
+            }
+            (JsoncValue::Array(base), JsoncValue::Array(elements)) => match &options.array {
+                ArrayMerge::Replace if base == elements => Ok(()),
+                ArrayMerge::Replace => self.resolve(path, other, options),
+                ArrayMerge::Append => Ok(base.extend(elements.iter().cloned())),
+                ArrayMerge::ByIndex => {
+                    for (i, value) in elements.iter().enumerate() {
+                        match base.get_mut(i) {
+                            Some(base) => {
+                                path.push(i.to_string());
+                                base.deep_merge_recursive(path, value, options)?;
+                                path.pop();
+                            }
+                            None => base.push(value.clone()),
+                        }
+                    }
+                    Ok(())
+                }
+                ArrayMerge::ByKey(key) => {
+                    let key = key.clone();
+                    for value in elements {
+                        let position =
+                            value.get(&key[..]).and_then(|k| base.iter().position(|b| b.get(&key[..]) == Some(k)));
+                        match position {
+                            Some(i) => {
+                                path.push(i.to_string());
+                                base[i].deep_merge_recursive(path, value, options)?;
+                                path.pop();
+                            }
+                            None => base.push(value.clone()),
+                        }
+                    }
+                    Ok(())
+                }
+            },
+            (base, other) if *base == *other => Ok(()),
+            _ => self.resolve(path, other, options),
+        }
+    }
+
+    fn resolve(&mut self, path: &[String], other: &Self, options: &mut MergeOptions<'_, I, F, M>) -> crate::Result<()> {
+        let resolution = match &mut options.on_conflict {
+            Some(on_conflict) => on_conflict(&to_pointer(path), self, other),
+            None => Resolution::Overwrite,
+        };
+        match resolution {
+            Resolution::Overwrite => *self = other.clone(),
+            Resolution::Keep => {}
+            Resolution::Value(value) => *self = value,
+            Resolution::Fail => Err(MergeError::Unresolved { pointer: to_pointer(path) })?,
+        }
+        Ok(())
+    }
+}
+
+/// Options of [`JsoncValue::deep_merge`].
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, value::merge::{ArrayMerge, MergeOptions, Resolution}};
+/// let mut value = jsonc!({"tags": ["a"], "debug": true, "name": "base"});
+/// let options = MergeOptions::new()
+///     .array(ArrayMerge::Append)
+///     .null_removes(true)
+///     .on_conflict(|_, base, other| match (base, other) {
+///         (base, other) if base.is_string() && other.is_string() => Resolution::Value(
+///             format!("{}-{}", base.as_str().unwrap(), other.as_str().unwrap()).into()
+///         ),
+///         _ => Resolution::Fail,
+///     });
+/// value.deep_merge(&jsonc!({"tags": ["b"], "debug": null, "name": "override"}), options).unwrap();
+/// assert_eq!(value, jsonc!({"tags": ["a", "b"], "name": "base-override"}));
+/// ```
+pub struct MergeOptions<'a, I = i64, F = f64, M: MapBackend = DefaultMap> {
+    array: ArrayMerge,
+    null_removes: bool,
+    on_conflict: Option<Box<OnConflict<'a, I, F, M>>>,
+}
+
+/// Callback that receives JSON Pointer of the conflict, base value and other value.
+pub type OnConflict<'a, I, F, M> =
+    dyn FnMut(&str, &JsoncValue<I, F, M>, &JsoncValue<I, F, M>) -> Resolution<I, F, M> + 'a;
+
+/// How two arrays are merged by [`JsoncValue::deep_merge`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ArrayMerge {
+    /// Other array replaces base array, that is treated as a conflict.
+    #[default]
+    Replace,
+    /// Elements of other array are appended to base array.
+    Append,
+    /// Elements are merged recursively by index, extra elements of other array are appended.
+    ByIndex,
+    /// Object elements that have the same value of the key field are merged recursively, others are appended.
+    ByKey(String),
+}
+
+/// Resolution of a conflict, that is two different values at the same location that cannot be merged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolution<I = i64, F = f64, M: MapBackend = DefaultMap> {
+    /// Use the value of other, this is the default.
+    Overwrite,
+    /// Keep the value of base.
+    Keep,
+    /// Use the given value.
+    Value(JsoncValue<I, F, M>),
+    /// Abort the merge with error.
+    Fail,
+}
+
+impl<'a, I, F, M: MapBackend> MergeOptions<'a, I, F, M> {
+    pub fn new() -> Self {
+        Self { array: ArrayMerge::default(), null_removes: false, on_conflict: None }
+    }
+
+    pub fn array(self, array: ArrayMerge) -> Self {
+        Self { array, ..self }
+    }
+
+    /// If `true`, `null` in other removes the key as JSON Merge Patch, otherwise `null` is merged as a value.
+    pub fn null_removes(self, null_removes: bool) -> Self {
+        Self { null_removes, ..self }
+    }
+
+    pub fn on_conflict(
+        self,
+        on_conflict: impl FnMut(&str, &JsoncValue<I, F, M>, &JsoncValue<I, F, M>) -> Resolution<I, F, M> + 'a,
+    ) -> Self {
+        Self { on_conflict: Some(Box::new(on_conflict)), ..self }
+    }
+}
+impl<I, F, M: MapBackend> Default for MergeOptions<'_, I, F, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<I, F, M: MapBackend> fmt::Debug for MergeOptions<'_, I, F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MergeOptions")
+            .field("array", &self.array)
+            .field("null_removes", &self.null_removes)
+            .field("on_conflict", &self.on_conflict.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc, Value};
+
+    use super::*;
+
+    #[test]
+    fn test_merge_patch_rfc_examples() {
+        // RFC 7386 appendix A
+        let cases = [
+            (jsonc!({"a": "b"}), jsonc!({"a": "c"}), jsonc!({"a": "c"})),
+            (jsonc!({"a": "b"}), jsonc!({"b": "c"}), jsonc!({"a": "b", "b": "c"})),
+            (jsonc!({"a": "b"}), jsonc!({"a": null}), jsonc!({})),
+            (jsonc!({"a": "b", "b": "c"}), jsonc!({"a": null}), jsonc!({"b": "c"})),
+            (jsonc!({"a": ["b"]}), jsonc!({"a": "c"}), jsonc!({"a": "c"})),
+            (jsonc!({"a": "c"}), jsonc!({"a": ["b"]}), jsonc!({"a": ["b"]})),
+            (jsonc!({"a": {"b": "c"}}), jsonc!({"a": {"b": "d", "c": null}}), jsonc!({"a": {"b": "d"}})),
+            (jsonc!({"a": [{"b": "c"}]}), jsonc!({"a": [1]}), jsonc!({"a": [1]})),
+            (jsonc!(["a", "b"]), jsonc!(["c", "d"]), jsonc!(["c", "d"])),
+            (jsonc!({"a": "b"}), jsonc!(["c"]), jsonc!(["c"])),
+            (jsonc!({"a": "foo"}), jsonc!(null), jsonc!(null)),
+            (jsonc!({"a": "foo"}), jsonc!("bar"), jsonc!("bar")),
+            (jsonc!({"e": null}), jsonc!({"a": 1}), jsonc!({"e": null, "a": 1})),
+            (jsonc!([1, 2]), jsonc!({"a": "b", "c": null}), jsonc!({"a": "b"})),
+            (jsonc!({}), jsonc!({"a": {"bb": {"ccc": null}}}), jsonc!({"a": {"bb": {}}})),
+        ];
+        for (mut target, patch, expected) in cases {
+            target.merge_patch(&patch);
+            assert_eq!(target, expected);
+        }
+    }
+
+    #[test]
+    fn test_deep_merge_arrays() {
+        let base = jsonc!({"list": [{"id": 1, "v": "a"}, {"id": 2, "v": "b"}]});
+        let other = jsonc!({"list": [{"id": 2, "v": "c"}, {"id": 3, "v": "d"}]});
+        let merge = |array: ArrayMerge| {
+            let mut merged = base.clone();
+            merged.deep_merge(&other, MergeOptions::new().array(array)).unwrap();
+            merged
+        };
+        assert_eq!(merge(ArrayMerge::Replace), other);
+        assert_eq!(
+            merge(ArrayMerge::Append),
+            jsonc!({"list": [{"id": 1, "v": "a"}, {"id": 2, "v": "b"}, {"id": 2, "v": "c"}, {"id": 3, "v": "d"}]})
+        );
+        assert_eq!(merge(ArrayMerge::ByIndex), jsonc!({"list": [{"id": 2, "v": "c"}, {"id": 3, "v": "d"}]}));
+        assert_eq!(
+            merge(ArrayMerge::ByKey("id".to_string())),
+            jsonc!({"list": [{"id": 1, "v": "a"}, {"id": 2, "v": "c"}, {"id": 3, "v": "d"}]})
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_conflicts() {
+        let mut value = jsonc!({"a": {"b": 1, "c": [1]}, "d": "x"});
+        let mut conflicts = Vec::new();
+        let options = MergeOptions::new().on_conflict(|pointer, base: &Value, other| {
+            conflicts.push((pointer.to_string(), base.clone(), other.clone()));
+            Resolution::Overwrite
+        });
+        value.deep_merge(&jsonc!({"a": {"b": 1, "c": [2]}, "d": {"e": null}}), options).unwrap();
+        assert_eq!(value, jsonc!({"a": {"b": 1, "c": [2]}, "d": {"e": null}}));
+        conflicts.sort_by(|(p1, _, _), (p2, _, _)| p1.cmp(p2));
+        assert_eq!(
+            conflicts,
+            vec![("/a/c".to_string(), jsonc!([1]), jsonc!([2])), ("/d".to_string(), jsonc!("x"), jsonc!({"e": null}))]
+        );
+
+        let before = value.clone();
+        let options = MergeOptions::new().on_conflict(|_, _, _| Resolution::Fail);
+        assert!(value.deep_merge(&jsonc!({"new": 1, "d": 1}), options).is_err());
+        assert_eq!(value, before);
+
+        let options = MergeOptions::new().on_conflict(|_, _, _| Resolution::Fail);
+        value.deep_merge(&jsonc!({"a": {"b": 1, "c": [2]}, "new": [[]]}), options).unwrap();
+        assert_eq!(value, jsonc!({"a": {"b": 1, "c": [2]}, "d": {"e": null}, "new": [[]]}));
+    }
+}
@@ -5,12 +5,113 @@ use crate::{
     error::Ensure,
 };
 
-#[derive(Debug, Clone, PartialEq, PartialOrd)]
+/// Number of JSON with comments, whether integer or float.
+///
+/// Numbers are totally ordered by their values, an integer and a float are compared as `f64`,
+/// and floats are ordered like `total_cmp` except that `-0.0 == 0.0` and every `NaN` is equal to each other
+/// and greater than any other number. An integer and a float with the same value are not equal,
+/// the integer is less than the float, so the order is consistent with `Eq` and `Hash`.
+/// So `Number` can be used as keys of `HashMap` or `BTreeMap`, sorted and deduplicated.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::value::number::Number;
+/// let mut numbers = vec![Number::Float(f64::NAN), Number::Float(1.5), Number::Integer(2), Number::Float(-0.0)];
+/// numbers.sort();
+/// assert_eq!(numbers, [Number::Float(0.0), Number::Float(1.5), Number::Integer(2), Number::Float(f64::NAN)]);
+///
+/// let mut numbers = vec![Number::Float(2.0), Number::Integer(10), Number::Float(2.5), Number::Integer(2)];
+/// numbers.sort();
+/// assert_eq!(numbers, [Number::Integer(2), Number::Float(2.0), Number::Float(2.5), Number::Integer(10)]);
+/// ```
+#[derive(Debug, Clone)]
 pub enum Number<I, F> {
     Integer(I),
     Float(F),
 }
 
+impl<I: PartialEq, F: PartialEq> PartialEq for Number<I, F> {
+    #[allow(clippy::eq_op)] // `f != f` means `NaN`
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Number::Integer(i1), Number::Integer(i2)) => i1 == i2,
+            (Number::Float(f1), Number::Float(f2)) => f1 == f2 || (f1 != f1 && f2 != f2),
+            _ => false,
+        }
+    }
+}
+impl<I: Eq, F: num::Float> Eq for Number<I, F> {}
+
+impl<I: PartialEq + num::ToPrimitive, F: PartialEq + num::ToPrimitive> Number<I, F> {
+    /// Returns `true` if two numbers have the same value, an integer and a float are compared as `f64`.
+    /// Unlike `==`, `Integer(1)` and `Float(1.0)` are equal, and `NaN` is not equal to `NaN`.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::value::number::Number;
+    /// assert!(Number::<i64, f64>::Integer(1).numeric_eq(&Number::Float(1.0)));
+    /// assert!(!Number::<i64, f64>::Integer(1).numeric_eq(&Number::Float(1.5)));
+    /// assert_ne!(Number::<i64, f64>::Integer(1), Number::Float(1.0));
+    /// ```
+    pub fn numeric_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Number::Integer(i1), Number::Integer(i2)) => i1 == i2,
+            (Number::Float(f1), Number::Float(f2)) => f1 == f2,
+            (Number::Integer(i), Number::Float(f)) | (Number::Float(f), Number::Integer(i)) => {
+                i.to_f64().is_some_and(|i| f.to_f64() == Some(i))
+            }
+        }
+    }
+}
+
+impl<I: PartialOrd + num::ToPrimitive, F: PartialOrd + num::ToPrimitive> PartialOrd for Number<I, F> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Number::Integer(i1), Number::Integer(i2)) => i1.partial_cmp(i2),
+            (Number::Float(f1), Number::Float(f2)) => float_cmp(f1, f2),
+            (Number::Integer(i), Number::Float(f)) => Some(mixed_cmp(i, f)),
+            (Number::Float(f), Number::Integer(i)) => Some(mixed_cmp(i, f).reverse()),
+        }
+    }
+}
+impl<I: Ord + num::ToPrimitive, F: num::Float> Ord for Number<I, F> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.partial_cmp(other).unwrap_or_else(|| unreachable!("NaN is handled by float_cmp"))
+    }
+}
+
+impl<I: std::hash::Hash, F: num::Float> std::hash::Hash for Number<I, F> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Number::Integer(i) => i.hash(state),
+            Number::Float(f) if f.is_nan() => F::nan().integer_decode().hash(state),
+            Number::Float(f) if f.is_zero() => F::zero().integer_decode().hash(state),
+            Number::Float(f) => f.integer_decode().hash(state),
+        }
+    }
+}
+
+/// Compare floats as `partial_cmp`, but `NaN` is equal to `NaN` and greater than any other float.
+fn float_cmp<F: PartialOrd>(f1: &F, f2: &F) -> Option<std::cmp::Ordering> {
+    let is_nan = |f: &F| f.partial_cmp(f).is_none();
+    match (f1.partial_cmp(f2), is_nan(f1), is_nan(f2)) {
+        (Some(ord), _, _) => Some(ord),
+        (None, true, true) => Some(std::cmp::Ordering::Equal),
+        (None, true, false) => Some(std::cmp::Ordering::Greater),
+        (None, false, true) => Some(std::cmp::Ordering::Less),
+        (None, false, false) => None,
+    }
+}
+
+/// Compare an integer and a float as `f64`, the integer is less if they have the same value or cannot be converted.
+fn mixed_cmp<I: num::ToPrimitive, F: num::ToPrimitive>(i: &I, f: &F) -> std::cmp::Ordering {
+    match (i.to_f64(), f.to_f64()) {
+        (Some(i), Some(f)) => float_cmp(&i, &f).unwrap_or(std::cmp::Ordering::Equal).then(std::cmp::Ordering::Less),
+        _ => std::cmp::Ordering::Less,
+    }
+}
+
 impl<I, F> FromNumberBuilder for Number<I, F>
 where
     I: FromNumberBuilder,
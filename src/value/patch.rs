@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{PatchError, PointerError};
+
+use super::{
+    pointer::{array_index, to_pointer, tokens, unescape},
+    DefaultMap, JsoncMap, JsoncValue, MapBackend,
+};
+
+impl<I: Clone + PartialEq + num::ToPrimitive, F: Clone + PartialEq + num::ToPrimitive, M: MapBackend>
+    JsoncValue<I, F, M>
+{
+    /// Apply [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch.
+    /// The patch is applied atomically, if any operation fails, the value is not changed at all.
+    ///
+    /// # Errors
+    /// Returns `Err` if a pointer is invalid, a target value does not exist, or a `test` operation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{from_str, jsonc, value::patch::Patch};
+    /// let mut value = jsonc!({"server": {"port": 80}, "debug": true});
+    /// let patch: Patch = from_str(r#"[
+    ///     { "op": "test", "path": "/server/port", "value": 80 },
+    ///     { "op": "replace", "path": "/server/port", "value": 8080 },
+    ///     { "op": "add", "path": "/server/hosts", "value": ["localhost"] },
+    ///     { "op": "remove", "path": "/debug" }, // comments are allowed in patch file
+    /// ]"#).unwrap();
+    /// value.apply_patch(&patch).unwrap();
+    /// assert_eq!(value, jsonc!({"server": {"port": 8080, "hosts": ["localhost"]}}));
+    ///
+    /// let failed: Patch = from_str(r#"[
+    ///     { "op": "remove", "path": "/server/port" },
+    ///     { "op": "test", "path": "/server/hosts/0", "value": "127.0.0.1" },
+    /// ]"#).unwrap();
+    /// assert!(value.apply_patch(&failed).is_err());
+    /// assert_eq!(value, jsonc!({"server": {"port": 8080, "hosts": ["localhost"]}}));
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Patch<I, F, M>) -> crate::Result<()> {
+        let mut patched = self.clone();
+        for operation in &patch.0 {
+            patched.apply_operation(operation)?;
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    fn apply_operation(&mut self, operation: &PatchOperation<I, F, M>) -> crate::Result<()> {
+        match operation {
+            PatchOperation::Add { path, value } => self.add(path, value.clone()),
+            PatchOperation::Remove { path } => self.remove(path).map(|_| ()),
+            PatchOperation::Replace { path, value } => {
+                tokens(path)?;
+                *self.pointer_mut(path).ok_or_else(|| PatchError::NotFound { pointer: path.clone() })? = value.clone();
+                Ok(())
+            }
+            PatchOperation::Move { from, path } => {
+                if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                    Err(PatchError::MoveIntoChild { from: from.clone(), pointer: path.clone() })?
+                }
+                let value = self.remove(from)?;
+                self.add(path, value)
+            }
+            PatchOperation::Copy { from, path } => {
+                tokens(from)?;
+                let value = self.pointer(from).ok_or_else(|| PatchError::NotFound { pointer: from.clone() })?;
+                self.add(path, value.clone())
+            }
+            PatchOperation::Test { path, value } => {
+                tokens(path)?;
+                match self.pointer(path) {
+                    Some(target) if test_eq(target, value) => Ok(()),
+                    Some(_) => Err(PatchError::TestFailed { pointer: path.clone() })?,
+                    None => Err(PatchError::NotFound { pointer: path.clone() })?,
+                }
+            }
+        }
+    }
+
+    /// Unlike [`Self::insert_at_pointer`], the parent of the target must exist.
+    fn add(&mut self, pointer: &str, value: Self) -> crate::Result<()> {
+        tokens(pointer)?;
+        let Some((parent, last)) = pointer.rsplit_once('/') else {
+            *self = value;
+            return Ok(());
+        };
+        let last = unescape(last)?;
+        match self.pointer_mut(parent).ok_or_else(|| PatchError::NotFound { pointer: parent.to_string() })? {
+            JsoncValue::Object(map) => {
+                map.insert(last.into_owned(), value);
+            }
+            JsoncValue::Array(vec) => {
+                let index = match &last[..] {
+                    "-" => vec.len(),
+                    _ => array_index(&last)?,
+                };
+                if index > vec.len() {
+                    Err(PointerError::OutOfRange { pointer: pointer.to_string(), index, len: vec.len() })?
+                }
+                vec.insert(index, value);
+            }
+            target => Err(PointerError::CannotTraverse {
+                pointer: pointer.to_string(),
+                token: last.into_owned(),
+                value: target.value_type(),
+            })?,
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, pointer: &str) -> crate::Result<Self> {
+        tokens(pointer)?;
+        Ok(self.remove_at_pointer(pointer).ok_or_else(|| PatchError::NotFound { pointer: pointer.to_string() })?)
+    }
+}
+
+/// Equality of the `test` operation, numbers are equal if their values are numerically equal (RFC 6902 section 4.6).
+fn test_eq<I, F, M>(target: &JsoncValue<I, F, M>, value: &JsoncValue<I, F, M>) -> bool
+where
+    I: PartialEq + num::ToPrimitive,
+    F: PartialEq + num::ToPrimitive,
+    M: MapBackend,
+{
+    match (target, value) {
+        (JsoncValue::Object(m1), JsoncValue::Object(m2)) => {
+            m1.len() == m2.len() && m1.iter().all(|(k, v1)| m2.get(k).is_some_and(|v2| test_eq(v1, v2)))
+        }
+        (JsoncValue::Array(v1), JsoncValue::Array(v2)) => {
+            v1.len() == v2.len() && v1.iter().zip(v2).all(|(e1, e2)| test_eq(e1, e2))
+        }
+        (JsoncValue::Number(n1), JsoncValue::Number(n2)) => n1.numeric_eq(n2),
+        _ => target == value,
+    }
+}
+
+/// [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902) JSON Patch document, that is an array of operations.
+/// It can be deserialized from JSONC text and serialized into JSONC text.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, to_string, value::patch::{Patch, PatchOperation}};
+/// let patch = Patch(vec![
+///     PatchOperation::Add { path: "/a".to_string(), value: jsonc!([1, 2]) },
+///     PatchOperation::Move { from: "/b".to_string(), path: "/c".to_string() },
+/// ]);
+/// assert_eq!(
+///     to_string(&patch).unwrap(),
+///     r#"[{"op":"add","path":"/a","value":[1,2]},{"op":"move","from":"/b","path":"/c"}]"#,
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[serde(bound(serialize = "I: Serialize, F: Serialize", deserialize = "I: num::FromPrimitive, F: num::FromPrimitive"))]
+pub struct Patch<I = i64, F = f64, M: MapBackend = DefaultMap>(pub Vec<PatchOperation<I, F, M>>);
+
+/// Operation of JSON Patch, the `path` and `from` members are JSON Pointer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+#[serde(bound(serialize = "I: Serialize, F: Serialize", deserialize = "I: num::FromPrimitive, F: num::FromPrimitive"))]
+pub enum PatchOperation<I = i64, F = f64, M: MapBackend = DefaultMap> {
+    Add { path: String, value: JsoncValue<I, F, M> },
+    Remove { path: String },
+    Replace { path: String, value: JsoncValue<I, F, M> },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: JsoncValue<I, F, M> },
+}
+
+impl<I: Clone + PartialEq, F: Clone + PartialEq, M: MapBackend> Patch<I, F, M> {
+    /// Generate patch that transforms `from` into `to`.
+    /// Object members are added, removed or patched recursively, and array elements are patched by position.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, value::patch::Patch};
+    /// let (from, to) = (jsonc!({"a": 1, "b": [1, 2, 3]}), jsonc!({"a": 2, "b": [1, 2], "c": null}));
+    /// let patch = Patch::diff(&from, &to);
+    /// assert_eq!(patch.0.len(), 3);
+    ///
+    /// let mut patched = from.clone();
+    /// patched.apply_patch(&patch).unwrap();
+    /// assert_eq!(patched, to);
+    /// ```
+    pub fn diff(from: &JsoncValue<I, F, M>, to: &JsoncValue<I, F, M>) -> Self {
+        let mut operations = Vec::new();
+        Self::diff_recursive(&mut Vec::new(), from, to, &mut operations);
+        Self(operations)
+    }
+
+    fn diff_recursive(
+        path: &mut Vec<String>,
+        from: &JsoncValue<I, F, M>,
+        to: &JsoncValue<I, F, M>,
+        operations: &mut Vec<PatchOperation<I, F, M>>,
+    ) {
+        match (from, to) {
+            _ if from == to => {}
+            (JsoncValue::Object(from), JsoncValue::Object(to)) => {
+                for (key, value) in from.iter() {
+                    path.push(key.clone());
+                    match to.get(key) {
+                        Some(to) => Self::diff_recursive(path, value, to, operations),
+                        None => operations.push(PatchOperation::Remove { path: to_pointer(&*path) }),
+                    }
+                    path.pop();
+                }
+                for (key, value) in to.iter().filter(|(key, _)| !from.contains_key(key)) {
+                    path.push(key.clone());
+                    operations.push(PatchOperation::Add { path: to_pointer(&*path), value: value.clone() });
+                    path.pop();
+                }
+            }
+            (JsoncValue::Array(from), JsoncValue::Array(to)) => {
+                for (i, (from, to)) in from.iter().zip(to).enumerate() {
+                    path.push(i.to_string());
+                    Self::diff_recursive(path, from, to, operations);
+                    path.pop();
+                }
+                for i in (to.len()..from.len()).rev() {
+                    path.push(i.to_string());
+                    operations.push(PatchOperation::Remove { path: to_pointer(&*path) });
+                    path.pop();
+                }
+                for (i, value) in to.iter().enumerate().skip(from.len()) {
+                    path.push(i.to_string());
+                    operations.push(PatchOperation::Add { path: to_pointer(&*path), value: value.clone() });
+                    path.pop();
+                }
+            }
+            _ => operations.push(PatchOperation::Replace { path: to_pointer(&*path), value: to.clone() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_str, jsonc, to_string, Value};
+
+    use super::*;
+
+    fn apply(target: Value, patch: &str) -> crate::Result<Value> {
+        let mut target = target;
+        target.apply_patch(&from_str(patch)?)?;
+        Ok(target)
+    }
+
+    #[test]
+    fn test_rfc_examples() {
+        // RFC 6902 appendix A
+        assert_eq!(
+            apply(jsonc!({"foo": "bar"}), r#"[{"op": "add", "path": "/baz", "value": "qux"}]"#).unwrap(),
+            jsonc!({"baz": "qux", "foo": "bar"})
+        );
+        assert_eq!(
+            apply(jsonc!({"foo": ["bar", "baz"]}), r#"[{"op": "add", "path": "/foo/1", "value": "qux"}]"#).unwrap(),
+            jsonc!({"foo": ["bar", "qux", "baz"]})
+        );
+        assert_eq!(
+            apply(jsonc!({"baz": "qux", "foo": "bar"}), r#"[{"op": "remove", "path": "/baz"}]"#).unwrap(),
+            jsonc!({"foo": "bar"})
+        );
+        assert_eq!(
+            apply(jsonc!({"foo": ["bar", "qux", "baz"]}), r#"[{"op": "remove", "path": "/foo/1"}]"#).unwrap(),
+            jsonc!({"foo": ["bar", "baz"]})
+        );
+        assert_eq!(
+            apply(jsonc!({"baz": "qux", "foo": "bar"}), r#"[{"op": "replace", "path": "/baz", "value": "boo"}]"#)
+                .unwrap(),
+            jsonc!({"baz": "boo", "foo": "bar"})
+        );
+        assert_eq!(
+            apply(
+                jsonc!({"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}),
+                r#"[{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]"#
+            )
+            .unwrap(),
+            jsonc!({"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}})
+        );
+        assert_eq!(
+            apply(
+                jsonc!({"foo": ["all", "grass", "cows", "eat"]}),
+                r#"[{"op": "move", "from": "/foo/1", "path": "/foo/3"}]"#
+            )
+            .unwrap(),
+            jsonc!({"foo": ["all", "cows", "eat", "grass"]})
+        );
+        assert_eq!(
+            apply(
+                jsonc!({"baz": "qux", "foo": ["a", 2, "c"]}),
+                r#"[{"op": "test", "path": "/baz", "value": "qux"}, {"op": "test", "path": "/foo/1", "value": 2}]"#
+            )
+            .unwrap(),
+            jsonc!({"baz": "qux", "foo": ["a", 2, "c"]})
+        );
+        assert!(apply(jsonc!({"baz": "qux"}), r#"[{"op": "test", "path": "/baz", "value": "bar"}]"#).is_err());
+        assert_eq!(
+            apply(jsonc!({"foo": "bar"}), r#"[{"op": "add", "path": "/child", "value": {"grandchild": {}}}]"#).unwrap(),
+            jsonc!({"foo": "bar", "child": {"grandchild": {}}})
+        );
+        assert_eq!(
+            apply(jsonc!({"foo": "bar"}), r#"[{"op": "add", "path": "/baz", "value": "qux", "xyz": 123}]"#).unwrap(),
+            jsonc!({"foo": "bar", "baz": "qux"})
+        );
+        assert!(apply(jsonc!({"foo": "bar"}), r#"[{"op": "add", "path": "/baz/bat", "value": "qux"}]"#).is_err());
+        assert_eq!(
+            apply(jsonc!({"/": 9, "~1": 10}), r#"[{"op": "test", "path": "/~01", "value": 10}]"#).unwrap(),
+            jsonc!({"/": 9, "~1": 10})
+        );
+        assert_eq!(
+            apply(jsonc!({"foo": ["bar"]}), r#"[{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]"#).unwrap(),
+            jsonc!({"foo": ["bar", ["abc", "def"]]})
+        );
+    }
+
+    #[test]
+    fn test_numeric_equality() {
+        let target = jsonc!({"a": 1, "b": [2.0, {"c": -3}], "d": 0.5});
+        let test = r#"[
+            {"op": "test", "path": "/a", "value": 1.0},
+            {"op": "test", "path": "/b", "value": [2, {"c": -3.0}]},
+            {"op": "test", "path": "/d", "value": 0.5},
+        ]"#;
+        assert_eq!(apply(target.clone(), test).unwrap(), target);
+        assert!(apply(target.clone(), r#"[{"op": "test", "path": "/a", "value": 1.5}]"#).is_err());
+        assert!(apply(target.clone(), r#"[{"op": "test", "path": "/b", "value": [2, {"c": -3}, 4]}]"#).is_err());
+        assert!(apply(target.clone(), r#"[{"op": "test", "path": "/a", "value": "1"}]"#).is_err());
+    }
+
+    #[test]
+    fn test_invalid_operations() {
+        let target = jsonc!({"a": {"b": [1, 2]}});
+        for invalid in [
+            r#"[{"op": "unknown", "path": "/a"}]"#,
+            r#"[{"op": "add", "path": "/a/b/3", "value": 3}]"#,
+            r#"[{"op": "add", "path": "/a/b/x", "value": 3}]"#,
+            r#"[{"op": "remove", "path": "/a/c"}]"#,
+            r#"[{"op": "replace", "path": "/c", "value": 3}]"#,
+            r#"[{"op": "move", "from": "/a", "path": "/a/c"}]"#,
+            r#"[{"op": "copy", "from": "/c", "path": "/d"}]"#,
+            r#"[{"op": "remove", "path": "a"}]"#,
+            r#"[{"op": "add", "value": 3}]"#,
+        ] {
+            assert!(apply(target.clone(), invalid).is_err(), "{invalid} should fail");
+        }
+        assert_eq!(
+            apply(
+                target.clone(),
+                r#"[{"op": "copy", "from": "/a/b", "path": "/a/c"}, {"op": "move", "from": "/a", "path": ""}]"#
+            )
+            .unwrap(),
+            jsonc!({"b": [1, 2], "c": [1, 2]})
+        );
+        assert_eq!(apply(target.clone(), r#"[{"op": "move", "from": "/a", "path": "/a"}]"#).unwrap(), target);
+    }
+
+    #[test]
+    fn test_diff() {
+        let cases = [
+            (jsonc!({"a": 1}), jsonc!({"a": 1})),
+            (jsonc!({"a": 1, "b": {"c": [1, 2, 3]}}), jsonc!({"b": {"c": [1, 4], "d": true}})),
+            (jsonc!([1, {"a": 2}]), jsonc!([0, {"a": 3}, 4, 5])),
+            (jsonc!({"a": [1]}), jsonc!("replaced")),
+        ];
+        for (from, to) in cases {
+            let patch = Patch::diff(&from, &to);
+            let serialized = to_string(&patch).unwrap();
+            let mut patched = from.clone();
+            patched.apply_patch(&from_str(&serialized).unwrap()).unwrap();
+            assert_eq!(patched, to);
+        }
+        assert_eq!(
+            Patch::diff(&jsonc!([1, 2, 3]), &jsonc!([1])),
+            from_str(r#"[{"op": "remove", "path": "/2"}, {"op": "remove", "path": "/1"}]"#).unwrap()
+        );
+    }
+}
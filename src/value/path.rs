@@ -0,0 +1,427 @@
+mod eval;
+mod query;
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::error::{Ensure, PathError};
+
+use self::{eval::Evaluator, query::Parser};
+
+use super::{JsoncMap, JsoncValue, MapBackend};
+
+type LocatedMut<'a, I, F, M> = (NormalizedPath, &'a mut JsoncValue<I, F, M>);
+
+impl<I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> JsoncValue<I, F, M> {
+    /// Returns references to the nodes that are selected by [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535)
+    /// JSONPath query. See [`JsonPath`] for supported syntax.
+    ///
+    /// # Errors
+    /// Returns `Err` if the query is not valid JSONPath.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let value = jsonc!({
+    ///     "servers": [
+    ///         {"host": "alpha", "port": 80, "enabled": true},
+    ///         {"host": "beta", "port": 443, "enabled": false},
+    ///         {"host": "gamma", "port": 8080, "enabled": true},
+    ///     ],
+    /// });
+    /// assert_eq!(value.select("$.servers[?@.enabled == true].port").unwrap(), vec![&80.into(), &8080.into()]);
+    /// assert_eq!(value.select("$.servers[?@.port > 100].host").unwrap(), vec![&"beta".into(), &"gamma".into()]);
+    /// assert_eq!(value.select("$.servers[-1:].host").unwrap(), vec![&"gamma".into()]);
+    /// assert_eq!(value.select("$..port").unwrap(), vec![&80.into(), &443.into(), &8080.into()]);
+    /// assert!(value.select("servers").is_err());
+    /// ```
+    pub fn select(&self, path: &str) -> crate::Result<Vec<&Self>> {
+        Ok(JsonPath::parse(path)?.select(self))
+    }
+
+    /// Returns the nodes that are selected by JSONPath query with their normalized paths (see [`Self::select`]).
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let value = jsonc!({"servers": [{"port": 80}, {"port": 443}]});
+    /// let located = value.select_located("$.servers[*].port").unwrap();
+    /// let paths: Vec<_> = located.iter().map(|(path, _)| path.to_string()).collect();
+    /// assert_eq!(paths, vec!["$['servers'][0]['port']", "$['servers'][1]['port']"]);
+    /// assert_eq!(located[1].0.to_pointer(), "/servers/1/port");
+    /// assert_eq!(located[1].1, &443.into());
+    /// ```
+    pub fn select_located(&self, path: &str) -> crate::Result<Vec<(NormalizedPath, &Self)>> {
+        Ok(JsonPath::parse(path)?.select_located(self))
+    }
+
+    /// Returns mutable references to the nodes that are selected by JSONPath query (see [`Self::select`]).
+    /// The node selected more than once is returned only once.
+    ///
+    /// # Errors
+    /// Returns `Err` if the query is not valid JSONPath, or a selected node is a descendant of another selected node.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"servers": [{"port": 80, "enabled": true}, {"port": 443, "enabled": false}]});
+    /// for port in value.select_mut("$.servers[?@.enabled == true].port").unwrap() {
+    ///     *port = 8080.into();
+    /// }
+    /// assert_eq!(value, jsonc!({"servers": [{"port": 8080, "enabled": true}, {"port": 443, "enabled": false}]}));
+    ///
+    /// assert!(value.select_mut("$..*").is_err());
+    /// ```
+    pub fn select_mut(&mut self, path: &str) -> crate::Result<Vec<&mut Self>> {
+        JsonPath::parse(path)?.select_mut(self)
+    }
+
+    /// Returns mutable references to the nodes that are selected by JSONPath query with their normalized paths
+    /// (see [`Self::select_mut`]).
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"ports": [80, 443]});
+    /// for (path, port) in value.select_located_mut("$.ports[*]").unwrap() {
+    ///     *port = path.to_pointer().into();
+    /// }
+    /// assert_eq!(value, jsonc!({"ports": ["/ports/0", "/ports/1"]}));
+    /// ```
+    pub fn select_located_mut(&mut self, path: &str) -> crate::Result<Vec<(NormalizedPath, &mut Self)>> {
+        JsonPath::parse(path)?.select_located_mut(self)
+    }
+}
+
+/// Parsed [RFC 9535](https://www.rfc-editor.org/rfc/rfc9535) JSONPath query, that can be reused for many values.
+///
+/// Supported syntax is
+/// - root `$`, child `.name` `['name']` `[0]` `[-1]`, wildcard `.*` `[*]`, descendant `..name` `..[0]` `..*`
+/// - slice `[start:end:step]`, union `[0, 'name', 1:3]`
+/// - filter `[?@.price < 10 && !@.sold]`, with `||` `&&` `!` `( )`, comparison `==` `!=` `<` `<=` `>` `>=`,
+///   and functions `length()` `count()` `value()`, and also `match()` `search()` if `regex` feature is enabled.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::{jsonc, value::path::JsonPath};
+/// let path: JsonPath = "$.store.book[?length(@.tags) >= 2].title".parse().unwrap();
+/// let value = jsonc!({"store": {"book": [
+///     {"title": "Sayings of the Century", "tags": ["quotes"]},
+///     {"title": "Moby Dick", "tags": ["novel", "sea"]},
+/// ]}});
+/// assert_eq!(path.select(&value), vec![&"Moby Dick".into()]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath {
+    query: query::Query,
+}
+impl FromStr for JsonPath {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+impl JsonPath {
+    /// Parse JSONPath query, that must be well-formed and well-typed.
+    pub fn parse(path: &str) -> crate::Result<Self> {
+        Ok(Self { query: Parser::new(path).parse()? })
+    }
+
+    /// Returns references to the selected nodes, see [`JsoncValue::select`].
+    pub fn select<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend>(
+        &self,
+        value: &'a JsoncValue<I, F, M>,
+    ) -> Vec<&'a JsoncValue<I, F, M>> {
+        Evaluator::new(value).evaluate(&self.query, value).into_iter().map(|(_, node)| node).collect()
+    }
+
+    /// Returns the selected nodes with their normalized paths, see [`JsoncValue::select_located`].
+    pub fn select_located<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend>(
+        &self,
+        value: &'a JsoncValue<I, F, M>,
+    ) -> Vec<(NormalizedPath, &'a JsoncValue<I, F, M>)> {
+        let located = Evaluator::new(value).evaluate(&self.query, value);
+        located.into_iter().map(|(path, node)| (NormalizedPath(path), node)).collect()
+    }
+
+    /// Returns mutable references to the selected nodes, see [`JsoncValue::select_mut`].
+    pub fn select_mut<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend>(
+        &self,
+        value: &'a mut JsoncValue<I, F, M>,
+    ) -> crate::Result<Vec<&'a mut JsoncValue<I, F, M>>> {
+        Ok(self.select_located_mut(value)?.into_iter().map(|(_, node)| node).collect())
+    }
+
+    /// Returns mutable references to the selected nodes with their normalized paths,
+    /// see [`JsoncValue::select_located_mut`].
+    pub fn select_located_mut<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend>(
+        &self,
+        value: &'a mut JsoncValue<I, F, M>,
+    ) -> crate::Result<Vec<LocatedMut<'a, I, F, M>>> {
+        let mut paths = Vec::new();
+        for (path, _) in self.select_located(value) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+
+        // in lexicographic order, the descendants of a path immediately follow the path
+        let mut sorted: Vec<_> = paths.iter().collect();
+        sorted.sort();
+        if let Some(w) = sorted.windows(2).find(|w| w[1].0.starts_with(&w[0].0)) {
+            Err(PathError::OverlappingSelection { ancestor: w[0].to_string(), descendant: w[1].to_string() })?
+        }
+
+        let mut slots: Vec<_> = paths.iter().map(|_| None).collect();
+        collect_mut(value, paths.iter().map(|path| &path.0[..]).enumerate().collect(), &mut slots);
+        paths.into_iter().zip(slots).map(|(path, node)| Ok((path, node.ok_or(Ensure::SelectedNode)?))).collect()
+    }
+}
+
+/// Distribute disjoint mutable references to the slots, the targets must not overlap each other.
+fn collect_mut<'a, I, F, M: MapBackend>(
+    value: &'a mut JsoncValue<I, F, M>,
+    targets: Vec<(usize, &[PathElement])>,
+    slots: &mut [Option<&'a mut JsoncValue<I, F, M>>],
+) {
+    if let Some(&(slot, _)) = targets.iter().find(|(_, path)| path.is_empty()) {
+        slots[slot] = Some(value);
+        return;
+    }
+    let mut children: HashMap<_, Vec<_>> = HashMap::new();
+    for (slot, path) in targets {
+        children.entry(&path[0]).or_default().push((slot, &path[1..]));
+    }
+    match value {
+        JsoncValue::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if let Some(targets) = children.remove(&PathElement::Name(key.clone())) {
+                    collect_mut(child, targets, slots);
+                }
+            }
+        }
+        JsoncValue::Array(vec) => {
+            for (index, child) in vec.iter_mut().enumerate() {
+                if let Some(targets) = children.remove(&PathElement::Index(index)) {
+                    collect_mut(child, targets, slots);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Normalized path (RFC 9535 section 2.7) that identifies exactly one node, such as `$['store']['book'][0]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NormalizedPath(pub Vec<PathElement>);
+
+/// Element of [`NormalizedPath`], member name of object or index of array.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathElement {
+    Name(String),
+    Index(usize),
+}
+
+impl NormalizedPath {
+    /// Convert into RFC 6901 JSON Pointer, that can be used with [`JsoncValue::pointer`].
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::value::path::{NormalizedPath, PathElement};
+    /// let path = NormalizedPath(vec![PathElement::Name("a/b".into()), PathElement::Index(0)]);
+    /// assert_eq!(path.to_pointer(), "/a~1b/0");
+    /// ```
+    pub fn to_pointer(&self) -> String {
+        super::pointer::to_pointer(self.0.iter().map(|element| match element {
+            PathElement::Name(name) => name.clone(),
+            PathElement::Index(index) => index.to_string(),
+        }))
+    }
+}
+impl fmt::Display for NormalizedPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+        self.0.iter().try_for_each(|element| write!(f, "{element}"))
+    }
+}
+impl fmt::Display for PathElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathElement::Name(name) => {
+                write!(f, "['")?;
+                for c in name.chars() {
+                    match c {
+                        '\x08' => write!(f, "\\b")?,
+                        '\x0C' => write!(f, "\\f")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\r' => write!(f, "\\r")?,
+                        '\t' => write!(f, "\\t")?,
+                        '\'' => write!(f, "\\'")?,
+                        '\\' => write!(f, "\\\\")?,
+                        c if c < '\x20' => write!(f, "\\u{:04x}", c as u32)?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                write!(f, "']")
+            }
+            PathElement::Index(index) => write!(f, "[{index}]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc, Value};
+
+    use super::*;
+
+    fn bookstore() -> Value {
+        jsonc!({
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "title": "Sayings of the Century", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "title": "Sword of Honour", "price": 12.99},
+                    {
+                        "category": "fiction", "author": "Herman Melville", "title": "Moby Dick",
+                        "isbn": "0-553-21311-3", "price": 8.99,
+                    },
+                    {
+                        "category": "fiction", "author": "J. R. R. Tolkien", "title": "The Lord of the Rings",
+                        "isbn": "0-395-19395-8", "price": 22.99,
+                    },
+                ],
+                "bicycle": {"color": "red", "price": 399},
+            },
+        })
+    }
+
+    fn paths(value: &Value, path: &str) -> Vec<String> {
+        let mut paths: Vec<_> = value.select_located(path).unwrap().into_iter().map(|(p, _)| p.to_string()).collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_rfc_examples() {
+        let value = bookstore();
+        assert_eq!(
+            value.select("$.store.book[*].author").unwrap(),
+            vec![&"Nigel Rees".into(), &"Evelyn Waugh".into(), &"Herman Melville".into(), &"J. R. R. Tolkien".into()]
+        );
+        assert_eq!(value.select("$..author").unwrap().len(), 4);
+        assert_eq!(value.select("$.store.*").unwrap().len(), 2);
+        assert_eq!(value.select("$.store..price").unwrap().len(), 5);
+        assert_eq!(value.select("$..book[2].title").unwrap(), vec![&"Moby Dick".into()]);
+        assert_eq!(value.select("$..book[-1].title").unwrap(), vec![&"The Lord of the Rings".into()]);
+        assert_eq!(
+            value.select("$..book[0,1].title").unwrap(),
+            vec![&"Sayings of the Century".into(), &"Sword of Honour".into()]
+        );
+        assert_eq!(
+            value.select("$..book[:2].title").unwrap(),
+            vec![&"Sayings of the Century".into(), &"Sword of Honour".into()]
+        );
+        assert_eq!(
+            value.select("$..book[?@.isbn].title").unwrap(),
+            vec![&"Moby Dick".into(), &"The Lord of the Rings".into()]
+        );
+        assert_eq!(
+            value.select("$..book[?@.price<10].title").unwrap(),
+            vec![&"Sayings of the Century".into(), &"Moby Dick".into()]
+        );
+        assert_eq!(value.select("$..*").unwrap().len(), 27);
+        assert_eq!(value.select("$").unwrap(), vec![&value]);
+    }
+
+    #[test]
+    fn test_normalized_paths() {
+        let value = bookstore();
+        assert_eq!(
+            paths(&value, "$.store.bicycle.*"),
+            vec!["$['store']['bicycle']['color']", "$['store']['bicycle']['price']"]
+        );
+        assert_eq!(paths(&value, "$..book[?@.price > 20]"), vec!["$['store']['book'][3]"]);
+
+        let escaped = NormalizedPath(vec![PathElement::Name("a'\\\n\u{1}b".into()), PathElement::Index(2)]);
+        assert_eq!(escaped.to_string(), r"$['a\'\\\n\u0001b'][2]");
+        assert_eq!(NormalizedPath::default().to_string(), "$");
+    }
+
+    #[test]
+    fn test_filter_expressions() {
+        let value = jsonc!({"a": [3, 5, 1, 2, 4, 6, {"b": "j"}, {"b": "k"}, {"b": {}}, {"b": "kilo"}]});
+        assert_eq!(value.select("$.a[?@.b == 'kilo']").unwrap(), vec![&jsonc!({"b": "kilo"})]);
+        assert_eq!(value.select("$.a[?@>3.5]").unwrap(), vec![&5.into(), &4.into(), &6.into()]);
+        assert_eq!(value.select("$.a[?@.b]").unwrap().len(), 4);
+        assert_eq!(value.select("$.a[?@<2 || @.b == \"k\"]").unwrap(), vec![&1.into(), &jsonc!({"b": "k"})]);
+        assert_eq!(value.select("$.a[?!(@ < 5 || @.b)]").unwrap(), vec![&5.into(), &6.into()]);
+        assert_eq!(value.select("$.a[?@.b == @.b.c]").unwrap().len(), 6); // Nothing == Nothing
+        assert_eq!(value.select("$.a[?@.b == $.x]").unwrap().len(), 6);
+        assert_eq!(value.select("$.a[?length(@.b) == 4]").unwrap(), vec![&jsonc!({"b": "kilo"})]);
+        assert_eq!(value.select("$[?count(@[*]) > 5]").unwrap().len(), 1);
+        assert_eq!(value.select("$.a[?value(@..b) == 'j']").unwrap(), vec![&jsonc!({"b": "j"})]);
+        assert_eq!(value.select("$.a[?@ == 1.0]").unwrap(), vec![&1.into()]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex_functions() {
+        let value = jsonc!(["abc", "abd", "xabcx", "a\nc"]);
+        assert_eq!(value.select("$[?match(@, 'ab.')]").unwrap(), vec![&"abc".into(), &"abd".into()]);
+        assert_eq!(value.select("$[?search(@, 'bc')]").unwrap(), vec![&"abc".into(), &"xabcx".into()]);
+        assert_eq!(value.select("$[?match(@, 'a.c')]").unwrap(), vec![&"abc".into()]);
+        assert_eq!(value.select("$[?match(@, '[')]").unwrap(), Vec::<&Value>::new());
+    }
+
+    #[test]
+    fn test_invalid_queries() {
+        for invalid in [
+            "",
+            "store",
+            "$.",
+            "$[",
+            "$[0",
+            "$['a'",
+            "$[01]",
+            "$[-0]",
+            "$[9007199254740992]",
+            "$.a b",
+            "$[?@.a == ]",
+            "$[?@.* == 1]",
+            "$[?length(@.*) == 1]",
+            "$[?count(1) == 1]",
+            "$[?length(@)]",
+            "$[?1]",
+            "$[?foo(@)]",
+            "$['\\u0061",
+            "$['\\uD800']",
+        ] {
+            assert!(JsonPath::parse(invalid).is_err(), "{invalid:?} should be invalid");
+        }
+        #[cfg(not(feature = "regex"))]
+        assert!(JsonPath::parse("$[?match(@, 'a')]").is_err());
+
+        for valid in ["$", "$ .a", "$[ 'a' , 0 ]", "$[::-1]", "$[\"\\u0061\"]", "$.日本", "$[?(@.a)]", "$..[0]"] {
+            assert!(JsonPath::parse(valid).is_ok(), "{valid:?} should be valid");
+        }
+    }
+
+    #[test]
+    fn test_select_mut() {
+        let mut value = jsonc!({"a": [1, 2, 3], "b": {"c": 4}});
+        for node in value.select_mut("$.a[0,0,2]").unwrap() {
+            *node = 0.into();
+        }
+        assert_eq!(value, jsonc!({"a": [0, 2, 0], "b": {"c": 4}}));
+
+        let located = value.select_located_mut("$..c").unwrap();
+        assert_eq!(located.len(), 1);
+        assert_eq!(located[0].0.to_pointer(), "/b/c");
+        *located.into_iter().next().unwrap().1 = 5.into();
+        assert_eq!(value, jsonc!({"a": [0, 2, 0], "b": {"c": 5}}));
+
+        assert!(value.select_mut("$[*, 'b'].c").is_ok());
+        assert!(value.select_mut("$['b', 'b'].c").is_ok());
+        assert!(value.select_mut("$[*, 'b']").is_ok());
+        assert!(value.select_mut("$..['b', 'c']").is_err());
+    }
+}
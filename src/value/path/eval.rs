@@ -0,0 +1,383 @@
+#[cfg(feature = "regex")]
+use std::{cell::RefCell, collections::HashMap};
+
+use crate::value::{number::Number, DefaultMap, JsoncMap, JsoncValue, MapBackend};
+
+use super::{
+    query::{Comparable, ComparisonOp, Function, FunctionExpr, Literal, LogicalExpr, Query, Root, Segment, Selector},
+    PathElement,
+};
+
+pub type Located<'a, I, F, M> = (Vec<PathElement>, &'a JsoncValue<I, F, M>);
+
+/// Evaluate parsed query against the root value, see RFC 9535 section 2.
+pub struct Evaluator<'a, I, F, M: MapBackend = DefaultMap> {
+    root: &'a JsoncValue<I, F, M>,
+    #[cfg(feature = "regex")]
+    regex: RefCell<HashMap<(String, bool), Option<regex::Regex>>>,
+}
+impl<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> Evaluator<'a, I, F, M> {
+    pub fn new(root: &'a JsoncValue<I, F, M>) -> Self {
+        Self {
+            root,
+            #[cfg(feature = "regex")]
+            regex: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn evaluate(&self, query: &Query, current: &'a JsoncValue<I, F, M>) -> Vec<Located<'a, I, F, M>> {
+        let start = match query.root {
+            Root::Root => self.root,
+            Root::Current => current,
+        };
+        query.segments.iter().fold(vec![(Vec::new(), start)], |nodes, segment| {
+            let mut selected = Vec::new();
+            for (path, node) in nodes {
+                match segment {
+                    Segment::Child(selectors) => self.select(selectors, &path, node, &mut selected),
+                    Segment::Descendant(selectors) => self.descend(selectors, &path, node, &mut selected),
+                }
+            }
+            selected
+        })
+    }
+
+    /// Apply selectors to the node itself and its descendants, nodes are visited before their descendants.
+    fn descend(
+        &self,
+        selectors: &[Selector],
+        path: &[PathElement],
+        node: &'a JsoncValue<I, F, M>,
+        selected: &mut Vec<Located<'a, I, F, M>>,
+    ) {
+        self.select(selectors, path, node, selected);
+        for (element, child) in children(node) {
+            self.descend(selectors, &extend(path, element), child, selected);
+        }
+    }
+
+    fn select(
+        &self,
+        selectors: &[Selector],
+        path: &[PathElement],
+        node: &'a JsoncValue<I, F, M>,
+        selected: &mut Vec<Located<'a, I, F, M>>,
+    ) {
+        for selector in selectors {
+            match (selector, node) {
+                (Selector::Name(name), JsoncValue::Object(map)) => {
+                    if let Some(child) = map.get(name) {
+                        selected.push((extend(path, PathElement::Name(name.clone())), child));
+                    }
+                }
+                (Selector::Wildcard, _) => {
+                    selected.extend(children(node).map(|(element, child)| (extend(path, element), child)));
+                }
+                (Selector::Index(index), JsoncValue::Array(vec)) => {
+                    if let Some(i) = normalize_index(*index, vec.len()).filter(|&i| i < vec.len()) {
+                        selected.push((extend(path, PathElement::Index(i)), &vec[i]));
+                    }
+                }
+                (&Selector::Slice { start, end, step }, JsoncValue::Array(vec)) => {
+                    for i in slice_indices(start, end, step, vec.len()) {
+                        selected.push((extend(path, PathElement::Index(i)), &vec[i]));
+                    }
+                }
+                (Selector::Filter(expr), _) => {
+                    let filtered = children(node).filter(|(_, child)| self.test(expr, child));
+                    selected.extend(filtered.map(|(element, child)| (extend(path, element), child)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn test(&self, expr: &LogicalExpr, current: &'a JsoncValue<I, F, M>) -> bool {
+        match expr {
+            LogicalExpr::Or(exprs) => exprs.iter().any(|expr| self.test(expr, current)),
+            LogicalExpr::And(exprs) => exprs.iter().all(|expr| self.test(expr, current)),
+            LogicalExpr::Not(expr) => !self.test(expr, current),
+            LogicalExpr::Exist(query) => !self.evaluate(query, current).is_empty(),
+            LogicalExpr::Function(function) => self.logical(function, current),
+            LogicalExpr::Comparison(left, op, right) => {
+                let (left, right) = (self.operand(left, current), self.operand(right, current));
+                match op {
+                    ComparisonOp::Eq => left.equal(&right),
+                    ComparisonOp::Ne => !left.equal(&right),
+                    ComparisonOp::Lt => left.less(&right),
+                    ComparisonOp::Le => left.less(&right) || left.equal(&right),
+                    ComparisonOp::Gt => right.less(&left),
+                    ComparisonOp::Ge => right.less(&left) || left.equal(&right),
+                }
+            }
+        }
+    }
+
+    fn operand<'q>(&self, comparable: &'q Comparable, current: &'a JsoncValue<I, F, M>) -> Operand<'q, I, F, M>
+    where
+        'a: 'q,
+    {
+        match comparable {
+            Comparable::Literal(literal) => Operand::from_literal(literal),
+            Comparable::Query(query) => match &self.evaluate(query, current)[..] {
+                [(_, node)] => Operand::from_value(node),
+                _ => Operand::Nothing,
+            },
+            Comparable::Function(function) => self.value(function, current),
+        }
+    }
+
+    fn nodes(&self, comparable: &Comparable, current: &'a JsoncValue<I, F, M>) -> Vec<Located<'a, I, F, M>> {
+        match comparable {
+            Comparable::Query(query) => self.evaluate(query, current),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Evaluate function that returns ValueType.
+    fn value<'q>(&self, function: &'q FunctionExpr, current: &'a JsoncValue<I, F, M>) -> Operand<'q, I, F, M>
+    where
+        'a: 'q,
+    {
+        match function.function {
+            Function::Length => match self.operand(&function.args[0], current) {
+                Operand::String(s) => Operand::Number(Num::Integer(s.chars().count() as i128)),
+                Operand::Array(vec) => Operand::Number(Num::Integer(vec.len() as i128)),
+                Operand::Object(map) => Operand::Number(Num::Integer(map.len() as i128)),
+                _ => Operand::Nothing,
+            },
+            Function::Count => Operand::Number(Num::Integer(self.nodes(&function.args[0], current).len() as i128)),
+            Function::Value => match &self.nodes(&function.args[0], current)[..] {
+                [(_, node)] => Operand::from_value(node),
+                _ => Operand::Nothing,
+            },
+            Function::Match | Function::Search => Operand::Nothing,
+        }
+    }
+
+    /// Evaluate function that returns LogicalType.
+    fn logical(&self, function: &FunctionExpr, current: &'a JsoncValue<I, F, M>) -> bool {
+        match function.function {
+            #[cfg(feature = "regex")]
+            Function::Match | Function::Search => {
+                let (target, pattern) =
+                    (self.operand(&function.args[0], current), self.operand(&function.args[1], current));
+                match (target, pattern) {
+                    (Operand::String(target), Operand::String(pattern)) => {
+                        self.is_match(target, pattern, function.function == Function::Match)
+                    }
+                    _ => false,
+                }
+            }
+            _ => {
+                let _ = current;
+                false
+            }
+        }
+    }
+
+    /// Test I-Regexp (RFC 9485) pattern, invalid pattern never matches.
+    #[cfg(feature = "regex")]
+    fn is_match(&self, target: &str, pattern: &str, anchored: bool) -> bool {
+        let mut cache = self.regex.borrow_mut();
+        let regex = cache.entry((pattern.to_string(), anchored)).or_insert_with(|| {
+            let translated = translate_iregexp(pattern);
+            let translated = if anchored { format!("^(?:{translated})$") } else { translated };
+            regex::Regex::new(&translated).ok()
+        });
+        regex.as_ref().is_some_and(|regex| regex.is_match(target))
+    }
+}
+
+/// I-Regexp `.` matches any character except line terminators, and other syntax is compatible with `regex` crate.
+#[cfg(feature = "regex")]
+fn translate_iregexp(pattern: &str) -> String {
+    let (mut translated, mut in_class, mut chars) = (String::new(), false, pattern.chars());
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                translated.push(c);
+                translated.extend(chars.next());
+            }
+            '[' if !in_class => {
+                in_class = true;
+                translated.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                translated.push(c);
+            }
+            '.' if !in_class => translated.push_str(r"[^\n\r]"),
+            c => translated.push(c),
+        }
+    }
+    translated
+}
+
+fn extend(path: &[PathElement], element: PathElement) -> Vec<PathElement> {
+    let mut extended = Vec::with_capacity(path.len() + 1);
+    extended.extend_from_slice(path);
+    extended.push(element);
+    extended
+}
+
+type Children<'a, I, F, M> = Box<dyn Iterator<Item = (PathElement, &'a JsoncValue<I, F, M>)> + 'a>;
+fn children<I, F, M: MapBackend>(node: &JsoncValue<I, F, M>) -> Children<'_, I, F, M> {
+    match node {
+        JsoncValue::Object(map) => Box::new(map.iter().map(|(k, v)| (PathElement::Name(k.clone()), v))),
+        JsoncValue::Array(vec) => Box::new(vec.iter().enumerate().map(|(i, v)| (PathElement::Index(i), v))),
+        _ => Box::new(std::iter::empty()),
+    }
+}
+
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    match index {
+        0.. => Some(index as usize),
+        _ => (len as i64 + index).try_into().ok(),
+    }
+}
+
+/// Indices selected by slice, see RFC 9535 section 2.3.4.2.2.
+pub fn slice_indices(start: Option<i64>, end: Option<i64>, step: Option<i64>, len: usize) -> Vec<usize> {
+    let (len, step) = (len as i64, step.unwrap_or(1));
+    let normalize = |i: i64| if i >= 0 { i } else { len + i };
+    match step {
+        0 => Vec::new(),
+        1.. => {
+            let lower = normalize(start.unwrap_or(0)).clamp(0, len);
+            let upper = normalize(end.unwrap_or(len)).clamp(0, len);
+            (lower..upper).step_by(step as usize).map(|i| i as usize).collect()
+        }
+        _ => {
+            let upper = normalize(start.unwrap_or(len - 1)).clamp(-1, len - 1);
+            let lower = normalize(end.unwrap_or(-len - 1)).clamp(-1, len - 1);
+            std::iter::successors(Some(upper), |i| Some(i + step))
+                .take_while(|&i| i > lower)
+                .map(|i| i as usize)
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+enum Num {
+    Integer(i128),
+    Float(f64),
+}
+impl Num {
+    fn from_number<I: num::ToPrimitive, F: num::ToPrimitive>(number: &Number<I, F>) -> Self {
+        match number {
+            Number::Integer(i) => {
+                i.to_i128().map(Num::Integer).unwrap_or_else(|| Num::Float(i.to_f64().unwrap_or(f64::NAN)))
+            }
+            Number::Float(f) => Num::Float(f.to_f64().unwrap_or(f64::NAN)),
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        match self {
+            Num::Integer(i) => i as f64,
+            Num::Float(f) => f,
+        }
+    }
+
+    fn equal(self, other: Self) -> bool {
+        match (self, other) {
+            (Num::Integer(a), Num::Integer(b)) => a == b,
+            (a, b) => a.to_f64() == b.to_f64(),
+        }
+    }
+
+    fn less(self, other: Self) -> bool {
+        match (self, other) {
+            (Num::Integer(a), Num::Integer(b)) => a < b,
+            (a, b) => a.to_f64() < b.to_f64(),
+        }
+    }
+}
+
+/// Value of comparison operand, `Nothing` represents the absence of a value.
+enum Operand<'a, I, F, M: MapBackend> {
+    Nothing,
+    Null,
+    Bool(bool),
+    Number(Num),
+    String(&'a str),
+    Array(&'a [JsoncValue<I, F, M>]),
+    Object(&'a M::Map<JsoncValue<I, F, M>>),
+}
+impl<'a, I: num::ToPrimitive, F: num::ToPrimitive, M: MapBackend> Operand<'a, I, F, M> {
+    fn from_literal(literal: &'a Literal) -> Self {
+        match literal {
+            Literal::Integer(i) => Operand::Number(Num::Integer(*i)),
+            Literal::Float(f) => Operand::Number(Num::Float(*f)),
+            Literal::String(s) => Operand::String(s),
+            Literal::Bool(b) => Operand::Bool(*b),
+            Literal::Null => Operand::Null,
+        }
+    }
+
+    fn from_value(value: &'a JsoncValue<I, F, M>) -> Self {
+        match value {
+            JsoncValue::Object(map) => Operand::Object(map),
+            JsoncValue::Array(vec) => Operand::Array(vec),
+            JsoncValue::Bool(b) => Operand::Bool(*b),
+            JsoncValue::Null => Operand::Null,
+            JsoncValue::String(s) => Operand::String(s),
+            JsoncValue::Number(n) => Operand::Number(Num::from_number(n)),
+        }
+    }
+
+    fn equal(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Operand::Nothing, Operand::Nothing) | (Operand::Null, Operand::Null) => true,
+            (Operand::Bool(a), Operand::Bool(b)) => a == b,
+            (Operand::Number(a), Operand::Number(b)) => a.equal(*b),
+            (Operand::String(a), Operand::String(b)) => a == b,
+            (Operand::Array(a), Operand::Array(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b.iter()).all(|(a, b)| Operand::from_value(a).equal(&Operand::from_value(b)))
+            }
+            (Operand::Object(a), Operand::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, a)| b.get(k).is_some_and(|b| Operand::from_value(a).equal(&Operand::from_value(b))))
+            }
+            _ => false,
+        }
+    }
+
+    fn less(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Operand::Number(a), Operand::Number(b)) => a.less(*b),
+            (Operand::String(a), Operand::String(b)) => a < b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_indices() {
+        assert_eq!(slice_indices(Some(1), Some(3), None, 7), vec![1, 2]);
+        assert_eq!(slice_indices(Some(5), None, None, 7), vec![5, 6]);
+        assert_eq!(slice_indices(Some(1), Some(5), Some(2), 7), vec![1, 3]);
+        assert_eq!(slice_indices(Some(5), Some(1), Some(-2), 7), vec![5, 3]);
+        assert_eq!(slice_indices(None, None, Some(-1), 7), vec![6, 5, 4, 3, 2, 1, 0]);
+        assert_eq!(slice_indices(Some(-2), None, None, 7), vec![5, 6]);
+        assert_eq!(slice_indices(None, None, Some(0), 7), Vec::<usize>::new());
+        assert_eq!(slice_indices(Some(-100), Some(100), None, 3), vec![0, 1, 2]);
+        assert_eq!(slice_indices(None, None, None, 0), Vec::<usize>::new());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_translate_iregexp() {
+        assert_eq!(translate_iregexp("a.c"), r"a[^\n\r]c");
+        assert_eq!(translate_iregexp(r"a\.c"), r"a\.c");
+        assert_eq!(translate_iregexp("[.]"), "[.]");
+    }
+}
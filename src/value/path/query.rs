@@ -0,0 +1,602 @@
+use crate::error::PathError;
+
+/// Parsed JSONPath query, that is `$` or `@` followed by segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub root: Root,
+    pub segments: Vec<Segment>,
+}
+impl Query {
+    /// Singular query selects at most one node, it consists of only name and index selectors.
+    pub fn is_singular(&self) -> bool {
+        self.segments.iter().all(|segment| match segment {
+            Segment::Child(selectors) => {
+                matches!(selectors[..], [Selector::Name(_)] | [Selector::Index(_)])
+            }
+            Segment::Descendant(_) => false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Root {
+    /// `$`, the root node of the query argument.
+    Root,
+    /// `@`, the current node of the filter.
+    Current,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Child(Vec<Selector>),
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice { start: Option<i64>, end: Option<i64>, step: Option<i64> },
+    Filter(LogicalExpr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalExpr {
+    Or(Vec<LogicalExpr>),
+    And(Vec<LogicalExpr>),
+    Not(Box<LogicalExpr>),
+    Comparison(Comparable, ComparisonOp, Comparable),
+    Exist(Query),
+    Function(FunctionExpr),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Comparable {
+    Literal(Literal),
+    Query(Query),
+    Function(FunctionExpr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionExpr {
+    pub function: Function,
+    pub args: Vec<Comparable>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    Length,
+    Count,
+    Match,
+    Search,
+    Value,
+}
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "length" => Some(Function::Length),
+            "count" => Some(Function::Count),
+            "match" => Some(Function::Match),
+            "search" => Some(Function::Search),
+            "value" => Some(Function::Value),
+            _ => None,
+        }
+    }
+
+    fn parameters(&self) -> &'static [FunctionType] {
+        match self {
+            Function::Length => &[FunctionType::Value],
+            Function::Count | Function::Value => &[FunctionType::Nodes],
+            Function::Match | Function::Search => &[FunctionType::Value, FunctionType::Value],
+        }
+    }
+
+    fn result(&self) -> FunctionType {
+        match self {
+            Function::Length | Function::Count | Function::Value => FunctionType::Value,
+            Function::Match | Function::Search => FunctionType::Logical,
+        }
+    }
+}
+
+/// Type system of function extensions, see RFC 9535 section 2.4.1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FunctionType {
+    Value,
+    Logical,
+    Nodes,
+}
+
+/// Maximum absolute value of index and slice parameter, the range of I-JSON integer.
+const MAX_INT: i64 = (1 << 53) - 1;
+
+pub struct Parser<'q> {
+    query: &'q str,
+    pos: usize,
+}
+impl<'q> Parser<'q> {
+    pub fn new(query: &'q str) -> Self {
+        Self { query, pos: 0 }
+    }
+
+    /// Parse the whole query, that must start with `$` and must not have trailing characters.
+    pub fn parse(mut self) -> crate::Result<Query> {
+        if !self.eat("$") {
+            return self.unexpected("root identifier `$`");
+        }
+        let segments = self.parse_segments()?;
+        match self.peek() {
+            None => Ok(Query { root: Root::Root, segments }),
+            Some(_) => self.unexpected("segment or end of query"),
+        }
+    }
+
+    fn rest(&self) -> &'q str {
+        &self.query[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let starts = self.rest().starts_with(s);
+        if starts {
+            self.pos += s.len();
+        }
+        starts
+    }
+
+    fn skip_blank(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected<T>(&self, expected: &'static str) -> crate::Result<T> {
+        let found = self.peek().map(String::from).unwrap_or_default();
+        Err(PathError::UnexpectedToken { pos: self.pos, expected, found })?
+    }
+
+    fn parse_segments(&mut self) -> crate::Result<Vec<Segment>> {
+        let mut segments = Vec::new();
+        loop {
+            let start = self.pos;
+            self.skip_blank();
+            if self.eat("..") {
+                segments.push(Segment::Descendant(match self.peek() {
+                    Some('[') => self.parse_bracketed_selection()?,
+                    Some('*') => {
+                        self.next();
+                        vec![Selector::Wildcard]
+                    }
+                    _ => vec![Selector::Name(self.parse_member_name_shorthand()?)],
+                }));
+            } else if self.eat(".") {
+                segments.push(Segment::Child(match self.peek() {
+                    Some('*') => {
+                        self.next();
+                        vec![Selector::Wildcard]
+                    }
+                    _ => vec![Selector::Name(self.parse_member_name_shorthand()?)],
+                }));
+            } else if self.peek() == Some('[') {
+                segments.push(Segment::Child(self.parse_bracketed_selection()?));
+            } else {
+                self.pos = start;
+                return Ok(segments);
+            }
+        }
+    }
+
+    fn parse_member_name_shorthand(&mut self) -> crate::Result<String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() || c == '_' || !c.is_ascii() => {}
+            _ => return self.unexpected("member name"),
+        }
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || !c.is_ascii()) {
+            self.next();
+        }
+        Ok(self.query[start..self.pos].to_string())
+    }
+
+    fn parse_bracketed_selection(&mut self) -> crate::Result<Vec<Selector>> {
+        self.eat("[");
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_blank();
+            selectors.push(self.parse_selector()?);
+            self.skip_blank();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(selectors),
+                _ => {
+                    self.pos -= self.query[..self.pos].chars().next_back().map_or(0, char::len_utf8);
+                    return self.unexpected("`,` or `]`");
+                }
+            }
+        }
+    }
+
+    fn parse_selector(&mut self) -> crate::Result<Selector> {
+        match self.peek() {
+            Some('\'' | '"') => Ok(Selector::Name(self.parse_string_literal()?)),
+            Some('*') => {
+                self.next();
+                Ok(Selector::Wildcard)
+            }
+            Some('?') => {
+                self.next();
+                self.skip_blank();
+                Ok(Selector::Filter(self.parse_logical_or()?))
+            }
+            Some(':') => self.parse_slice(None),
+            Some('-' | '0'..='9') => {
+                let index = self.parse_int()?;
+                let start = self.pos;
+                self.skip_blank();
+                match self.peek() {
+                    Some(':') => self.parse_slice(Some(index)),
+                    _ => {
+                        self.pos = start;
+                        Ok(Selector::Index(index))
+                    }
+                }
+            }
+            _ => self.unexpected("selector"),
+        }
+    }
+
+    fn parse_slice(&mut self, start: Option<i64>) -> crate::Result<Selector> {
+        self.eat(":");
+        self.skip_blank();
+        let end = match self.peek() {
+            Some('-' | '0'..='9') => Some(self.parse_int()?),
+            _ => None,
+        };
+        self.skip_blank();
+        let step = match self.eat(":") {
+            true => {
+                self.skip_blank();
+                match self.peek() {
+                    Some('-' | '0'..='9') => Some(self.parse_int()?),
+                    _ => None,
+                }
+            }
+            false => None,
+        };
+        Ok(Selector::Slice { start, end, step })
+    }
+
+    /// Parse `0` or optional `-` followed by digits without leading zero.
+    fn parse_int(&mut self) -> crate::Result<i64> {
+        let start = self.pos;
+        self.eat("-");
+        match self.next() {
+            Some('0') if self.pos - start == 1 => return Ok(0),
+            Some('1'..='9') => {}
+            _ => {
+                self.pos = start;
+                return self.unexpected("integer without leading zeros");
+            }
+        }
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.next();
+        }
+        match self.query[start..self.pos].parse::<i64>() {
+            Ok(int) if int.abs() <= MAX_INT => Ok(int),
+            _ => Err(PathError::IntegerOutOfRange { pos: start })?,
+        }
+    }
+
+    fn parse_string_literal(&mut self) -> crate::Result<String> {
+        let quote = self.next();
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                None => return self.unexpected("end of string literal"),
+                Some(c) if Some(c) == quote => return Ok(string),
+                Some('\\') => match self.next() {
+                    Some('b') => string.push('\x08'),
+                    Some('f') => string.push('\x0C'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some(c @ ('/' | '\\')) => string.push(c),
+                    Some(c) if Some(c) == quote => string.push(c),
+                    Some('u') => string.push(self.parse_unicode_escape()?),
+                    _ => {
+                        self.pos -= 1;
+                        return self.unexpected("escape sequence");
+                    }
+                },
+                Some(c) if c < '\x20' => {
+                    self.pos -= 1;
+                    return self.unexpected("non-control character");
+                }
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> crate::Result<char> {
+        let high = self.parse_hex4()?;
+        let code = match high {
+            0xD800..=0xDBFF => {
+                let start = self.pos;
+                match self.eat("\\u") {
+                    true => match self.parse_hex4()? {
+                        low @ 0xDC00..=0xDFFF => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                        _ => Err(PathError::InvalidUnicode { pos: start })?,
+                    },
+                    false => Err(PathError::InvalidUnicode { pos: start })?,
+                }
+            }
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| PathError::InvalidUnicode { pos: self.pos }.into())
+    }
+
+    fn parse_hex4(&mut self) -> crate::Result<u32> {
+        let hex = self.rest().get(..4).filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()));
+        match hex.map(|h| u32::from_str_radix(h, 16)) {
+            Some(Ok(code)) => {
+                self.pos += 4;
+                Ok(code)
+            }
+            _ => self.unexpected("4 hex digits"),
+        }
+    }
+
+    fn parse_logical_or(&mut self) -> crate::Result<LogicalExpr> {
+        let mut exprs = vec![self.parse_logical_and()?];
+        loop {
+            let start = self.pos;
+            self.skip_blank();
+            if !self.eat("||") {
+                self.pos = start;
+                break;
+            }
+            self.skip_blank();
+            exprs.push(self.parse_logical_and()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.remove(0) } else { LogicalExpr::Or(exprs) })
+    }
+
+    fn parse_logical_and(&mut self) -> crate::Result<LogicalExpr> {
+        let mut exprs = vec![self.parse_basic_expr()?];
+        loop {
+            let start = self.pos;
+            self.skip_blank();
+            if !self.eat("&&") {
+                self.pos = start;
+                break;
+            }
+            self.skip_blank();
+            exprs.push(self.parse_basic_expr()?);
+        }
+        Ok(if exprs.len() == 1 { exprs.remove(0) } else { LogicalExpr::And(exprs) })
+    }
+
+    fn parse_basic_expr(&mut self) -> crate::Result<LogicalExpr> {
+        if self.eat("!") {
+            self.skip_blank();
+            let expr = match self.peek() {
+                Some('(') => self.parse_paren_expr()?,
+                _ => self.parse_test_expr()?,
+            };
+            return Ok(LogicalExpr::Not(Box::new(expr)));
+        } else if self.peek() == Some('(') {
+            return self.parse_paren_expr();
+        }
+
+        let start = self.pos;
+        let left = self.parse_comparable()?;
+        let before_op = self.pos;
+        self.skip_blank();
+        match self.parse_comparison_op() {
+            Some(op) => {
+                self.ensure_comparable(&left, start)?;
+                self.skip_blank();
+                let right_start = self.pos;
+                let right = self.parse_comparable()?;
+                self.ensure_comparable(&right, right_start)?;
+                Ok(LogicalExpr::Comparison(left, op, right))
+            }
+            None => {
+                self.pos = before_op;
+                match left {
+                    Comparable::Query(query) => Ok(LogicalExpr::Exist(query)),
+                    Comparable::Function(function) if function.function.result() == FunctionType::Logical => {
+                        Ok(LogicalExpr::Function(function))
+                    }
+                    Comparable::Function(_) => Err(PathError::NotWellTyped { pos: start })?,
+                    Comparable::Literal(_) => self.unexpected("comparison operator"),
+                }
+            }
+        }
+    }
+
+    fn parse_paren_expr(&mut self) -> crate::Result<LogicalExpr> {
+        self.eat("(");
+        self.skip_blank();
+        let expr = self.parse_logical_or()?;
+        self.skip_blank();
+        match self.eat(")") {
+            true => Ok(expr),
+            false => self.unexpected("`)`"),
+        }
+    }
+
+    fn parse_test_expr(&mut self) -> crate::Result<LogicalExpr> {
+        let start = self.pos;
+        match self.parse_comparable()? {
+            Comparable::Query(query) => Ok(LogicalExpr::Exist(query)),
+            Comparable::Function(function) if function.function.result() == FunctionType::Logical => {
+                Ok(LogicalExpr::Function(function))
+            }
+            _ => Err(PathError::NotWellTyped { pos: start })?,
+        }
+    }
+
+    fn parse_comparison_op(&mut self) -> Option<ComparisonOp> {
+        let ops = [
+            ("==", ComparisonOp::Eq),
+            ("!=", ComparisonOp::Ne),
+            ("<=", ComparisonOp::Le),
+            (">=", ComparisonOp::Ge),
+            ("<", ComparisonOp::Lt),
+            (">", ComparisonOp::Gt),
+        ];
+        ops.into_iter().find_map(|(s, op)| self.eat(s).then_some(op))
+    }
+
+    /// Comparable in comparison must be a literal, a singular query or a function that returns value.
+    fn ensure_comparable(&self, comparable: &Comparable, pos: usize) -> crate::Result<()> {
+        match comparable {
+            Comparable::Query(query) if !query.is_singular() => Err(PathError::NotWellTyped { pos })?,
+            Comparable::Function(function) if function.function.result() != FunctionType::Value => {
+                Err(PathError::NotWellTyped { pos })?
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Parse literal, filter query or function expression. Query and function are not type checked here.
+    fn parse_comparable(&mut self) -> crate::Result<Comparable> {
+        match self.peek() {
+            Some('@' | '$') => {
+                let root = if self.next() == Some('@') { Root::Current } else { Root::Root };
+                Ok(Comparable::Query(Query { root, segments: self.parse_segments()? }))
+            }
+            Some('\'' | '"') => Ok(Comparable::Literal(Literal::String(self.parse_string_literal()?))),
+            Some('-' | '0'..='9') => Ok(Comparable::Literal(self.parse_number()?)),
+            Some('a'..='z') => {
+                let start = self.pos;
+                while matches!(self.peek(), Some('a'..='z' | '0'..='9' | '_')) {
+                    self.next();
+                }
+                let name = &self.query[start..self.pos];
+                match (self.peek(), name) {
+                    (Some('('), _) => self.parse_function(name, start).map(Comparable::Function),
+                    (_, "true") => Ok(Comparable::Literal(Literal::Bool(true))),
+                    (_, "false") => Ok(Comparable::Literal(Literal::Bool(false))),
+                    (_, "null") => Ok(Comparable::Literal(Literal::Null)),
+                    _ => {
+                        self.pos = start;
+                        self.unexpected("literal, query or function")
+                    }
+                }
+            }
+            _ => self.unexpected("literal, query or function"),
+        }
+    }
+
+    fn parse_number(&mut self) -> crate::Result<Literal> {
+        let start = self.pos;
+        self.eat("-");
+        match self.next() {
+            Some('0') => {}
+            Some('1'..='9') => {
+                while matches!(self.peek(), Some('0'..='9')) {
+                    self.next();
+                }
+            }
+            _ => {
+                self.pos = start;
+                return self.unexpected("number");
+            }
+        }
+        let mut float = false;
+        if self.eat(".") {
+            float = true;
+            if !matches!(self.next(), Some('0'..='9')) {
+                self.pos -= 1;
+                return self.unexpected("digits of fraction");
+            }
+            while matches!(self.peek(), Some('0'..='9')) {
+                self.next();
+            }
+        }
+        if self.eat("e") || self.eat("E") {
+            float = true;
+            let _ = self.eat("-") || self.eat("+");
+            if !matches!(self.next(), Some('0'..='9')) {
+                self.pos -= 1;
+                return self.unexpected("digits of exponent");
+            }
+            while matches!(self.peek(), Some('0'..='9')) {
+                self.next();
+            }
+        }
+        let rep = &self.query[start..self.pos];
+        match (float, rep.parse::<i128>()) {
+            (false, Ok(int)) => Ok(Literal::Integer(int)),
+            _ => Ok(Literal::Float(rep.parse()?)),
+        }
+    }
+
+    fn parse_function(&mut self, name: &str, start: usize) -> crate::Result<FunctionExpr> {
+        let function = Function::from_name(name)
+            .ok_or_else(|| PathError::UnknownFunction { pos: start, name: name.to_string() })?;
+        #[cfg(not(feature = "regex"))]
+        if matches!(function, Function::Match | Function::Search) {
+            Err(PathError::RegexFeatureRequired { pos: start, name: name.to_string() })?
+        }
+
+        self.eat("(");
+        let mut args = Vec::new();
+        for (i, param) in function.parameters().iter().enumerate() {
+            self.skip_blank();
+            if i > 0 && !self.eat(",") {
+                return self.unexpected("`,`");
+            }
+            self.skip_blank();
+            let arg_start = self.pos;
+            let arg = self.parse_comparable()?;
+            let well_typed = match (param, &arg) {
+                (FunctionType::Value, Comparable::Literal(_)) => true,
+                (FunctionType::Value, Comparable::Query(query)) => query.is_singular(),
+                (FunctionType::Value, Comparable::Function(f)) => f.function.result() == FunctionType::Value,
+                (FunctionType::Nodes, Comparable::Query(_)) => true,
+                _ => false,
+            };
+            if !well_typed {
+                Err(PathError::NotWellTyped { pos: arg_start })?
+            }
+            args.push(arg);
+        }
+        self.skip_blank();
+        match self.eat(")") {
+            true => Ok(FunctionExpr { function, args }),
+            false => self.unexpected("`)`"),
+        }
+    }
+}
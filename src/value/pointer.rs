@@ -0,0 +1,369 @@
+use std::borrow::Cow;
+
+use crate::error::PointerError;
+
+use super::{JsoncMap, JsoncValue, MapBackend};
+
+impl<I, F, M: MapBackend> JsoncValue<I, F, M> {
+    /// Returns a reference to the value that is pointed by [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)
+    /// JSON Pointer, or `None` if the pointer is invalid or the value does not exist.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let value = jsonc!({
+    ///     "server": {"ports": [80, 443]},
+    ///     "a/b": {"m~n": true},
+    ///     "": "empty key",
+    /// });
+    /// assert_eq!(value.pointer(""), Some(&value));
+    /// assert_eq!(value.pointer("/server/ports/1"), Some(&443.into()));
+    /// assert_eq!(value.pointer("/a~1b/m~0n"), Some(&true.into()));
+    /// assert_eq!(value.pointer("/"), Some(&"empty key".into()));
+    /// assert_eq!(value.pointer("/server/ports/2"), None);
+    /// assert_eq!(value.pointer("/server/ports/01"), None);
+    /// assert_eq!(value.pointer("server"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Self> {
+        tokens(pointer).ok()?.iter().try_fold(self, |value, token| match value {
+            JsoncValue::Object(map) => map.get(&token[..]),
+            JsoncValue::Array(vec) => vec.get(array_index(token).ok()?),
+            _ => None,
+        })
+    }
+
+    /// Returns a mutable reference to the value that is pointed by JSON Pointer (see [`Self::pointer`]).
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"server": {"ports": [80, 443]}});
+    /// *value.pointer_mut("/server/ports/0").unwrap() = 8080.into();
+    /// assert_eq!(value, jsonc!({"server": {"ports": [8080, 443]}}));
+    /// assert_eq!(value.pointer_mut("/server/host"), None);
+    /// ```
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Self> {
+        tokens(pointer).ok()?.iter().try_fold(self, |value, token| match value {
+            JsoncValue::Object(map) => map.get_mut(&token[..]),
+            JsoncValue::Array(vec) => vec.get_mut(array_index(token).ok()?),
+            _ => None,
+        })
+    }
+
+    /// Insert `value` at the location that is pointed by JSON Pointer, and returns the replaced value if exists.
+    /// - if the parent is an object, the value is inserted as its entry, the existing value is replaced.
+    /// - if the parent is an array, the value is inserted at the index and the following elements are shifted,
+    ///   the `-` token or the index equal to its length appends the value.
+    /// - if intermediate values do not exist, they are created as arrays if the next token is an array index or `-`,
+    ///   and as objects otherwise. `null` intermediate values are also replaced.
+    /// - if the pointer is `""`, the whole value is replaced.
+    ///
+    /// # Errors
+    /// Returns `Err` if the pointer is invalid, or an intermediate value is neither an object, an array nor `null`,
+    /// or an array index is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"server": {"ports": [80, 443]}});
+    /// assert_eq!(value.insert_at_pointer("/server/ports/1", 8080.into()).unwrap(), None);
+    /// assert_eq!(value.insert_at_pointer("/server/ports/-", 8443.into()).unwrap(), None);
+    /// assert_eq!(value.insert_at_pointer("/server/host", "localhost".into()).unwrap(), None);
+    /// assert_eq!(value.insert_at_pointer("/server/host", "127.0.0.1".into()).unwrap(), Some("localhost".into()));
+    /// assert_eq!(value.insert_at_pointer("/log/targets/0/path", "/var/log".into()).unwrap(), None);
+    /// assert_eq!(value, jsonc!({
+    ///     "server": {"ports": [80, 8080, 443, 8443], "host": "127.0.0.1"},
+    ///     "log": {"targets": [{"path": "/var/log"}]},
+    /// }));
+    ///
+    /// assert!(value.insert_at_pointer("/server/host/name", "localhost".into()).is_err());
+    /// assert!(value.insert_at_pointer("/server/ports/10", 0.into()).is_err());
+    /// ```
+    pub fn insert_at_pointer(&mut self, pointer: &str, value: Self) -> crate::Result<Option<Self>> {
+        let tokens = tokens(pointer)?;
+        let Some((last, parents)) = tokens.split_last() else {
+            return Ok(Some(std::mem::replace(self, value)));
+        };
+        // intermediate values are created while traversing, so check the whole pointer before modifying
+        self.check_insert(pointer, &tokens)?;
+
+        let mut target = self;
+        for (i, token) in parents.iter().enumerate() {
+            if target.is_null() {
+                *target = Self::container_for(token);
+            }
+            target = match target {
+                JsoncValue::Object(map) => {
+                    map.get_or_insert_with(token.to_string(), || Self::container_for(&tokens[i + 1]))
+                }
+                JsoncValue::Array(vec) => {
+                    let index = insert_index(pointer, token, vec.len())?;
+                    if index == vec.len() {
+                        vec.push(Self::container_for(&tokens[i + 1]));
+                    }
+                    &mut vec[index]
+                }
+                _ => Err(PointerError::CannotTraverse {
+                    pointer: pointer.to_string(),
+                    token: token.to_string(),
+                    value: target.value_type(),
+                })?,
+            };
+        }
+
+        if target.is_null() {
+            *target = Self::container_for(last);
+        }
+        match target {
+            JsoncValue::Object(map) => Ok(map.insert(last.to_string(), value)),
+            JsoncValue::Array(vec) => {
+                vec.insert(insert_index(pointer, last, vec.len())?, value);
+                Ok(None)
+            }
+            _ => Err(PointerError::CannotTraverse {
+                pointer: pointer.to_string(),
+                token: last.to_string(),
+                value: target.value_type(),
+            })?,
+        }
+    }
+
+    /// Check that [`Self::insert_at_pointer`] succeeds without modifying the value,
+    /// missing or `null` values are treated as empty containers that will be created.
+    fn check_insert(&self, pointer: &str, tokens: &[Cow<'_, str>]) -> crate::Result<()> {
+        let mut target = Some(self);
+        for token in tokens {
+            target = match target {
+                Some(JsoncValue::Object(map)) => map.get(&token[..]),
+                Some(JsoncValue::Array(vec)) => vec.get(insert_index(pointer, token, vec.len())?),
+                None | Some(JsoncValue::Null) => match Self::container_for(token) {
+                    JsoncValue::Array(_) => insert_index(pointer, token, 0).map(|_| None)?,
+                    _ => None,
+                },
+                Some(value) => Err(PointerError::CannotTraverse {
+                    pointer: pointer.to_string(),
+                    token: token.to_string(),
+                    value: value.value_type(),
+                })?,
+            };
+        }
+        Ok(())
+    }
+
+    /// Remove the value that is pointed by JSON Pointer, and returns it if exists.
+    /// If the parent is an array, the following elements are shifted.
+    /// The whole value cannot be removed by `""`, and `-` does not point any element.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"server": {"ports": [80, 443], "host": "localhost"}});
+    /// assert_eq!(value.remove_at_pointer("/server/ports/0"), Some(80.into()));
+    /// assert_eq!(value.remove_at_pointer("/server/host"), Some("localhost".into()));
+    /// assert_eq!(value.remove_at_pointer("/server/host"), None);
+    /// assert_eq!(value.remove_at_pointer("/server/ports/-"), None);
+    /// assert_eq!(value.remove_at_pointer(""), None);
+    /// assert_eq!(value, jsonc!({"server": {"ports": [443]}}));
+    /// ```
+    pub fn remove_at_pointer(&mut self, pointer: &str) -> Option<Self> {
+        let (parent, last) = pointer.rsplit_once('/')?;
+        match self.pointer_mut(parent)? {
+            JsoncValue::Object(map) => map.remove(&unescape(last).ok()?),
+            JsoncValue::Array(vec) => {
+                let index = array_index(last).ok()?;
+                (index < vec.len()).then(|| vec.remove(index))
+            }
+            _ => None,
+        }
+    }
+
+    fn container_for(token: &str) -> Self {
+        if token == "-" || array_index(token).is_ok() {
+            JsoncValue::Array(Vec::new())
+        } else {
+            JsoncValue::Object(Default::default())
+        }
+    }
+}
+
+/// Split JSON Pointer into unescaped reference tokens.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::value::pointer::tokens;
+/// assert_eq!(tokens("").unwrap(), Vec::<String>::new());
+/// assert_eq!(tokens("/a~1b/m~0n/0/").unwrap(), vec!["a/b", "m~n", "0", ""]);
+/// assert!(tokens("a").is_err());
+/// assert!(tokens("/~2").is_err());
+/// ```
+pub fn tokens(pointer: &str) -> crate::Result<Vec<Cow<'_, str>>> {
+    match pointer.strip_prefix('/') {
+        None if pointer.is_empty() => Ok(Vec::new()),
+        None => Err(PointerError::InvalidPointer { pointer: pointer.to_string() })?,
+        Some(rest) => rest.split('/').map(unescape).collect(),
+    }
+}
+
+/// Index of the array with length `len` to insert a value at, `-` is the end of the array.
+fn insert_index(pointer: &str, token: &str, len: usize) -> crate::Result<usize> {
+    let index = match token {
+        "-" => len,
+        _ => array_index(token)?,
+    };
+    match index <= len {
+        true => Ok(index),
+        false => Err(PointerError::OutOfRange { pointer: pointer.to_string(), index, len })?,
+    }
+}
+
+/// Unescape `~1` to `/` and `~0` to `~` in a reference token.
+pub fn unescape(token: &str) -> crate::Result<Cow<'_, str>> {
+    if !token.contains('~') {
+        return Ok(Cow::Borrowed(token));
+    }
+    let mut unescaped = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '~' => match chars.next() {
+                Some('0') => unescaped.push('~'),
+                Some('1') => unescaped.push('/'),
+                _ => Err(PointerError::InvalidEscape { token: token.to_string() })?,
+            },
+            _ => unescaped.push(c),
+        }
+    }
+    Ok(Cow::Owned(unescaped))
+}
+
+/// Escape `~` to `~0` and `/` to `~1` in a reference token.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::value::pointer::{escape, to_pointer};
+/// assert_eq!(escape("a/b~c"), "a~1b~0c");
+/// assert_eq!(to_pointer(["server", "a/b", "0"]), "/server/a~1b/0");
+/// assert_eq!(to_pointer(Vec::<String>::new()), "");
+/// ```
+pub fn escape(token: &str) -> Cow<'_, str> {
+    if token.contains(['~', '/']) {
+        Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Build JSON Pointer from unescaped reference tokens.
+pub fn to_pointer<T: AsRef<str>>(tokens: impl IntoIterator<Item = T>) -> String {
+    tokens.into_iter().map(|token| format!("/{}", escape(token.as_ref()))).collect()
+}
+
+/// Parse array index token, that must be `0` or digits without leading zeros.
+pub fn array_index(token: &str) -> crate::Result<usize> {
+    let valid =
+        !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) && (token == "0" || !token.starts_with('0'));
+    match valid.then(|| token.parse().ok()).flatten() {
+        Some(index) => Ok(index),
+        None => Err(PointerError::InvalidArrayIndex { token: token.to_string() })?,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jsonc;
+
+    #[test]
+    fn test_rfc6901_examples() {
+        let value = jsonc!({
+            "foo": ["bar", "baz"],
+            "": 0,
+            "a/b": 1,
+            "c%d": 2,
+            "e^f": 3,
+            "g|h": 4,
+            "i\\j": 5,
+            "k\"l": 6,
+            " ": 7,
+            "m~n": 8,
+        });
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/foo"), Some(&jsonc!(["bar", "baz"])));
+        assert_eq!(value.pointer("/foo/0"), Some(&"bar".into()));
+        assert_eq!(value.pointer("/"), Some(&0.into()));
+        assert_eq!(value.pointer("/a~1b"), Some(&1.into()));
+        assert_eq!(value.pointer("/c%d"), Some(&2.into()));
+        assert_eq!(value.pointer("/e^f"), Some(&3.into()));
+        assert_eq!(value.pointer("/g|h"), Some(&4.into()));
+        assert_eq!(value.pointer("/i\\j"), Some(&5.into()));
+        assert_eq!(value.pointer("/k\"l"), Some(&6.into()));
+        assert_eq!(value.pointer("/ "), Some(&7.into()));
+        assert_eq!(value.pointer("/m~0n"), Some(&8.into()));
+    }
+
+    #[test]
+    fn test_insert_at_pointer_failure_unchanged() {
+        let mut value = jsonc!({});
+        assert!(value.insert_at_pointer("/x/y/5", true.into()).is_err());
+        assert!(value.insert_at_pointer("/x/y/0/z/-/1", true.into()).is_err());
+        assert_eq!(value, jsonc!({}));
+
+        let mut value = jsonc!({"a": [null, {"b": 1}]});
+        assert!(value.insert_at_pointer("/a/0/c/01", true.into()).is_ok());
+        assert!(value.insert_at_pointer("/a/2/c/d", true.into()).is_ok());
+        let expected = jsonc!({"a": [{"c": {"01": true}}, {"b": 1}, {"c": {"d": true}}]});
+        assert_eq!(value, expected);
+        assert!(value.insert_at_pointer("/a/4/c", true.into()).is_err());
+        assert!(value.insert_at_pointer("/a/1/b/c", true.into()).is_err());
+        assert!(value.insert_at_pointer("/a/-/c/1", true.into()).is_err());
+        assert!(value.insert_at_pointer("/new/-/~2", true.into()).is_err());
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_invalid_pointer() {
+        let value = jsonc!({"foo": ["bar"], "~/": true});
+        assert_eq!(value.pointer("foo"), None);
+        assert_eq!(value.pointer("/foo/-"), None);
+        assert_eq!(value.pointer("/foo/+0"), None);
+        assert_eq!(value.pointer("/foo/00"), None);
+        assert_eq!(value.pointer("/foo/0/bar"), None);
+        assert_eq!(value.pointer("/~"), None);
+        assert_eq!(value.pointer("/~0~1"), Some(&true.into()));
+        assert_eq!(value.pointer("/~01"), None); // `~01` is `~1`, not `/`
+    }
+
+    #[test]
+    fn test_insert_at_pointer() {
+        let mut value = jsonc!(null);
+        assert_eq!(value.insert_at_pointer("/a/0/b~1c/-", 1.into()).unwrap(), None);
+        assert_eq!(value, jsonc!({"a": [{"b/c": [1]}]}));
+        assert_eq!(value.insert_at_pointer("/a/0/b~1c/0", 0.into()).unwrap(), None);
+        assert_eq!(value.insert_at_pointer("/a/-", "end".into()).unwrap(), None);
+        assert_eq!(value.insert_at_pointer("/a/2/x", "created".into()).unwrap(), None);
+        assert_eq!(value, jsonc!({"a": [{"b/c": [0, 1]}, "end", {"x": "created"}]}));
+
+        assert_eq!(
+            value.insert_at_pointer("", jsonc!([])).unwrap(),
+            Some(jsonc!({"a": [{"b/c": [0, 1]}, "end", {"x": "created"}]}))
+        );
+        assert_eq!(value, jsonc!([]));
+
+        assert!(value.insert_at_pointer("no/slash", 0.into()).is_err());
+        assert!(value.insert_at_pointer("/01", 0.into()).is_err());
+        assert!(value.insert_at_pointer("/1", 0.into()).is_err());
+        assert!(value.insert_at_pointer("/key", 0.into()).is_err());
+        assert_eq!(value, jsonc!([]));
+    }
+
+    #[test]
+    fn test_remove_at_pointer() {
+        let mut value = jsonc!({"a": [1, 2, 3], "b": {"c~d": null}});
+        assert_eq!(value.remove_at_pointer("/a/1"), Some(2.into()));
+        assert_eq!(value.remove_at_pointer("/a/2"), None);
+        assert_eq!(value.remove_at_pointer("/b/c~0d"), Some(jsonc!(null)));
+        assert_eq!(value.remove_at_pointer("/b/c~0d"), None);
+        assert_eq!(value.remove_at_pointer("/b/~"), None);
+        assert_eq!(value.remove_at_pointer("/a/0/x"), None);
+        assert_eq!(value, jsonc!({"a": [1, 3], "b": {}}));
+    }
+}
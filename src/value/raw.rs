@@ -0,0 +1,242 @@
+use std::fmt;
+
+use serde::{de, ser, Deserialize, Serialize};
+
+use crate::error::SemanticError;
+
+/// Name of the newtype struct, that tells the deserializer to capture the source text instead of parsing it.
+pub(crate) const TOKEN: &str = "$json_with_comments::private::RawValue";
+
+/// Unparsed JSON with comments text of a value, that keeps the original text including its inner comments.
+/// Deserializing `&RawValue` borrows the source text, and `Box<RawValue>` owns it (it also works with readers).
+/// Serializing it writes the raw text back as it is.
+///
+/// # Examples
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use json_with_comments::value::RawValue;
+/// #[derive(Deserialize, Serialize)]
+/// struct Config<'a> {
+///     name: String,
+///     #[serde(borrow)]
+///     plugin: &'a RawValue,
+/// }
+/// let text = r#"{"name": "app", "plugin": {
+///     "enabled": true, // plugin specific comment
+/// }}"#;
+/// let config: Config = json_with_comments::from_str(text).unwrap();
+/// assert_eq!(config.plugin.get(), "{\n    \"enabled\": true, // plugin specific comment\n}");
+///
+/// #[derive(Deserialize)]
+/// struct Plugin {
+///     enabled: bool,
+/// }
+/// let plugin: Plugin = config.plugin.parse().unwrap();
+/// assert!(plugin.enabled);
+/// let written = json_with_comments::to_string(&config).unwrap();
+/// assert_eq!(written, format!(r#"{{"name":"app","plugin":{}}}"#, config.plugin));
+/// ```
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RawValue {
+    text: str,
+}
+impl RawValue {
+    fn from_borrowed(text: &str) -> &Self {
+        // SAFETY: `RawValue` is `#[repr(transparent)]` wrapper of `str`
+        unsafe { &*(text as *const str as *const RawValue) }
+    }
+
+    fn from_owned(text: Box<str>) -> Box<Self> {
+        // SAFETY: `RawValue` is `#[repr(transparent)]` wrapper of `str`
+        unsafe { Box::from_raw(Box::into_raw(text) as *mut RawValue) }
+    }
+
+    /// Create a [`RawValue`] from JSON with comments text, returns `Err` if the text is not a valid value.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::value::RawValue;
+    /// assert_eq!(RawValue::from_string("[1, /* two */ 2]".to_string()).unwrap().get(), "[1, /* two */ 2]");
+    /// assert!(RawValue::from_string("[1, 2".to_string()).is_err());
+    /// ```
+    pub fn from_string(text: String) -> crate::Result<Box<Self>> {
+        crate::from_str::<de::IgnoredAny>(&text)?;
+        Ok(Self::from_owned(text.into_boxed_str()))
+    }
+
+    /// Serialize `value` as minified JSON with comments text and wrap it as [`RawValue`].
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, value::RawValue};
+    /// let raw = RawValue::to_raw_value(&jsonc!([1, "two"])).unwrap();
+    /// assert_eq!(raw.get(), r#"[1,"two"]"#);
+    /// ```
+    pub fn to_raw_value<T: Serialize + ?Sized>(value: &T) -> crate::Result<Box<Self>> {
+        Ok(Self::from_owned(crate::to_string(value)?.into_boxed_str()))
+    }
+
+    /// Returns the raw text.
+    pub fn get(&self) -> &str {
+        &self.text
+    }
+
+    /// Deserialize the raw text as type `D`.
+    pub fn parse<'a, D: Deserialize<'a>>(&'a self) -> crate::Result<D> {
+        crate::from_str(&self.text)
+    }
+}
+impl Clone for Box<RawValue> {
+    fn clone(&self) -> Self {
+        (**self).to_owned()
+    }
+}
+impl ToOwned for RawValue {
+    type Owned = Box<RawValue>;
+    fn to_owned(&self) -> Self::Owned {
+        RawValue::from_owned(self.text.into())
+    }
+}
+impl fmt::Debug for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawValue").field(&&self.text).finish()
+    }
+}
+impl fmt::Display for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Serialize for RawValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, &self.text)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for &'a RawValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct BorrowedRawVisitor;
+        impl<'de> de::Visitor<'de> for BorrowedRawVisitor {
+            type Value = &'de RawValue;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a borrowed raw JSONC value")
+            }
+            fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(RawValue::from_borrowed(v))
+            }
+            fn visit_str<E: de::Error>(self, _v: &str) -> Result<Self::Value, E> {
+                Err(E::custom(SemanticError::BorrowedRawValue))
+            }
+        }
+        deserializer.deserialize_newtype_struct(TOKEN, BorrowedRawVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Box<RawValue> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct OwnedRawVisitor;
+        impl<'de> de::Visitor<'de> for OwnedRawVisitor {
+            type Value = Box<RawValue>;
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a raw JSONC value")
+            }
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(RawValue::from_owned(v.into()))
+            }
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(RawValue::from_owned(v.into_boxed_str()))
+            }
+            // other deserializers such as `JsoncValue` do not know `TOKEN`, so the value is parsed and written back
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                let value = crate::Value::deserialize(deserializer)?;
+                RawValue::to_raw_value(&value).map_err(de::Error::custom)
+            }
+        }
+        deserializer.deserialize_newtype_struct(TOKEN, OwnedRawVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{jsonc, ser::formatter::pretty::PrettyFormatter};
+
+    use super::RawValue;
+
+    #[derive(Deserialize, Serialize)]
+    struct Sections<'a> {
+        #[serde(borrow)]
+        first: &'a RawValue,
+        second: Box<RawValue>,
+        rest: Vec<Box<RawValue>>,
+    }
+
+    #[test]
+    fn test_raw_value_keeps_source() {
+        let text = r#"
+        {
+            "first": /* leading */ {"a": [1, 2,], /* inner */ "b": "\n"} // trailing
+            ,
+            "second": -1.5e3,
+            "rest": [ true , "str" , null ],
+        }"#;
+        let sections: Sections = crate::from_str(text).unwrap();
+        assert_eq!(sections.first.get(), r#"{"a": [1, 2,], /* inner */ "b": "\n"}"#);
+        assert_eq!(sections.second.get(), "-1.5e3");
+        assert_eq!(sections.rest.iter().map(|r| r.get()).collect::<Vec<_>>(), ["true", r#""str""#, "null"]);
+        assert_eq!(sections.first.parse::<crate::Value>().unwrap(), jsonc!({"a": [1, 2], "b": "\n"}));
+
+        let minified = crate::to_string(&sections).unwrap();
+        assert_eq!(
+            minified,
+            r#"{"first":{"a": [1, 2,], /* inner */ "b": "\n"},"second":-1.5e3,"rest":[true,"str",null]}"#
+        );
+        let pretty = crate::to_string_with(&sections, PrettyFormatter::new()).unwrap();
+        assert!(pretty.contains(r#""first": {"a": [1, 2,], /* inner */ "b": "\n"},"#));
+    }
+
+    #[test]
+    fn test_raw_value_from_read() {
+        #[derive(Deserialize)]
+        struct Owned {
+            raw: Box<RawValue>,
+        }
+        let owned: Owned = crate::from_read(
+            r#"{"raw": [1, // one
+            2]}"#
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(owned.raw.get(), "[1, // one\n            2]");
+    }
+
+    #[test]
+    fn test_raw_value_with_value() {
+        let value = crate::to_value(RawValue::from_string("{/* c */ \"k\": [1]}".to_string()).unwrap()).unwrap();
+        assert_eq!(value, jsonc!({"k": [1]}));
+
+        let target = jsonc!({"raw": {"k": [1]}});
+        #[derive(Deserialize)]
+        struct Owned {
+            raw: Box<RawValue>,
+        }
+        let owned: Owned = target.into_deserialize().unwrap();
+        assert_eq!(owned.raw.get(), r#"{"k":[1]}"#);
+        assert!(crate::from_str::<&RawValue>("[1,").is_err());
+    }
+}
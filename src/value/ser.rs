@@ -4,17 +4,21 @@ pub mod number;
 pub mod seq;
 pub mod serializer;
 
+use std::fmt;
+
 use serde::Serialize;
 
-use super::{number::Number, JsoncValue};
+use crate::ser::formatter::{minify::MinifyFormatter, pretty::PrettyFormatter};
+
+use super::{number::Number, JsoncMap, JsoncValue, MapBackend};
 
-impl<I: Serialize, F: Serialize> Serialize for JsoncValue<I, F> {
+impl<I: Serialize, F: Serialize, M: MapBackend> Serialize for JsoncValue<I, F, M> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         match self {
-            JsoncValue::Object(map) => map.serialize(serializer),
+            JsoncValue::Object(map) => serializer.collect_map(map.iter()),
             JsoncValue::Array(vec) => vec.serialize(serializer),
             JsoncValue::Bool(b) => b.serialize(serializer),
             JsoncValue::Null => ().serialize(serializer),
@@ -27,7 +31,28 @@ impl<I: Serialize, F: Serialize> Serialize for JsoncValue<I, F> {
     }
 }
 
-impl<I, F> JsoncValue<I, F>
+/// `{}` writes minified JSON with comments text, and `{:#}` writes pretty formatted one.
+///
+/// # Examples
+/// ```
+/// use json_with_comments::jsonc;
+/// let value = jsonc!({"name": "candy", "tags": ["sweet"]});
+/// assert_eq!(format!("{}", value["tags"]), r#"["sweet"]"#);
+/// assert_eq!(format!("{:#}", value["tags"]), "[\n  \"sweet\",\n]");
+/// assert_eq!(jsonc!("text").to_string(), r#""text""#);
+/// ```
+impl<I: Serialize, F: Serialize, M: MapBackend> fmt::Display for JsoncValue<I, F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = if f.alternate() {
+            crate::to_fmt(self, &mut *f, PrettyFormatter::new())
+        } else {
+            crate::to_fmt(self, &mut *f, MinifyFormatter::new())
+        };
+        result.map_err(|_| fmt::Error)
+    }
+}
+
+impl<I, F, M: MapBackend> JsoncValue<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
@@ -64,6 +89,16 @@ mod tests {
 
     use super::JsoncValue;
 
+    #[test]
+    fn test_display_value() {
+        let target = jsonc!({"obj": {"arr": [false, 1, 2.5, "日本語"]}});
+        assert_eq!(format!("{target}"), r#"{"obj":{"arr":[false,1,2.5,"日本語"]}}"#);
+        assert_eq!(target.to_string(), to_string(&target).unwrap());
+        assert_eq!(format!("{target:#}"), crate::to_string_pretty(&target).unwrap());
+        assert_eq!(format!("{:#}", jsonc!(null)), "null");
+        assert_eq!(format!("[{}]", jsonc!([])), "[[]]");
+    }
+
     #[test]
     fn test_serialize_value() {
         let target = jsonc!({
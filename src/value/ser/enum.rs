@@ -6,7 +6,7 @@ use serde::{
 use crate::{
     error::Ensure,
     ser::access::r#enum::Delegate,
-    value::{JsoncValue, MapImpl},
+    value::{DefaultMap, JsoncValue, MapBackend},
 };
 
 use super::{
@@ -14,24 +14,24 @@ use super::{
     seq::ValueSeqSerializer,
 };
 
-pub struct ValueEnumSerializer<I, F> {
+pub struct ValueEnumSerializer<I, F, M: MapBackend = DefaultMap> {
     key: String,
-    delegate: Delegate<ValueSeqSerializer<I, F>, ValueMapSerializer<I, F>>,
+    delegate: Delegate<ValueSeqSerializer<I, F, M>, ValueMapSerializer<I, F, M>>,
 }
 
-impl<I, F> ValueEnumSerializer<I, F> {
+impl<I, F, M: MapBackend> ValueEnumSerializer<I, F, M> {
     pub fn start_newtype_variant<S: ser::Serializer, T: ?Sized>(
         serializer: S,
         variant: &'static str,
         value: &T,
-    ) -> crate::Result<JsoncValue<I, F>>
+    ) -> crate::Result<JsoncValue<I, F, M>>
     where
-        JsoncValue<I, F>: From<S::Ok>,
+        JsoncValue<I, F, M>: From<S::Ok>,
         crate::Error: From<S::Error>,
         T: ser::Serialize,
     {
         let key = variant.serialize(ValueMapKeySerializer)?;
-        Ok(JsoncValue::Object(MapImpl::from_iter([(key, value.serialize(serializer)?.into())])))
+        Ok(JsoncValue::Object(FromIterator::from_iter([(key, value.serialize(serializer)?.into())])))
     }
 
     pub fn start_tuple_variant(variant: &str, len: usize) -> crate::Result<Self> {
@@ -42,7 +42,7 @@ impl<I, F> ValueEnumSerializer<I, F> {
         Self::start(variant, len, Delegate::<(), _>::Map(()))
     }
 
-    fn start<S, M>(variant: &str, len: usize, delegate_type: Delegate<S, M>) -> crate::Result<Self> {
+    fn start<S, P>(variant: &str, len: usize, delegate_type: Delegate<S, P>) -> crate::Result<Self> {
         let key = variant.serialize(ValueMapKeySerializer)?;
         let delegate = match delegate_type {
             Delegate::Seq(_) => Delegate::Seq(ValueSeqSerializer::start(Some(len))?),
@@ -52,12 +52,12 @@ impl<I, F> ValueEnumSerializer<I, F> {
     }
 }
 
-impl<I, F> ser::SerializeTupleVariant for ValueEnumSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeTupleVariant for ValueEnumSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
 {
-    type Ok = JsoncValue<I, F>;
+    type Ok = JsoncValue<I, F, M>;
     type Error = crate::Error;
 
     fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
@@ -75,16 +75,16 @@ where
             Delegate::Seq(seq) => seq.end()?,
             Delegate::Map(_) => Err(Ensure::SeqLikeVariant)?,
         };
-        Ok(JsoncValue::Object(MapImpl::from_iter([(self.key, value)])))
+        Ok(JsoncValue::Object(FromIterator::from_iter([(self.key, value)])))
     }
 }
 
-impl<I, F> ser::SerializeStructVariant for ValueEnumSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeStructVariant for ValueEnumSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
 {
-    type Ok = JsoncValue<I, F>;
+    type Ok = JsoncValue<I, F, M>;
     type Error = crate::Error;
 
     fn serialize_field<T: ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
@@ -102,6 +102,6 @@ where
             Delegate::Seq(_) => Err(Ensure::MapLikeVariant)?,
             Delegate::Map(map) => map.end()?,
         };
-        Ok(JsoncValue::Object(MapImpl::from_iter([(self.key, value)])))
+        Ok(JsoncValue::Object(FromIterator::from_iter([(self.key, value)])))
     }
 }
@@ -3,28 +3,28 @@ use serde::ser;
 use crate::{
     error::{Ensure, SemanticError},
     to_string,
-    value::{JsoncValue, MapImpl},
+    value::{DefaultMap, JsoncMap, JsoncValue, MapBackend},
 };
 
 use super::serializer::ValueSerializer;
 
-pub struct ValueMapSerializer<I, F> {
-    object: MapImpl<String, JsoncValue<I, F>>,
+pub struct ValueMapSerializer<I, F, M: MapBackend = DefaultMap> {
+    object: M::Map<JsoncValue<I, F, M>>,
     key: Option<String>,
 }
 
-impl<I, F> ValueMapSerializer<I, F> {
+impl<I, F, M: MapBackend> ValueMapSerializer<I, F, M> {
     pub fn start(len: Option<usize>) -> crate::Result<Self> {
-        Ok(Self { object: len.map(MapImpl::with_capacity).unwrap_or_default(), key: None })
+        Ok(Self { object: len.map(JsoncMap::with_capacity).unwrap_or_default(), key: None })
     }
 }
 
-impl<I, F> ser::SerializeMap for ValueMapSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeMap for ValueMapSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
 {
-    type Ok = JsoncValue<I, F>;
+    type Ok = JsoncValue<I, F, M>;
     type Error = crate::Error;
 
     fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<(), Self::Error>
@@ -48,7 +48,7 @@ where
     }
 }
 
-impl<I, F> ser::SerializeStruct for ValueMapSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeStruct for ValueMapSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
@@ -1,25 +1,25 @@
 use serde::ser;
 
-use crate::value::JsoncValue;
+use crate::value::{DefaultMap, JsoncValue, MapBackend};
 
 use super::serializer::ValueSerializer;
 
-pub struct ValueSeqSerializer<I, F> {
-    array: Vec<JsoncValue<I, F>>,
+pub struct ValueSeqSerializer<I, F, M: MapBackend = DefaultMap> {
+    array: Vec<JsoncValue<I, F, M>>,
 }
 
-impl<I, F> ValueSeqSerializer<I, F> {
+impl<I, F, M: MapBackend> ValueSeqSerializer<I, F, M> {
     pub fn start(len: Option<usize>) -> crate::Result<Self> {
         Ok(Self { array: len.map(Vec::with_capacity).unwrap_or_default() })
     }
 }
 
-impl<I, F> ser::SerializeSeq for ValueSeqSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeSeq for ValueSeqSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
 {
-    type Ok = JsoncValue<I, F>;
+    type Ok = JsoncValue<I, F, M>;
     type Error = crate::Error;
 
     fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error>
@@ -34,7 +34,7 @@ where
     }
 }
 
-impl<I, F> ser::SerializeTuple for ValueSeqSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeTuple for ValueSeqSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
@@ -54,7 +54,7 @@ where
     }
 }
 
-impl<I, F> ser::SerializeTupleStruct for ValueSeqSerializer<I, F>
+impl<I, F, M: MapBackend> ser::SerializeTupleStruct for ValueSeqSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
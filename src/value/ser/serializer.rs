@@ -1,14 +1,14 @@
 use serde::ser;
 
-use crate::value::JsoncValue;
+use crate::value::{DefaultMap, JsoncValue, MapBackend};
 
 use super::{map::ValueMapSerializer, number::ToNumber, r#enum::ValueEnumSerializer, seq::ValueSeqSerializer};
 
-pub struct ValueSerializer<I, F> {
-    phantom: std::marker::PhantomData<(I, F)>,
+pub struct ValueSerializer<I, F, M: MapBackend = DefaultMap> {
+    phantom: std::marker::PhantomData<(I, F, M)>,
 }
 
-impl<I, F> ValueSerializer<I, F>
+impl<I, F, M: MapBackend> ValueSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
@@ -26,20 +26,20 @@ where
     }
 }
 
-impl<I, F> ser::Serializer for ValueSerializer<I, F>
+impl<I, F, M: MapBackend> ser::Serializer for ValueSerializer<I, F, M>
 where
     I: num::FromPrimitive,
     F: num::FromPrimitive,
 {
-    type Ok = JsoncValue<I, F>;
+    type Ok = JsoncValue<I, F, M>;
     type Error = crate::Error;
-    type SerializeSeq = ValueSeqSerializer<I, F>;
-    type SerializeTuple = ValueSeqSerializer<I, F>;
-    type SerializeTupleStruct = ValueSeqSerializer<I, F>;
-    type SerializeTupleVariant = ValueEnumSerializer<I, F>;
-    type SerializeMap = ValueMapSerializer<I, F>;
-    type SerializeStruct = ValueMapSerializer<I, F>;
-    type SerializeStructVariant = ValueEnumSerializer<I, F>;
+    type SerializeSeq = ValueSeqSerializer<I, F, M>;
+    type SerializeTuple = ValueSeqSerializer<I, F, M>;
+    type SerializeTupleStruct = ValueSeqSerializer<I, F, M>;
+    type SerializeTupleVariant = ValueEnumSerializer<I, F, M>;
+    type SerializeMap = ValueMapSerializer<I, F, M>;
+    type SerializeStruct = ValueMapSerializer<I, F, M>;
+    type SerializeStructVariant = ValueEnumSerializer<I, F, M>;
 
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
         Ok(JsoncValue::Bool(v))
@@ -125,11 +125,14 @@ where
         self.serialize_str(variant)
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
+    fn serialize_newtype_struct<T: ?Sized>(self, name: &'static str, value: &T) -> Result<Self::Ok, Self::Error>
     where
         T: ser::Serialize,
     {
-        value.serialize(self)
+        match value.serialize(self)? {
+            JsoncValue::String(raw) if name == crate::value::raw::TOKEN => crate::from_str(&raw),
+            value => Ok(value),
+        }
     }
 
     fn serialize_newtype_variant<T: ?Sized>(
@@ -0,0 +1,348 @@
+use super::{
+    path::{NormalizedPath, PathElement},
+    DefaultMap, JsoncMap, JsoncValue, MapBackend,
+};
+
+impl<I, F, M: MapBackend> JsoncValue<I, F, M> {
+    /// Returns an iterator that visits every node in pre-order, that is a node is visited before its descendants.
+    /// Each item has [`NormalizedPath`] of the node, the path of the root is `$`.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let value = jsonc!({"users": [{"name": "alice", "password": "secret"}]});
+    /// let strings: Vec<_> = value.walk().filter_map(|(path, v)| Some((path.to_pointer(), v.as_str()?))).collect();
+    /// let mut sorted = strings.clone();
+    /// sorted.sort();
+    /// assert_eq!(sorted, vec![("/users/0/name".to_string(), "alice"), ("/users/0/password".to_string(), "secret")]);
+    /// assert_eq!(value.walk().count(), 5);
+    /// ```
+    pub fn walk(&self) -> Walk<'_, I, F, M> {
+        Walk { stack: vec![(Vec::new(), self)] }
+    }
+
+    /// Visit every node mutably in pre-order, see also [`Self::walk`].
+    /// Mutable references to a node and its descendants cannot be alive at the same time, so this takes a callback
+    /// instead of returning an iterator. The children of a node are visited after the callback modifies the node.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"users": [{"name": "alice", "password": "secret"}], "password": "admin"});
+    /// value.walk_mut(|path, v| {
+    ///     if matches!(path.0.last(), Some(json_with_comments::value::path::PathElement::Name(k)) if k == "password") {
+    ///         *v = "********".into();
+    ///     }
+    /// });
+    /// assert_eq!(value, jsonc!({"users": [{"name": "alice", "password": "********"}], "password": "********"}));
+    /// ```
+    pub fn walk_mut(&mut self, mut f: impl FnMut(&NormalizedPath, &mut Self)) {
+        struct WalkMut<T>(T);
+        impl<I, F, M, T> VisitorMut<I, F, M> for WalkMut<T>
+        where
+            M: MapBackend,
+            T: FnMut(&NormalizedPath, &mut JsoncValue<I, F, M>),
+        {
+            fn enter(&mut self, path: &NormalizedPath, value: &mut JsoncValue<I, F, M>) -> Control {
+                (self.0)(path, value);
+                Control::Continue
+            }
+        }
+        self.accept_mut(&mut WalkMut(&mut f));
+    }
+
+    /// Traverse the value with [`Visitor`], `enter` is called in pre-order and `leave` is called in post-order.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, value::{walk::{Control, Visitor}, path::NormalizedPath}, Value};
+    /// struct Depth { current: usize, max: usize }
+    /// impl Visitor<i64, f64> for Depth {
+    ///     fn enter(&mut self, _: &NormalizedPath, _: &Value) -> Control {
+    ///         self.current += 1;
+    ///         self.max = self.max.max(self.current);
+    ///         Control::Continue
+    ///     }
+    ///     fn leave(&mut self, _: &NormalizedPath, _: &Value) {
+    ///         self.current -= 1;
+    ///     }
+    /// }
+    /// let mut depth = Depth { current: 0, max: 0 };
+    /// jsonc!({"a": [1, {"b": [2]}], "c": 3}).accept(&mut depth);
+    /// assert_eq!(depth.max, 5);
+    /// ```
+    pub fn accept<V: Visitor<I, F, M>>(&self, visitor: &mut V) {
+        self.accept_recursive(&mut NormalizedPath::default(), visitor);
+    }
+
+    fn accept_recursive<V: Visitor<I, F, M>>(&self, path: &mut NormalizedPath, visitor: &mut V) -> bool {
+        match visitor.enter(path, self) {
+            Control::Continue => {}
+            Control::Skip => {
+                visitor.leave(path, self);
+                return true;
+            }
+            Control::Break => return false,
+        }
+        let continued = match self {
+            JsoncValue::Object(map) => map.iter().all(|(key, value)| {
+                path.0.push(PathElement::Name(key.clone()));
+                let continued = value.accept_recursive(path, visitor);
+                path.0.pop();
+                continued
+            }),
+            JsoncValue::Array(vec) => vec.iter().enumerate().all(|(index, value)| {
+                path.0.push(PathElement::Index(index));
+                let continued = value.accept_recursive(path, visitor);
+                path.0.pop();
+                continued
+            }),
+            _ => true,
+        };
+        if continued {
+            visitor.leave(path, self);
+        }
+        continued
+    }
+
+    /// Traverse the value mutably with [`VisitorMut`], see also [`Self::accept`].
+    pub fn accept_mut<V: VisitorMut<I, F, M>>(&mut self, visitor: &mut V) {
+        self.accept_mut_recursive(&mut NormalizedPath::default(), visitor);
+    }
+
+    fn accept_mut_recursive<V: VisitorMut<I, F, M>>(&mut self, path: &mut NormalizedPath, visitor: &mut V) -> bool {
+        match visitor.enter(path, self) {
+            Control::Continue => {}
+            Control::Skip => {
+                visitor.leave(path, self);
+                return true;
+            }
+            Control::Break => return false,
+        }
+        let continued = match self {
+            JsoncValue::Object(map) => map.iter_mut().all(|(key, value)| {
+                path.0.push(PathElement::Name(key.clone()));
+                let continued = value.accept_mut_recursive(path, visitor);
+                path.0.pop();
+                continued
+            }),
+            JsoncValue::Array(vec) => vec.iter_mut().enumerate().all(|(index, value)| {
+                path.0.push(PathElement::Index(index));
+                let continued = value.accept_mut_recursive(path, visitor);
+                path.0.pop();
+                continued
+            }),
+            _ => true,
+        };
+        if continued {
+            visitor.leave(path, self);
+        }
+        continued
+    }
+
+    /// Retain only the nodes for which `f` returns `true`, recursively. The root is always retained.
+    /// The descendants of a removed node are not visited, and paths are the locations before any removal.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::jsonc;
+    /// let mut value = jsonc!({"a": [1, null, {"b": null, "c": 2}], "d": null});
+    /// value.retain_recursive(|_, v| !v.is_null());
+    /// assert_eq!(value, jsonc!({"a": [1, {"c": 2}]}));
+    /// ```
+    pub fn retain_recursive(&mut self, mut f: impl FnMut(&NormalizedPath, &Self) -> bool) {
+        self.retain_recursive_inner(&mut NormalizedPath::default(), &mut f);
+    }
+
+    fn retain_recursive_inner(
+        &mut self,
+        path: &mut NormalizedPath,
+        f: &mut impl FnMut(&NormalizedPath, &Self) -> bool,
+    ) {
+        match self {
+            JsoncValue::Object(map) => map.retain(|key, value| {
+                path.0.push(PathElement::Name(key.clone()));
+                let retained = f(path, value);
+                if retained {
+                    value.retain_recursive_inner(path, f);
+                }
+                path.0.pop();
+                retained
+            }),
+            JsoncValue::Array(vec) => {
+                let mut index = 0;
+                vec.retain_mut(|value| {
+                    path.0.push(PathElement::Index(index));
+                    let retained = f(path, value);
+                    if retained {
+                        value.retain_recursive_inner(path, f);
+                    }
+                    path.0.pop();
+                    index += 1;
+                    retained
+                })
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace every leaf, that is neither an object nor an array, with the value returned by `f`.
+    ///
+    /// # Examples
+    /// ```
+    /// use json_with_comments::{jsonc, value::{number::Number, JsoncValue}};
+    /// let mut value = jsonc!({"price": 100, "discounts": [10, 0.5], "name": "item"});
+    /// value.map_leaves(|_, v| match v {
+    ///     JsoncValue::Number(Number::Integer(i)) => (i as f64).into(),
+    ///     v => v,
+    /// });
+    /// assert_eq!(value, jsonc!({"price": 100.0, "discounts": [10.0, 0.5], "name": "item"}));
+    /// ```
+    pub fn map_leaves(&mut self, mut f: impl FnMut(&NormalizedPath, Self) -> Self) {
+        self.walk_mut(|path, value| {
+            if !matches!(value, JsoncValue::Object(_) | JsoncValue::Array(_)) {
+                *value = f(path, value.take());
+            }
+        });
+    }
+}
+
+/// Whether the traversal continues, returned by `enter` of [`Visitor`] and [`VisitorMut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Control {
+    /// Visit the children of the node.
+    #[default]
+    Continue,
+    /// Do not visit the children of the node, `leave` is still called.
+    Skip,
+    /// Stop the whole traversal immediately, no more `leave` is called.
+    Break,
+}
+
+/// Visitor of [`JsoncValue::accept`].
+pub trait Visitor<I, F, M: MapBackend = DefaultMap> {
+    /// Called before the children of the node are visited.
+    fn enter(&mut self, path: &NormalizedPath, value: &JsoncValue<I, F, M>) -> Control {
+        let _ = (path, value);
+        Control::Continue
+    }
+
+    /// Called after the children of the node are visited.
+    fn leave(&mut self, path: &NormalizedPath, value: &JsoncValue<I, F, M>) {
+        let _ = (path, value);
+    }
+}
+
+/// Visitor of [`JsoncValue::accept_mut`], the node can be modified before and after its children are visited.
+pub trait VisitorMut<I, F, M: MapBackend = DefaultMap> {
+    /// Called before the children of the node are visited.
+    fn enter(&mut self, path: &NormalizedPath, value: &mut JsoncValue<I, F, M>) -> Control {
+        let _ = (path, value);
+        Control::Continue
+    }
+
+    /// Called after the children of the node are visited.
+    fn leave(&mut self, path: &NormalizedPath, value: &mut JsoncValue<I, F, M>) {
+        let _ = (path, value);
+    }
+}
+
+type Frame<'a, I, F, M> = (Vec<PathElement>, &'a JsoncValue<I, F, M>);
+
+/// Iterator of [`JsoncValue::walk`].
+#[derive(Debug, Clone)]
+pub struct Walk<'a, I, F, M: MapBackend = DefaultMap> {
+    stack: Vec<Frame<'a, I, F, M>>,
+}
+impl<'a, I, F, M: MapBackend> Iterator for Walk<'a, I, F, M> {
+    type Item = (NormalizedPath, &'a JsoncValue<I, F, M>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, value) = self.stack.pop()?;
+        let child = |element| {
+            let mut child = path.clone();
+            child.push(element);
+            child
+        };
+        match value {
+            JsoncValue::Object(map) => {
+                let children: Vec<_> = map.iter().map(|(k, v)| (child(PathElement::Name(k.clone())), v)).collect();
+                self.stack.extend(children.into_iter().rev());
+            }
+            JsoncValue::Array(vec) => {
+                let children = vec.iter().enumerate().map(|(i, v)| (child(PathElement::Index(i)), v));
+                self.stack.extend(children.rev());
+            }
+            _ => {}
+        }
+        Some((NormalizedPath(path), value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{jsonc, Value};
+
+    use super::*;
+
+    #[test]
+    fn test_walk_order() {
+        let value = jsonc!([1, [2, [3]], 4]);
+        let pointers: Vec<_> = value.walk().map(|(path, _)| path.to_pointer()).collect();
+        assert_eq!(pointers, vec!["", "/0", "/1", "/1/0", "/1/1", "/1/1/0", "/2"]);
+        assert_eq!(jsonc!(null).walk().count(), 1);
+    }
+
+    #[test]
+    fn test_visitor_control() {
+        struct Collect(Vec<String>);
+        impl Visitor<i64, f64> for Collect {
+            fn enter(&mut self, path: &NormalizedPath, value: &Value) -> Control {
+                self.0.push(format!("enter {}", path.to_pointer()));
+                match value {
+                    JsoncValue::String(s) if s == "skip" => Control::Skip,
+                    JsoncValue::String(s) if s == "break" => Control::Break,
+                    JsoncValue::Array(v) if v.is_empty() => Control::Skip,
+                    _ => Control::Continue,
+                }
+            }
+            fn leave(&mut self, path: &NormalizedPath, _: &Value) {
+                self.0.push(format!("leave {}", path.to_pointer()));
+            }
+        }
+        let mut collect = Collect(Vec::new());
+        jsonc!([[], "skip", ["break", 1], 2]).accept(&mut collect);
+        assert_eq!(collect.0, vec!["enter ", "enter /0", "leave /0", "enter /1", "leave /1", "enter /2", "enter /2/0"]);
+    }
+
+    #[test]
+    fn test_visitor_mut() {
+        struct Normalize;
+        impl VisitorMut<i64, f64> for Normalize {
+            fn leave(&mut self, _: &NormalizedPath, value: &mut Value) {
+                if let JsoncValue::Array(vec) = value {
+                    vec.sort_by_key(|v| v.as_integer().copied());
+                }
+            }
+        }
+        let mut value = jsonc!({"a": [3, 1, 2], "b": [[2, 1], 0]});
+        value.accept_mut(&mut Normalize);
+        assert_eq!(value, jsonc!({"a": [1, 2, 3], "b": [[1, 2], 0]}));
+    }
+
+    #[test]
+    fn test_retain_and_map_leaves() {
+        let mut value = jsonc!({"keep": [1, 2, 3, 4], "drop": {"x": 1}});
+        let mut visited = Vec::new();
+        value.retain_recursive(|path, v| {
+            visited.push(path.to_pointer());
+            path.to_pointer() != "/drop/x" && v.as_integer().is_none_or(|i| i % 2 == 0)
+        });
+        visited.sort();
+        assert_eq!(visited, vec!["/drop", "/drop/x", "/keep", "/keep/0", "/keep/1", "/keep/2", "/keep/3"]);
+        assert_eq!(value, jsonc!({"keep": [2, 4], "drop": {}}));
+
+        let mut value = jsonc!({"a": ["x", 1, true], "b": {}});
+        value.map_leaves(|path, _| path.to_pointer().into());
+        assert_eq!(value, jsonc!({"a": ["/a/0", "/a/1", "/a/2"], "b": {}}));
+    }
+}
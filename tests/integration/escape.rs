@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use json_with_comments::{
+    from_str, jsonc,
+    ser::formatter::{escape::EscapeStrategy, minify::MinifyFormatter, pretty::PrettyFormatter},
+    to_write, Value,
+};
+
+const TARGETS: [&str; 10] = [
+    "",
+    "plain ascii",
+    r#""double" 'single' `back`"#,
+    r"C:\Program Files\json-with-comments",
+    "// not a comment /* neither */",
+    "\x08\x0C\n\r\t \0 \x1b \x7f",
+    "<script>alert('&amp;')</script>",
+    "日本語 epsilon ε",
+    "emoji 💯🥒 and \u{10FFFF}",
+    "line separator \u{2028} paragraph separator \u{2029}",
+];
+
+fn strategies() -> [EscapeStrategy; 6] {
+    [
+        EscapeStrategy::minimal(),
+        EscapeStrategy::ascii_only(),
+        EscapeStrategy::html_safe(),
+        EscapeStrategy::minimal().slash(true),
+        EscapeStrategy::ascii_only().html(true),
+        EscapeStrategy::ascii_only().html(true).slash(true),
+    ]
+}
+
+fn minify(value: impl serde::Serialize, escape: EscapeStrategy) -> String {
+    let mut write = Vec::new();
+    to_write(value, &mut write, MinifyFormatter::with_escape(escape)).unwrap();
+    String::from_utf8(write).unwrap()
+}
+
+fn pretty(value: impl serde::Serialize, escape: EscapeStrategy) -> String {
+    let mut write = Vec::new();
+    to_write(value, &mut write, PrettyFormatter::with_escape(escape)).unwrap();
+    String::from_utf8(write).unwrap()
+}
+
+#[test]
+fn test_roundtrip_escaped_string() {
+    for escape in strategies() {
+        for target in TARGETS {
+            let jsonc = minify(target, escape);
+            assert_eq!(from_str::<String>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+            let jsonc = pretty(target, escape);
+            assert_eq!(from_str::<String>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+        }
+    }
+}
+
+#[test]
+fn test_roundtrip_escaped_map_key() {
+    for escape in strategies() {
+        let target: BTreeMap<_, _> = TARGETS.iter().enumerate().map(|(i, t)| (t.to_string(), i)).collect();
+        let jsonc = minify(&target, escape);
+        assert_eq!(from_str::<BTreeMap<String, usize>>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+        let jsonc = pretty(&target, escape);
+        assert_eq!(from_str::<BTreeMap<String, usize>>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+    }
+}
+
+#[test]
+fn test_roundtrip_escaped_value() {
+    let target = jsonc!({
+        "html": "<a href=\"https://example.com/?a=1&b=2\">link</a>",
+        "unicode": ["日本語", "💯", "\u{7f}"],
+        "<key>": {"\"quoted\"": "\\"},
+    });
+    for escape in strategies() {
+        let jsonc = minify(&target, escape);
+        assert_eq!(from_str::<Value>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+        let jsonc = pretty(&target, escape);
+        assert_eq!(from_str::<Value>(&jsonc).unwrap(), target, "{:?} with {:?}", jsonc, escape);
+    }
+}
+
+#[test]
+fn test_ascii_only_output_is_ascii() {
+    for escape in [EscapeStrategy::ascii_only(), EscapeStrategy::ascii_only().html(true)] {
+        for target in TARGETS {
+            assert!(minify(target, escape).is_ascii());
+            assert!(pretty(vec![target], escape).is_ascii());
+        }
+    }
+}
+
+#[test]
+fn test_html_safe_output_has_no_html_special_chars() {
+    for escape in [EscapeStrategy::html_safe(), EscapeStrategy::ascii_only().html(true)] {
+        for target in TARGETS {
+            let jsonc = minify(target, escape);
+            assert!(!jsonc.contains(['<', '>', '&']), "{:?}", jsonc);
+        }
+    }
+}
@@ -63,6 +63,24 @@ fn test_deserialize_from_file_content() {
     ));
 }
 
+#[test]
+fn test_deserialize_from_file_with_bom() {
+    let expected: OwnedPerson = json_with_comments::from_path("tests/data/john.json".as_ref()).unwrap();
+
+    let path = std::path::Path::new("tests/data/john_bom.json");
+    let owned_person: OwnedPerson = json_with_comments::from_path(path).unwrap();
+    assert_eq!(owned_person, expected);
+    let content = std::fs::read_to_string(path).unwrap();
+    let owned_person: OwnedPerson = json_with_comments::from_str(&content).unwrap();
+    assert_eq!(owned_person, expected);
+
+    let path = std::path::Path::new("tests/data/john_utf16le.jsonc");
+    assert!(json_with_comments::from_path::<OwnedPerson>(path).unwrap_err().to_string().contains("UTF-16LE"));
+    let decoding = json_with_comments::de::encoding::Decoding::Detect;
+    let owned_person: OwnedPerson = json_with_comments::from_path_with(path, decoding).unwrap();
+    assert_eq!(owned_person, expected);
+}
+
 #[test]
 fn test_serialize_to_file() {
     let path = std::path::Path::new("tests/data/john2.json");
@@ -83,3 +101,26 @@ fn test_serialize_to_file() {
     let deserialized: OwnedPerson = json_with_comments::from_path(path).unwrap();
     assert_eq!(person, deserialized);
 }
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_roundtrip_async_duplex() {
+    let person = OwnedPerson {
+        name: "John Doe".to_string(),
+        address: OwnedAddress { street: "Async".to_string(), number: 42 },
+        email: "async@example.com".to_string(),
+        active: false,
+    };
+    let (client, server) = tokio::io::duplex(8);
+    let formatter = json_with_comments::ser::formatter::pretty::PrettyFormatter::new();
+    let (written, deserialized) = tokio::join!(
+        json_with_comments::to_async_write(&person, client, formatter),
+        json_with_comments::from_async_read::<_, OwnedPerson>(server),
+    );
+    written.unwrap();
+    assert_eq!(deserialized.unwrap(), person);
+
+    let file = tokio::fs::File::open("tests/data/john_bom.json").await;
+    let expected: OwnedPerson = json_with_comments::from_path("tests/data/john.json".as_ref()).unwrap();
+    assert_eq!(json_with_comments::from_async_read::<_, OwnedPerson>(file.unwrap()).await.unwrap(), expected);
+}
@@ -1,5 +1,6 @@
 pub mod basic;
 pub mod edge;
+pub mod escape;
 pub mod io;
 pub mod spec;
 pub mod value;